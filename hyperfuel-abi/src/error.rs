@@ -0,0 +1,34 @@
+use std::result::Result as StdResult;
+use thiserror::Error as ThisError;
+
+#[derive(Debug, ThisError)]
+pub enum Error {
+    #[error("Failed to parse JSON ABI: {0}")]
+    ParseJson(#[from] serde_json::Error),
+    #[error("Unsupported ABI encoding version {0}.")]
+    UnsupportedEncodingVersion(String),
+    #[error("Failed to parse log id {0}.")]
+    InvalidLogId(String),
+    #[error("Concrete type {0} not found.")]
+    ConcreteTypeNotFound(String),
+    #[error("Metadata type {0} not found.")]
+    MetadataTypeNotFound(usize),
+    #[error("Concrete type {0} has no metadata type.")]
+    NoMetadataType(String),
+    #[error("Type {type_field} expects {expected} type arguments, got {got}.")]
+    TypeArgumentCount {
+        type_field: String,
+        expected: usize,
+        got: usize,
+    },
+    #[error("Unbound {0}.")]
+    UnboundGeneric(String),
+    #[error("Failed to parse the length of {0}.")]
+    InvalidLength(String),
+    #[error("{0} has no element type.")]
+    NoElementType(String),
+    #[error("Unsupported ABI type {0}.")]
+    UnsupportedType(String),
+}
+
+pub type Result<T> = StdResult<T, Error>;
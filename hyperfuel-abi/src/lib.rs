@@ -0,0 +1,13 @@
+//! Model of the Sway JSON ABI and resolution of its types, shared by `hyperfuel-client` and the
+//! `hyperfuel_abigen!` macro.
+
+mod error;
+mod program;
+mod resolve;
+
+pub use error::{Error, Result};
+pub use program::{
+    Component, ConcreteType, FunctionDecl, FunctionInput, LogId, LoggedTypeDecl, MetadataType,
+    ProgramAbi, TypeRef,
+};
+pub use resolve::{primitive, Generics, Primitive, Resolver, TypeBuilder};
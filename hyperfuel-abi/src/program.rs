@@ -0,0 +1,127 @@
+use serde::Deserialize;
+
+use crate::{Error, Result};
+
+/// JSON ABI of a Sway program, as written by `forc build` (spec version 1).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProgramAbi {
+    #[serde(default)]
+    pub encoding_version: Option<String>,
+    #[serde(default)]
+    pub concrete_types: Vec<ConcreteType>,
+    #[serde(default)]
+    pub metadata_types: Vec<MetadataType>,
+    #[serde(default)]
+    pub functions: Vec<FunctionDecl>,
+    #[serde(default)]
+    pub logged_types: Vec<LoggedTypeDecl>,
+}
+
+/// A type with all its type arguments given, referred to by its hex id.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConcreteType {
+    /// Type declaration, e.g. `struct Transfer` or `u64`.
+    #[serde(rename = "type")]
+    pub type_field: String,
+    pub concrete_type_id: String,
+    #[serde(default)]
+    pub metadata_type_id: Option<usize>,
+    #[serde(default)]
+    pub type_arguments: Vec<String>,
+}
+
+/// A possibly generic type declaration with its components, referred to by its index.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetadataType {
+    #[serde(rename = "type")]
+    pub type_field: String,
+    pub metadata_type_id: usize,
+    /// Struct fields, enum variants, tuple elements or the array element.
+    #[serde(default)]
+    pub components: Vec<Component>,
+    /// Metadata type ids of the `generic T` types that are the type parameters.
+    #[serde(default)]
+    pub type_parameters: Vec<usize>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Component {
+    #[serde(default)]
+    pub name: String,
+    pub type_id: TypeRef,
+    #[serde(default)]
+    pub type_arguments: Vec<Component>,
+}
+
+/// Components refer to concrete types by their hex id and to metadata types by their index.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum TypeRef {
+    Metadata(usize),
+    Concrete(String),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FunctionDecl {
+    pub name: String,
+    #[serde(default)]
+    pub inputs: Vec<FunctionInput>,
+    /// Concrete type id of the return type.
+    pub output: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FunctionInput {
+    pub name: String,
+    pub concrete_type_id: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoggedTypeDecl {
+    pub log_id: LogId,
+    pub concrete_type_id: String,
+}
+
+/// Log ids are written as decimal strings since they don't fit into a JSON number.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum LogId {
+    Number(u64),
+    String(String),
+}
+
+impl LogId {
+    pub fn value(&self) -> Result<u64> {
+        match self {
+            Self::Number(id) => Ok(*id),
+            Self::String(id) => id.parse().map_err(|_| Error::InvalidLogId(id.clone())),
+        }
+    }
+}
+
+impl ProgramAbi {
+    /// Parses a JSON ABI, only the encoding version 1 is supported.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let abi: Self = serde_json::from_str(json)?;
+
+        match abi.encoding_version.as_deref() {
+            None | Some("1") => Ok(abi),
+            Some(version) => Err(Error::UnsupportedEncodingVersion(version.to_owned())),
+        }
+    }
+
+    /// Returns the type declaration of the concrete type with the given id, e.g. `struct Transfer`.
+    pub fn type_name(&self, concrete_type_id: &str) -> Option<&str> {
+        self.concrete_types
+            .iter()
+            .find(|t| t.concrete_type_id == concrete_type_id)
+            .map(|t| t.type_field.as_str())
+    }
+}
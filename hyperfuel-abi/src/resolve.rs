@@ -0,0 +1,291 @@
+use std::collections::HashMap;
+
+use crate::{Component, ConcreteType, Error, MetadataType, ProgramAbi, Result, TypeRef};
+
+/// Types without components, including the std `Bytes` and `String` structs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Primitive {
+    /// `()`
+    Unit,
+    /// `bool`
+    Bool,
+    /// `u8`
+    U8,
+    /// `u16`
+    U16,
+    /// `u32`
+    U32,
+    /// `u64`
+    U64,
+    /// `u256`
+    U256,
+    /// `b256`
+    B256,
+    /// `std::bytes::Bytes`
+    Bytes,
+    /// `raw untyped slice`
+    RawSlice,
+    /// `std::string::String`
+    String,
+    /// `str`
+    StringSlice,
+    /// `str[N]`
+    StringArray(usize),
+}
+
+/// Builds the types that a [`Resolver`] resolves ABI types into.
+pub trait TypeBuilder {
+    type Type: Clone;
+
+    fn primitive(&mut self, primitive: Primitive) -> Self::Type;
+
+    /// `std::vec::Vec<T>`
+    fn vec(&mut self, elem: Self::Type) -> Self::Type;
+
+    fn tuple(&mut self, elems: Vec<Self::Type>) -> Self::Type;
+
+    fn array(&mut self, elem: Self::Type, len: usize) -> Self::Type;
+
+    /// A struct or enum applied to the type arguments `args`. Use [`Resolver::components`] to
+    /// resolve its fields or variants.
+    fn named(
+        &mut self,
+        resolver: &Resolver<'_>,
+        ty: &MetadataType,
+        args: Vec<Self::Type>,
+    ) -> Result<Self::Type>;
+}
+
+/// Types of the generic parameters in scope, by the metadata type id of the `generic T` type.
+pub type Generics<T> = HashMap<usize, T>;
+
+/// Resolves the types of an ABI, substituting generic parameters with the type arguments.
+pub struct Resolver<'a> {
+    concrete: HashMap<&'a str, &'a ConcreteType>,
+    metadata: HashMap<usize, &'a MetadataType>,
+}
+
+impl<'a> Resolver<'a> {
+    pub fn new(abi: &'a ProgramAbi) -> Self {
+        Self {
+            concrete: abi
+                .concrete_types
+                .iter()
+                .map(|t| (t.concrete_type_id.as_str(), t))
+                .collect(),
+            metadata: abi
+                .metadata_types
+                .iter()
+                .map(|t| (t.metadata_type_id, t))
+                .collect(),
+        }
+    }
+
+    pub fn concrete_type(&self, id: &str) -> Result<&'a ConcreteType> {
+        self.concrete
+            .get(id)
+            .copied()
+            .ok_or_else(|| Error::ConcreteTypeNotFound(id.to_owned()))
+    }
+
+    pub fn metadata_type(&self, id: usize) -> Result<&'a MetadataType> {
+        self.metadata
+            .get(&id)
+            .copied()
+            .ok_or(Error::MetadataTypeNotFound(id))
+    }
+
+    /// Resolves the concrete type with the given id.
+    pub fn concrete<B: TypeBuilder>(&self, b: &mut B, id: &str) -> Result<B::Type> {
+        let ty = self.concrete_type(id)?;
+
+        if let Some(primitive) = primitive(&ty.type_field)? {
+            return Ok(b.primitive(primitive));
+        }
+
+        let metadata_id = ty
+            .metadata_type_id
+            .ok_or_else(|| Error::NoMetadataType(ty.type_field.clone()))?;
+        let args = ty
+            .type_arguments
+            .iter()
+            .map(|arg| self.concrete(b, arg))
+            .collect::<Result<Vec<_>>>()?;
+
+        self.metadata_with_args(b, metadata_id, args)
+    }
+
+    /// Resolves the metadata type with the given id applied to the type arguments.
+    pub fn metadata_with_args<B: TypeBuilder>(
+        &self,
+        b: &mut B,
+        id: usize,
+        args: Vec<B::Type>,
+    ) -> Result<B::Type> {
+        let ty = self.metadata_type(id)?;
+        let type_field = ty.type_field.as_str();
+        let generics = bind(ty, args)?;
+
+        if let Some(primitive) = primitive(type_field)? {
+            return Ok(b.primitive(primitive));
+        }
+
+        match type_field {
+            "struct std::bytes::Bytes" => return Ok(b.primitive(Primitive::Bytes)),
+            "struct std::string::String" => return Ok(b.primitive(Primitive::String)),
+            "struct std::vec::Vec" => {
+                let elem = ty
+                    .type_parameters
+                    .first()
+                    .and_then(|param| generics.get(param))
+                    .ok_or_else(|| Error::NoElementType(type_field.to_owned()))?;
+                return Ok(b.vec(elem.clone()));
+            }
+            _ => (),
+        }
+
+        if type_field.starts_with("struct ") || type_field.starts_with("enum ") {
+            let args = ty
+                .type_parameters
+                .iter()
+                .map(|param| generics[param].clone())
+                .collect();
+            return b.named(self, ty, args);
+        }
+
+        self.inline(b, ty, &generics)
+    }
+
+    /// Resolves the fields of a struct or the variants of an enum applied to the type arguments,
+    /// as `(name, type)` in declaration order. Unit variants have type `()`.
+    pub fn components<B: TypeBuilder>(
+        &self,
+        b: &mut B,
+        ty: &MetadataType,
+        args: Vec<B::Type>,
+    ) -> Result<Vec<(String, B::Type)>> {
+        let generics = bind(ty, args)?;
+
+        ty.components
+            .iter()
+            .map(|c| Ok((c.name.clone(), self.component(b, c, &generics)?)))
+            .collect()
+    }
+
+    /// Resolves the type of a component, with `generics` in scope.
+    pub fn component<B: TypeBuilder>(
+        &self,
+        b: &mut B,
+        component: &Component,
+        generics: &Generics<B::Type>,
+    ) -> Result<B::Type> {
+        match &component.type_id {
+            TypeRef::Concrete(id) => self.concrete(b, id),
+            TypeRef::Metadata(id) => {
+                let ty = self.metadata_type(*id)?;
+                if ty.type_field.starts_with("generic ") {
+                    return generics
+                        .get(id)
+                        .cloned()
+                        .ok_or_else(|| Error::UnboundGeneric(ty.type_field.clone()));
+                }
+                // tuples and arrays have no type parameters, their components use the generics
+                // in scope.
+                if is_inline(&ty.type_field) {
+                    return self.inline(b, ty, generics);
+                }
+
+                let args = component
+                    .type_arguments
+                    .iter()
+                    .map(|arg| self.component(b, arg, generics))
+                    .collect::<Result<Vec<_>>>()?;
+                self.metadata_with_args(b, *id, args)
+            }
+        }
+    }
+
+    /// Resolves a tuple or array.
+    fn inline<B: TypeBuilder>(
+        &self,
+        b: &mut B,
+        ty: &MetadataType,
+        generics: &Generics<B::Type>,
+    ) -> Result<B::Type> {
+        let type_field = ty.type_field.as_str();
+        let components = ty
+            .components
+            .iter()
+            .map(|c| self.component(b, c, generics))
+            .collect::<Result<Vec<_>>>()?;
+
+        if type_field.starts_with('(') {
+            Ok(b.tuple(components))
+        } else if let Some(len) = array_len(type_field) {
+            let elem = components
+                .into_iter()
+                .next()
+                .ok_or_else(|| Error::NoElementType(type_field.to_owned()))?;
+            Ok(b.array(elem, len?))
+        } else {
+            Err(Error::UnsupportedType(type_field.to_owned()))
+        }
+    }
+}
+
+/// Binds the type parameters of `ty` to `args`.
+fn bind<T>(ty: &MetadataType, args: Vec<T>) -> Result<Generics<T>> {
+    if ty.type_parameters.len() != args.len() {
+        return Err(Error::TypeArgumentCount {
+            type_field: ty.type_field.clone(),
+            expected: ty.type_parameters.len(),
+            got: args.len(),
+        });
+    }
+
+    Ok(ty.type_parameters.iter().copied().zip(args).collect())
+}
+
+fn is_inline(type_field: &str) -> bool {
+    (type_field.starts_with('(') && type_field != "()") || array_len(type_field).is_some()
+}
+
+/// Returns the primitive type of a type declaration, or `None` if it isn't one.
+pub fn primitive(type_field: &str) -> Result<Option<Primitive>> {
+    let primitive = match type_field {
+        "()" => Primitive::Unit,
+        "bool" => Primitive::Bool,
+        "u8" => Primitive::U8,
+        "u16" => Primitive::U16,
+        "u32" => Primitive::U32,
+        "u64" => Primitive::U64,
+        "u256" => Primitive::U256,
+        "b256" => Primitive::B256,
+        "str" => Primitive::StringSlice,
+        "raw untyped slice" => Primitive::RawSlice,
+        _ => match type_field
+            .strip_prefix("str[")
+            .and_then(|s| s.strip_suffix(']'))
+        {
+            Some(len) => Primitive::StringArray(
+                len.parse()
+                    .map_err(|_| Error::InvalidLength(type_field.to_owned()))?,
+            ),
+            None => return Ok(None),
+        },
+    };
+
+    Ok(Some(primitive))
+}
+
+/// Parses the length of an array type, which is written as `[_; 3]`.
+fn array_len(type_field: &str) -> Option<Result<usize>> {
+    let len = type_field.strip_prefix('[')?.strip_suffix(']')?;
+    let (_, len) = len.split_once(';')?;
+
+    Some(
+        len.trim()
+            .parse()
+            .map_err(|_| Error::InvalidLength(type_field.to_owned())),
+    )
+}
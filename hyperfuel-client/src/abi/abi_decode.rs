@@ -0,0 +1,165 @@
+use std::{fmt, ops::Deref};
+
+use anyhow::{anyhow, Result};
+use hyperfuel_format::Hash;
+use ruint::aliases::U256;
+
+use super::param_type::{take, take_len, take_str, take_vec_len};
+
+/// Decoding of Rust types from Sway program data, implemented by the types that
+/// [`hyperfuel_abigen!`](crate::hyperfuel_abigen) generates.
+///
+/// Sway types map to `()`, `bool`, `u8`, `u16`, `u32`, `u64`, [`U256`], [`Hash`] (`b256`),
+/// `Vec<u8>` (`Bytes` and `raw untyped slice`), `String` (`String` and `str`), [`SizedStr`]
+/// (`str[N]`), arrays, tuples, `Vec<T>`, `Option<T>` and `Result<T, E>`.
+///
+/// Only the encoding version 1 is supported.
+pub trait AbiDecode: Sized {
+    /// Decodes a value from the start of `buf` and advances it past the value.
+    fn decode_from(buf: &mut &[u8]) -> Result<Self>;
+
+    /// Decodes a value from `data`, which must contain exactly one encoded value.
+    fn decode(data: &[u8]) -> Result<Self> {
+        let mut buf = data;
+        let value = Self::decode_from(&mut buf)?;
+
+        if !buf.is_empty() {
+            return Err(anyhow!("{} trailing bytes after decoding value", buf.len()));
+        }
+
+        Ok(value)
+    }
+}
+
+/// A Sway `str[N]`, which is encoded without a length prefix.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SizedStr<const N: usize>(pub String);
+
+impl<const N: usize> Deref for SizedStr<N> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl<const N: usize> fmt::Display for SizedStr<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AbiDecode for () {
+    fn decode_from(_buf: &mut &[u8]) -> Result<Self> {
+        Ok(())
+    }
+}
+
+impl AbiDecode for bool {
+    fn decode_from(buf: &mut &[u8]) -> Result<Self> {
+        match take::<1>(buf)? {
+            [0] => Ok(false),
+            [1] => Ok(true),
+            [b] => Err(anyhow!("invalid bool value {b}")),
+        }
+    }
+}
+
+macro_rules! impl_abi_decode_int {
+    ($($ty:ty),*) => {
+        $(
+            impl AbiDecode for $ty {
+                fn decode_from(buf: &mut &[u8]) -> Result<Self> {
+                    Ok(<$ty>::from_be_bytes(take(buf)?))
+                }
+            }
+        )*
+    };
+}
+
+impl_abi_decode_int!(u8, u16, u32, u64);
+
+impl AbiDecode for U256 {
+    fn decode_from(buf: &mut &[u8]) -> Result<Self> {
+        Ok(U256::from_be_bytes(take::<32>(buf)?))
+    }
+}
+
+impl AbiDecode for Hash {
+    fn decode_from(buf: &mut &[u8]) -> Result<Self> {
+        Ok(take::<32>(buf)?.into())
+    }
+}
+
+impl AbiDecode for String {
+    fn decode_from(buf: &mut &[u8]) -> Result<Self> {
+        let len = take_len(buf)?;
+        take_str(buf, len)
+    }
+}
+
+impl<const N: usize> AbiDecode for SizedStr<N> {
+    fn decode_from(buf: &mut &[u8]) -> Result<Self> {
+        take_str(buf, N).map(Self)
+    }
+}
+
+/// `Vec<u8>` is also used for `Bytes`, which has the same encoding.
+impl<T: AbiDecode> AbiDecode for Vec<T> {
+    fn decode_from(buf: &mut &[u8]) -> Result<Self> {
+        let len = take_vec_len(buf)?;
+        let mut values = Vec::with_capacity(len);
+        for _ in 0..len {
+            values.push(T::decode_from(buf)?);
+        }
+        Ok(values)
+    }
+}
+
+impl<T: AbiDecode, const N: usize> AbiDecode for [T; N] {
+    fn decode_from(buf: &mut &[u8]) -> Result<Self> {
+        let values = (0..N)
+            .map(|_| T::decode_from(buf))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(values.try_into().ok().unwrap())
+    }
+}
+
+impl<T: AbiDecode> AbiDecode for Option<T> {
+    fn decode_from(buf: &mut &[u8]) -> Result<Self> {
+        match u64::decode_from(buf)? {
+            0 => Ok(None),
+            1 => T::decode_from(buf).map(Some),
+            d => Err(anyhow!("invalid discriminant {d} for Option")),
+        }
+    }
+}
+
+impl<T: AbiDecode, E: AbiDecode> AbiDecode for Result<T, E> {
+    fn decode_from(buf: &mut &[u8]) -> Result<Self> {
+        match u64::decode_from(buf)? {
+            0 => T::decode_from(buf).map(Ok),
+            1 => E::decode_from(buf).map(Err),
+            d => Err(anyhow!("invalid discriminant {d} for Result")),
+        }
+    }
+}
+
+macro_rules! impl_abi_decode_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: AbiDecode),+> AbiDecode for ($($name,)+) {
+            fn decode_from(buf: &mut &[u8]) -> Result<Self> {
+                Ok(($($name::decode_from(buf)?,)+))
+            }
+        }
+    };
+}
+
+impl_abi_decode_tuple!(A);
+impl_abi_decode_tuple!(A, B);
+impl_abi_decode_tuple!(A, B, C);
+impl_abi_decode_tuple!(A, B, C, D);
+impl_abi_decode_tuple!(A, B, C, D, E);
+impl_abi_decode_tuple!(A, B, C, D, E, F);
+impl_abi_decode_tuple!(A, B, C, D, E, F, G);
+impl_abi_decode_tuple!(A, B, C, D, E, F, G, H);
@@ -0,0 +1,457 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use anyhow::{Context, Result};
+use hyperfuel_abi::ProgramAbi;
+use hyperfuel_format::{Hash, Receipt, ReceiptType};
+use hyperfuel_net_types::Sighash;
+use sha2::{Digest, Sha256};
+
+use super::{program::resolve, AbiDecode, DecodedValue, ParamType};
+
+/// A function of a Sway contract ABI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AbiFunction {
+    /// Name of the function.
+    pub name: String,
+    /// Argument names and types, in declaration order.
+    pub inputs: Vec<(String, ParamType)>,
+    /// Type of the return value.
+    pub output: ParamType,
+}
+
+impl AbiFunction {
+    /// Returns the function selector, see [`fn_selector`].
+    pub fn selector(&self) -> Vec<u8> {
+        fn_selector(&self.name)
+    }
+}
+
+/// A decoded `Call` receipt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedCall {
+    /// Contract that is called.
+    pub contract_id: Hash,
+    /// Name of the called function.
+    pub function: String,
+    /// Argument names and values, `None` if the function isn't in the ABI.
+    pub args: Option<Vec<(String, DecodedValue)>>,
+}
+
+/// Returns the selector of a function of a program built with encoding version 1.
+///
+/// The selector is the function name with a `u64` length prefix. The `param1` register of a
+/// `Call` receipt points to it in the VM memory.
+pub fn fn_selector(name: &str) -> Vec<u8> {
+    let mut selector = Vec::with_capacity(8 + name.len());
+    selector.extend((name.len() as u64).to_be_bytes());
+    selector.extend(name.as_bytes());
+    selector
+}
+
+/// Returns the selector of a function of a program built with encoding version 0, the first
+/// four bytes of the sha256 of its signature, e.g. `transfer(u64,b256)`.
+///
+/// The `param1` register of a `Call` receipt holds it as a number, see [`Sighash`].
+pub fn legacy_fn_selector(signature: &str) -> Sighash {
+    let hash = Sha256::digest(signature.as_bytes());
+    Sighash::from(<[u8; 4]>::try_from(&hash[..4]).unwrap())
+}
+
+/// Decodes `Call` and `ReturnData` receipts using the functions of a Sway JSON ABI.
+#[derive(Debug, Clone)]
+pub struct CallDecoder {
+    functions: BTreeMap<String, Arc<AbiFunction>>,
+}
+
+impl CallDecoder {
+    /// Creates a decoder from a Sway JSON ABI as written by `forc build`.
+    pub fn from_json_abi(json: &str) -> Result<Self> {
+        let abi = ProgramAbi::from_json(json)?;
+
+        let functions = abi
+            .functions
+            .iter()
+            .map(|decl| {
+                let inputs = decl
+                    .inputs
+                    .iter()
+                    .map(|input| Ok((input.name.clone(), resolve(&abi, &input.concrete_type_id)?)))
+                    .collect::<Result<_>>()
+                    .with_context(|| format!("resolve inputs of {}", decl.name))?;
+                let output = resolve(&abi, &decl.output)
+                    .with_context(|| format!("resolve output of {}", decl.name))?;
+
+                Ok((
+                    decl.name.clone(),
+                    Arc::new(AbiFunction {
+                        name: decl.name.clone(),
+                        inputs,
+                        output,
+                    }),
+                ))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self { functions })
+    }
+
+    /// Returns the function with the given name.
+    pub fn function(&self, name: &str) -> Option<&AbiFunction> {
+        self.functions.get(name).map(AsRef::as_ref)
+    }
+
+    /// Returns all functions, ordered by name.
+    pub fn functions(&self) -> impl Iterator<Item = &AbiFunction> {
+        self.functions.values().map(AsRef::as_ref)
+    }
+
+    /// Decodes a `Call` receipt using the `script_data` of its transaction.
+    ///
+    /// The selector and the arguments are read from the call data that the SDKs put into the
+    /// script data, which is located using the `param1` and `param2` registers of the receipt.
+    ///
+    /// Returns `None` if the receipt isn't a `Call` receipt or its call data can't be found in
+    /// the script data, e.g. if the call isn't made by the SDK's call script or the program is
+    /// built with encoding version 0.
+    pub fn decode_call(&self, call: &Receipt, script_data: &[u8]) -> Result<Option<DecodedCall>> {
+        let Some((function, args_data)) = call_data(call, script_data) else {
+            return Ok(None);
+        };
+        let contract_id = call.to.clone().context("call receipt without to field")?;
+
+        let args = match self.functions.get(function) {
+            Some(function) => {
+                let mut buf = args_data;
+                let args = function
+                    .inputs
+                    .iter()
+                    .map(|(name, ty)| {
+                        let value = ty
+                            .decode_from(&mut buf)
+                            .with_context(|| format!("decode argument {name}"))?;
+                        Ok((name.clone(), value))
+                    })
+                    .collect::<Result<_>>()
+                    .with_context(|| format!("decode arguments of {}", function.name))?;
+                Some(args)
+            }
+            None => None,
+        };
+
+        Ok(Some(DecodedCall {
+            contract_id,
+            function: function.to_owned(),
+            args,
+        }))
+    }
+
+    /// Decodes the return value of a call to `function` from a `ReturnData` receipt.
+    ///
+    /// Returns `None` if the receipt isn't a `ReturnData` receipt or the function isn't in the
+    /// ABI.
+    pub fn decode_return(&self, function: &str, receipt: &Receipt) -> Result<Option<DecodedValue>> {
+        let Some(function) = self.functions.get(function) else {
+            return Ok(None);
+        };
+        let Some(data) = return_data(receipt) else {
+            return Ok(None);
+        };
+
+        function
+            .output
+            .decode(data)
+            .with_context(|| format!("decode return value of {}", function.name))
+            .map(Some)
+    }
+}
+
+/// Decodes the data of a `ReturnData` receipt into a typed value.
+///
+/// Returns `None` if the receipt isn't a `ReturnData` receipt.
+pub fn decode_return_data<T: AbiDecode>(receipt: &Receipt) -> Result<Option<T>> {
+    return_data(receipt).map(T::decode).transpose()
+}
+
+fn return_data(receipt: &Receipt) -> Option<&[u8]> {
+    if receipt.receipt_type != ReceiptType::ReturnData {
+        return None;
+    }
+    receipt.data.as_ref().map(AsRef::as_ref)
+}
+
+/// Returns the called function name and the encoded arguments of a `Call` receipt.
+///
+/// The SDK's call script writes `param1` and `param2` into the script data, followed by the
+/// gas forwarded to the call if it is set and then the selector and the arguments they point
+/// to, so the call data is found by searching for them. Since the gas word is optional, the
+/// selector is looked for right after the offsets first and after the gas word otherwise. Reading
+/// the gas word as a selector length can't produce a match, since the name would then start with
+/// the zero bytes of the real selector length and names are identifiers.
+/// The arguments run to the end of the script data, decoding stops after the last argument.
+pub(crate) fn call_data<'a>(call: &Receipt, script_data: &'a [u8]) -> Option<(&'a str, &'a [u8])> {
+    if call.receipt_type != ReceiptType::Call {
+        return None;
+    }
+    let param1 = **call.param1.as_ref()?;
+    let param2 = **call.param2.as_ref()?;
+    let selector_len = usize::try_from(param2.checked_sub(param1)?).ok()?;
+
+    let mut pattern = [0; 16];
+    pattern[..8].copy_from_slice(&param1.to_be_bytes());
+    pattern[8..].copy_from_slice(&param2.to_be_bytes());
+
+    let start = script_data
+        .windows(pattern.len())
+        .position(|w| w == pattern)?
+        + pattern.len();
+
+    [0, 8].into_iter().find_map(|gas_len| {
+        let rest = script_data.get(start + gas_len..)?;
+        let (selector, args) = (rest.get(..selector_len)?, rest.get(selector_len..)?);
+
+        let (len, name) = selector.split_first_chunk::<8>()?;
+        if u64::from_be_bytes(*len) != name.len() as u64 || !is_ident(name) {
+            return None;
+        }
+        let name = std::str::from_utf8(name).ok()?;
+
+        Some((name, args))
+    })
+}
+
+fn is_ident(name: &[u8]) -> bool {
+    !name.is_empty() && name.iter().all(|b| b.is_ascii_alphanumeric() || *b == b'_')
+}
+
+/// Pairs the `Call` receipts of a transaction with the `Return` or `ReturnData` receipts of the
+/// called contracts.
+///
+/// Takes the receipts of a single transaction in order and returns the index of each call
+/// receipt with the index of its return receipt, if any. Calls that don't return, e.g.
+/// because they revert, have no return receipt.
+pub(crate) fn pair_calls(receipts: &[Receipt]) -> Vec<(usize, Option<usize>)> {
+    let mut pairs = Vec::new();
+    let mut stack: Vec<(usize, &Hash)> = Vec::new();
+
+    for (idx, receipt) in receipts.iter().enumerate() {
+        match receipt.receipt_type {
+            ReceiptType::Call => {
+                if let Some(to) = &receipt.to {
+                    pairs.push((idx, None));
+                    stack.push((pairs.len() - 1, to));
+                }
+            }
+            ReceiptType::Return | ReceiptType::ReturnData => {
+                let Some(contract_id) = &receipt.contract_id else {
+                    continue;
+                };
+                match stack.last() {
+                    Some((pair, to)) if *to == contract_id => {
+                        pairs[*pair].1 = Some(idx);
+                        stack.pop();
+                    }
+                    _ => (),
+                }
+            }
+            _ => (),
+        }
+    }
+
+    pairs
+}
+
+impl DecodedCall {
+    /// Returns the argument with the given name.
+    pub fn arg(&self, name: &str) -> Option<&DecodedValue> {
+        self.args
+            .as_ref()?
+            .iter()
+            .find(|(arg, _)| arg == name)
+            .map(|(_, value)| value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTRACT: [u8; 32] = [3; 32];
+    const SELECTOR_PTR: u64 = 10_376;
+
+    fn decoder() -> CallDecoder {
+        CallDecoder::from_json_abi(include_str!("../../test-data/logs-abi.json")).unwrap()
+    }
+
+    /// Script data laid out the way the SDK's call script expects it.
+    fn script_data(function: &str, gas_forwarded: Option<u64>, args: &[u8]) -> Vec<u8> {
+        let selector = fn_selector(function);
+
+        let mut data = 0u64.to_be_bytes().to_vec();
+        data.extend([0; 32]);
+        data.extend(CONTRACT);
+        data.extend(SELECTOR_PTR.to_be_bytes());
+        data.extend((SELECTOR_PTR + selector.len() as u64).to_be_bytes());
+        if let Some(gas) = gas_forwarded {
+            data.extend(gas.to_be_bytes());
+        }
+        data.extend(selector);
+        data.extend(args);
+        data
+    }
+
+    fn call(function: &str, to: [u8; 32]) -> Receipt {
+        Receipt {
+            receipt_type: ReceiptType::Call,
+            to: Some(to.into()),
+            param1: Some(SELECTOR_PTR.into()),
+            param2: Some((SELECTOR_PTR + 8 + function.len() as u64).into()),
+            ..Default::default()
+        }
+    }
+
+    fn ret(receipt_type: ReceiptType, contract_id: [u8; 32]) -> Receipt {
+        Receipt {
+            receipt_type,
+            contract_id: Some(contract_id.into()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_selectors() {
+        assert_eq!(fn_selector("transfer"), b"\0\0\0\0\0\0\0\x08transfer");
+        assert_eq!(
+            legacy_fn_selector("transfer(u64,b256)"),
+            Sighash::from([40, 199, 221, 139])
+        );
+        assert_eq!(
+            decoder().function("transfer").unwrap().selector(),
+            fn_selector("transfer")
+        );
+    }
+
+    #[test]
+    fn test_decode_call() {
+        let decoder = decoder();
+
+        let mut args = 5u64.to_be_bytes().to_vec();
+        args.extend(2u64.to_be_bytes());
+        args.extend(b"hi");
+        // data of the next call in the script data is ignored.
+        args.extend([9; 16]);
+
+        let decoded = decoder
+            .decode_call(
+                &call("transfer", CONTRACT),
+                &script_data("transfer", None, &args),
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(decoded.contract_id, Hash::from(CONTRACT));
+        assert_eq!(decoded.function, "transfer");
+        assert_eq!(decoded.arg("amount"), Some(&DecodedValue::U64(5)));
+        assert_eq!(
+            decoded.arg("memo"),
+            Some(&DecodedValue::String("hi".to_owned()))
+        );
+
+        // functions that aren't in the abi are still named.
+        let decoded = decoder
+            .decode_call(&call("mint", CONTRACT), &script_data("mint", None, &[]))
+            .unwrap()
+            .unwrap();
+        assert_eq!(decoded.function, "mint");
+        assert_eq!(decoded.args, None);
+
+        // call data isn't in the script data.
+        assert_eq!(
+            decoder
+                .decode_call(&call("transfer", CONTRACT), &script_data("mint", None, &[]))
+                .unwrap(),
+            None
+        );
+
+        assert!(decoder
+            .decode_call(
+                &call("transfer", CONTRACT),
+                &script_data("transfer", None, &[1])
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_decode_call_gas_forwarded() {
+        let decoder = decoder();
+
+        let mut args = 7u64.to_be_bytes().to_vec();
+        args.extend(0u64.to_be_bytes());
+
+        let decoded = decoder
+            .decode_call(
+                &call("transfer", CONTRACT),
+                &script_data("transfer", Some(1_000_000), &args),
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(decoded.function, "transfer");
+        assert_eq!(decoded.arg("amount"), Some(&DecodedValue::U64(7)));
+        assert_eq!(
+            decoded.arg("memo"),
+            Some(&DecodedValue::String(String::new()))
+        );
+
+        // a gas word that looks like a selector length doesn't shift the call data.
+        let decoded = decoder
+            .decode_call(
+                &call("transfer", CONTRACT),
+                &script_data("transfer", Some(8), &args),
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(decoded.function, "transfer");
+        assert_eq!(decoded.arg("amount"), Some(&DecodedValue::U64(7)));
+    }
+
+    #[test]
+    fn test_decode_return() {
+        let decoder = decoder();
+        let receipt = Receipt {
+            data: Some(vec![1].into()),
+            ..ret(ReceiptType::ReturnData, CONTRACT)
+        };
+
+        assert_eq!(
+            decoder.decode_return("transfer", &receipt).unwrap(),
+            Some(DecodedValue::Bool(true))
+        );
+        assert_eq!(decode_return_data::<bool>(&receipt).unwrap(), Some(true));
+        assert!(decode_return_data::<u64>(&receipt).is_err());
+
+        assert_eq!(decoder.decode_return("mint", &receipt).unwrap(), None);
+        assert_eq!(
+            decoder
+                .decode_return("transfer", &ret(ReceiptType::Return, CONTRACT))
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_pair_calls() {
+        let other = [4; 32];
+        let receipts = vec![
+            call("transfer", CONTRACT),
+            call("transfer", other),
+            ret(ReceiptType::ReturnData, other),
+            ret(ReceiptType::Return, CONTRACT),
+            call("transfer", CONTRACT),
+            // script return.
+            ret(ReceiptType::Return, [0; 32]),
+        ];
+
+        assert_eq!(
+            pair_calls(&receipts),
+            [(0, Some(3)), (1, Some(2)), (4, None)]
+        );
+    }
+}
@@ -0,0 +1,185 @@
+use std::fmt;
+
+use anyhow::Result;
+use hyperfuel_format::{Hash, PanicInstruction, Receipt, ReceiptType, RevertSignal};
+
+use super::{DecodedValue, LogDecoder};
+
+/// Cause of a failed call, decoded from a Panic or Revert receipt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureCause {
+    /// The VM panicked.
+    Panic(PanicInstruction),
+    /// The program reverted.
+    Revert(RevertSignal),
+}
+
+/// A Panic or Revert receipt joined with the values that were logged for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedFailure {
+    /// Transaction id of the receipt.
+    pub tx_id: Hash,
+    /// Index of the Panic or Revert receipt within the transaction.
+    pub receipt_index: u64,
+    /// Contract that failed, `None` if the script failed.
+    pub contract_id: Option<Hash>,
+    /// Decoded cause.
+    pub cause: FailureCause,
+    /// Values logged right before reverting, e.g. the error of a failed `require` or both
+    /// values of a failed `assert_eq`. Empty if they aren't logged types of the ABI.
+    pub logged_values: Vec<DecodedValue>,
+}
+
+impl fmt::Display for FailureCause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Panic(panic) => write!(f, "panic {panic}"),
+            Self::Revert(signal) => signal.fmt(f),
+        }
+    }
+}
+
+impl fmt::Display for DecodedFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.cause.fmt(f)?;
+        for (i, value) in self.logged_values.iter().enumerate() {
+            f.write_str(if i == 0 { ": " } else { ", " })?;
+            value.to_json().fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl LogDecoder {
+    /// Decodes the Panic and Revert receipts in `receipts`.
+    ///
+    /// Reverts of `require`, `assert_eq`, `assert_ne` and `revert_with_log` are joined with the
+    /// LogData receipts of the same contract right before them, which carry the error values.
+    /// `receipts` need to be ordered by transaction and receipt index, like in a response.
+    pub fn decode_failures(&self, receipts: &[Receipt]) -> Result<Vec<DecodedFailure>> {
+        let mut failures = Vec::new();
+
+        for (idx, receipt) in receipts.iter().enumerate() {
+            let cause = match (receipt.panic_instruction(), receipt.revert_signal()) {
+                (Some(panic), _) => FailureCause::Panic(panic),
+                (None, Some(signal)) => FailureCause::Revert(signal),
+                (None, None) => continue,
+            };
+
+            let num_logged = match cause {
+                FailureCause::Revert(signal) => signal.num_logged_values(),
+                FailureCause::Panic(_) => 0,
+            };
+            let logs = receipts[..idx]
+                .iter()
+                .rev()
+                .take(num_logged)
+                .take_while(|log| {
+                    log.receipt_type == ReceiptType::LogData
+                        && log.tx_id == receipt.tx_id
+                        && log.contract_id == receipt.contract_id
+                })
+                .collect::<Vec<_>>();
+
+            let mut logged_values = Vec::with_capacity(logs.len());
+            for log in logs.into_iter().rev() {
+                if let Some(value) = self.decode_receipt(log)? {
+                    logged_values.push(value);
+                }
+            }
+
+            failures.push(DecodedFailure {
+                tx_id: receipt.tx_id.clone(),
+                receipt_index: *receipt.receipt_index,
+                contract_id: receipt.contract_id.clone(),
+                cause,
+                logged_values,
+            });
+        }
+
+        Ok(failures)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hyperfuel_format::PanicReason;
+
+    const STATUS_LOG_ID: u64 = 4363211024530769387;
+    const U64_LOG_ID: u64 = 1515152261580153489;
+
+    fn decoder() -> LogDecoder {
+        LogDecoder::from_json_abi(include_str!("../../test-data/logs-abi.json")).unwrap()
+    }
+
+    fn receipt(receipt_index: u64, receipt_type: ReceiptType) -> Receipt {
+        Receipt {
+            receipt_index: receipt_index.into(),
+            receipt_type,
+            contract_id: Some([3; 32].into()),
+            ..Default::default()
+        }
+    }
+
+    fn log(receipt_index: u64, log_id: u64, data: Vec<u8>) -> Receipt {
+        Receipt {
+            rb: Some(log_id.into()),
+            data: Some(data.into()),
+            ..receipt(receipt_index, ReceiptType::LogData)
+        }
+    }
+
+    #[test]
+    fn test_decode_failures() {
+        let receipts = vec![
+            log(0, U64_LOG_ID, 1u64.to_be_bytes().to_vec()),
+            log(1, U64_LOG_ID, 2u64.to_be_bytes().to_vec()),
+            Receipt {
+                ra: Some(0xffff_ffff_ffff_0003.into()),
+                ..receipt(2, ReceiptType::Revert)
+            },
+            log(
+                3,
+                STATUS_LOG_ID,
+                1u64.to_be_bytes()
+                    .into_iter()
+                    .chain(7u64.to_be_bytes())
+                    .collect(),
+            ),
+            Receipt {
+                ra: Some(0xffff_ffff_ffff_0000.into()),
+                ..receipt(4, ReceiptType::Revert)
+            },
+            Receipt {
+                ra: Some(0xffff_ffff_ffff_0000.into()),
+                ..receipt(5, ReceiptType::Revert)
+            },
+            Receipt {
+                reason: Some(0x0800_0000_0000_0000.into()),
+                ..receipt(6, ReceiptType::Panic)
+            },
+        ];
+
+        let failures = decoder().decode_failures(&receipts).unwrap();
+        let messages = failures.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            [
+                "assert_eq failed: 1, 2",
+                r#"require failed: {"Done":7}"#,
+                "require failed",
+                "panic NotEnoughBalance (instruction 0x00000000)",
+            ]
+        );
+
+        assert_eq!(failures[0].receipt_index, 2);
+        assert_eq!(
+            failures[3].cause,
+            FailureCause::Panic(PanicInstruction {
+                reason: PanicReason::NotEnoughBalance,
+                instruction: 0,
+            })
+        );
+    }
+}
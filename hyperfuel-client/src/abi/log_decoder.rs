@@ -0,0 +1,404 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use anyhow::{Context, Result};
+use hyperfuel_abi::ProgramAbi;
+use hyperfuel_format::{Receipt, ReceiptType};
+use polars_arrow::{
+    array::{BinaryArray, MutableArray, MutableUtf8Array, UInt64Array, UInt8Array, Utf8Array},
+    datatypes::{ArrowDataType as DataType, ArrowSchema as Schema, Field},
+};
+
+use super::{program::resolve, DecodedValue, ParamType};
+use crate::{ArrowBatch, ArrowChunk, LogContext};
+
+/// A type that is logged by a Sway program.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoggedType {
+    /// Log id, LogData receipts of this type carry it in the `rb` register.
+    pub log_id: u64,
+    /// Type declaration from the ABI, e.g. `struct Transfer` or `u64`.
+    pub name: String,
+    /// Resolved type of the logged value.
+    pub param_type: ParamType,
+}
+
+/// Decodes LogData receipts using the logged types of a Sway JSON ABI.
+///
+/// Receipts are matched to logged types by their `rb` register, which holds the log id.
+#[derive(Debug, Clone)]
+pub struct LogDecoder {
+    pub(super) logged_types: BTreeMap<u64, Arc<LoggedType>>,
+}
+
+impl LogDecoder {
+    /// Receipt fields that [`LogDecoder::decode_batch`] and [`LogDecoder::decode_to_tables`] read.
+    pub(crate) const REQUIRED_FIELDS: &'static [&'static str] = &["receipt_type", "rb", "data"];
+
+    /// Creates a decoder from a Sway JSON ABI as written by `forc build`.
+    pub fn from_json_abi(json: &str) -> Result<Self> {
+        let abi = ProgramAbi::from_json(json)?;
+
+        let logged_types = abi
+            .logged_types
+            .iter()
+            .map(|decl| {
+                let log_id = decl.log_id.value()?;
+                let param_type = resolve(&abi, &decl.concrete_type_id)
+                    .with_context(|| format!("resolve type of log {log_id}"))?;
+                let name = abi
+                    .type_name(&decl.concrete_type_id)
+                    .unwrap_or_default()
+                    .to_owned();
+
+                Ok((
+                    log_id,
+                    Arc::new(LoggedType {
+                        log_id,
+                        name,
+                        param_type,
+                    }),
+                ))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self { logged_types })
+    }
+
+    /// Returns the logged type with the given log id.
+    pub fn logged_type(&self, log_id: u64) -> Option<&LoggedType> {
+        self.logged_types.get(&log_id).map(AsRef::as_ref)
+    }
+
+    /// Returns all logged types, ordered by log id.
+    pub fn logged_types(&self) -> impl Iterator<Item = &LoggedType> {
+        self.logged_types.values().map(AsRef::as_ref)
+    }
+
+    /// Returns the log ids of all logged types, to be used in `ReceiptSelection::rb`.
+    pub fn log_ids(&self) -> Vec<u64> {
+        self.logged_types.keys().copied().collect()
+    }
+
+    /// Decodes the data of a log with the given log id.
+    ///
+    /// Returns `None` if the ABI has no logged type with this id.
+    pub fn decode(&self, log_id: u64, data: &[u8]) -> Result<Option<DecodedValue>> {
+        let Some(logged_type) = self.logged_types.get(&log_id) else {
+            return Ok(None);
+        };
+
+        logged_type
+            .param_type
+            .decode(data)
+            .with_context(|| format!("decode log {} ({})", log_id, logged_type.name))
+            .map(Some)
+    }
+
+    /// Decodes the data of a LogData receipt.
+    ///
+    /// Returns `None` if the receipt isn't a LogData receipt or its log id isn't in the ABI.
+    pub fn decode_receipt(&self, receipt: &Receipt) -> Result<Option<DecodedValue>> {
+        if receipt.receipt_type != ReceiptType::LogData {
+            return Ok(None);
+        }
+
+        match (receipt.rb, receipt.data.as_ref()) {
+            (Some(rb), Some(data)) => self.decode(*rb, data),
+            _ => Ok(None),
+        }
+    }
+
+    /// Decodes the data of a log returned by
+    /// [`Client::preset_query_get_logs`](crate::Client::preset_query_get_logs).
+    ///
+    /// Returns `None` if the log isn't a LogData receipt or its log id isn't in the ABI.
+    pub fn decode_log(&self, log: &LogContext) -> Result<Option<DecodedValue>> {
+        if log.receipt_type != ReceiptType::LogData {
+            return Ok(None);
+        }
+
+        match (log.rb, log.data.as_ref()) {
+            (Some(rb), Some(data)) => self.decode(*rb, data),
+            _ => Ok(None),
+        }
+    }
+
+    /// Appends `decoded_log_type` and `decoded_log` columns to a batch of receipts.
+    ///
+    /// `decoded_log_type` is the type declaration of the logged type and `decoded_log` is the
+    /// decoded value as JSON, see [`DecodedValue::to_json`]. Both are null for rows that aren't
+    /// LogData receipts of a type in the ABI, or that fail to decode. Decode failures are logged
+    /// with a warning.
+    ///
+    /// The batch needs to have the `receipt_type`, `rb` and `data` columns.
+    pub fn decode_batch(&self, batch: &ArrowBatch) -> Result<ArrowBatch> {
+        let receipt_type = batch
+            .column::<UInt8Array>("receipt_type")
+            .context("get receipt_type column")?;
+        let rb = batch.column::<UInt64Array>("rb").context("get rb column")?;
+        let data = batch
+            .column::<BinaryArray<i32>>("data")
+            .context("get data column")?;
+
+        let mut log_types = MutableUtf8Array::<i32>::with_capacity(batch.chunk.len());
+        let mut logs = MutableUtf8Array::<i32>::with_capacity(batch.chunk.len());
+        let mut failures = DecodeFailures::default();
+
+        for ((receipt_type, rb), data) in receipt_type.iter().zip(rb.iter()).zip(data.iter()) {
+            let logged_type = match (receipt_type, rb, data) {
+                (Some(&receipt_type), Some(rb), Some(data))
+                    if receipt_type == ReceiptType::LogData.to_u8() =>
+                {
+                    self.logged_types.get(rb).and_then(|t| {
+                        let value = failures.check(t, t.param_type.decode(data))?;
+                        Some((t, value))
+                    })
+                }
+                _ => None,
+            };
+
+            match logged_type {
+                Some((logged_type, value)) => {
+                    log_types.push(Some(logged_type.name.as_str()));
+                    logs.push(Some(value.to_json().to_string()));
+                }
+                None => {
+                    log_types.push_null();
+                    logs.push_null();
+                }
+            }
+        }
+
+        failures.warn();
+
+        let log_types: Utf8Array<i32> = log_types.into();
+        let logs: Utf8Array<i32> = logs.into();
+
+        let mut fields = batch.schema.fields.clone();
+        fields.push(Field::new("decoded_log_type", DataType::Utf8, true));
+        fields.push(Field::new("decoded_log", DataType::Utf8, true));
+
+        let mut cols = batch.chunk.columns().to_vec();
+        cols.push(log_types.boxed());
+        cols.push(logs.boxed());
+
+        Ok(ArrowBatch {
+            chunk: ArrowChunk::new(cols).into(),
+            schema: Schema::from(fields).into(),
+        })
+    }
+}
+
+/// Counts logs that fail to decode, so they can be reported once per batch.
+#[derive(Default)]
+pub(super) struct DecodeFailures {
+    count: usize,
+    first: Option<anyhow::Error>,
+}
+
+impl DecodeFailures {
+    /// Returns the value if it decoded, records the error otherwise.
+    pub(super) fn check<T>(&mut self, logged_type: &LoggedType, res: Result<T>) -> Option<T> {
+        match res {
+            Ok(value) => Some(value),
+            Err(e) => {
+                self.count += 1;
+                self.first.get_or_insert_with(|| {
+                    e.context(format!(
+                        "decode log {} ({})",
+                        logged_type.log_id, logged_type.name
+                    ))
+                });
+                None
+            }
+        }
+    }
+
+    /// Logs a warning if any log failed to decode.
+    pub(super) fn warn(self) {
+        if let Some(e) = self.first {
+            log::warn!("failed to decode {} logs, first error: {:?}", self.count, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FromArrow, ToArrow};
+    use polars_arrow::array::Array;
+
+    const TRANSFER_LOG_ID: u64 = 17859024950540227480;
+    const STATUS_LOG_ID: u64 = 4363211024530769387;
+
+    fn decoder() -> LogDecoder {
+        LogDecoder::from_json_abi(include_str!("../../test-data/logs-abi.json")).unwrap()
+    }
+
+    fn encoded_transfer() -> Vec<u8> {
+        let mut data = vec![7; 32];
+        data.extend(1000u64.to_be_bytes());
+        data.extend(2u64.to_be_bytes());
+        data.extend(b"hi");
+        data.extend(2u64.to_be_bytes());
+        data.extend([1, 2]);
+        data.extend(1u64.to_be_bytes());
+        data.extend(5u64.to_be_bytes());
+        data.push(1);
+        data.extend(3u64.to_be_bytes());
+        data.extend(4u64.to_be_bytes());
+        data
+    }
+
+    #[test]
+    fn test_resolve_logged_types() {
+        let decoder = decoder();
+
+        assert_eq!(
+            decoder.log_ids(),
+            [1515152261580153489, STATUS_LOG_ID, TRANSFER_LOG_ID]
+        );
+
+        let status = decoder.logged_type(STATUS_LOG_ID).unwrap();
+        assert_eq!(status.name, "enum Status");
+        assert_eq!(
+            status.param_type,
+            ParamType::Enum {
+                name: "Status".to_owned(),
+                variants: vec![
+                    ("Pending".to_owned(), ParamType::Unit),
+                    ("Done".to_owned(), ParamType::U64),
+                    ("Failed".to_owned(), ParamType::StringArray(4)),
+                ],
+            }
+        );
+
+        let ParamType::Struct { fields, .. } =
+            &decoder.logged_type(TRANSFER_LOG_ID).unwrap().param_type
+        else {
+            panic!("expected struct");
+        };
+        let fields = fields.iter().cloned().collect::<BTreeMap<_, _>>();
+        assert_eq!(fields["memo"], ParamType::String);
+        assert_eq!(fields["tags"], ParamType::Vec(Box::new(ParamType::U8)));
+        assert_eq!(
+            fields["pair"],
+            ParamType::Tuple(vec![
+                ParamType::Bool,
+                ParamType::Array(Box::new(ParamType::U64), 2)
+            ])
+        );
+    }
+
+    #[test]
+    fn test_decode_transfer() {
+        let value = decoder()
+            .decode(TRANSFER_LOG_ID, &encoded_transfer())
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            value.to_json(),
+            serde_json::json!({
+                "to": { "bits": format!("0x{}", "07".repeat(32)) },
+                "amount": 1000,
+                "memo": "hi",
+                "tags": [1, 2],
+                "fee": { "Some": 5 },
+                "pair": [true, [3, 4]],
+            })
+        );
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        let decoder = decoder();
+
+        assert!(decoder.decode(1, &[]).unwrap().is_none());
+
+        let mut data = encoded_transfer();
+        data.pop();
+        assert!(decoder.decode(TRANSFER_LOG_ID, &data).is_err());
+
+        let mut data = encoded_transfer();
+        data.push(0);
+        assert!(decoder.decode(TRANSFER_LOG_ID, &data).is_err());
+
+        assert!(decoder.decode(STATUS_LOG_ID, &3u64.to_be_bytes()).is_err());
+
+        // `tags` claims more elements than there are bytes left
+        let mut data = vec![7; 32];
+        data.extend(1000u64.to_be_bytes());
+        data.extend(2u64.to_be_bytes());
+        data.extend(b"hi");
+        data.extend(u64::MAX.to_be_bytes());
+        data.extend([1, 2]);
+        let err = decoder.decode(TRANSFER_LOG_ID, &data).unwrap_err();
+        assert!(format!("{err:#}").contains("more than the 2 bytes left"));
+    }
+
+    #[test]
+    fn test_decode_batch() {
+        let receipts = vec![
+            Receipt {
+                receipt_type: ReceiptType::LogData,
+                rb: Some(STATUS_LOG_ID.into()),
+                data: Some(
+                    2u64.to_be_bytes()
+                        .into_iter()
+                        .chain(*b"oops")
+                        .collect::<Vec<_>>()
+                        .into(),
+                ),
+                ..Default::default()
+            },
+            Receipt {
+                receipt_type: ReceiptType::Log,
+                rb: Some(STATUS_LOG_ID.into()),
+                ..Default::default()
+            },
+            Receipt {
+                receipt_type: ReceiptType::LogData,
+                rb: Some(TRANSFER_LOG_ID.into()),
+                data: Some(vec![1, 2, 3].into()),
+                ..Default::default()
+            },
+        ];
+
+        let decoder = decoder();
+        assert_eq!(
+            decoder.decode_receipt(&receipts[0]).unwrap(),
+            Some(DecodedValue::Enum {
+                variant: "Failed".to_owned(),
+                value: Box::new(DecodedValue::String("oops".to_owned())),
+            })
+        );
+        assert_eq!(decoder.decode_receipt(&receipts[1]).unwrap(), None);
+        assert_eq!(
+            decoder
+                .decode_log(&LogContext::from(receipts[0].clone()))
+                .unwrap(),
+            decoder.decode_receipt(&receipts[0]).unwrap()
+        );
+
+        let batch = Receipt::to_arrow(&receipts, None).unwrap();
+        let decoded = decoder.decode_batch(&batch).unwrap();
+
+        let log_types = decoded
+            .column::<Utf8Array<i32>>("decoded_log_type")
+            .unwrap();
+        let logs = decoded.column::<Utf8Array<i32>>("decoded_log").unwrap();
+        assert_eq!(
+            log_types.iter().collect::<Vec<_>>(),
+            [Some("enum Status"), None, None]
+        );
+        assert_eq!(
+            logs.iter().collect::<Vec<_>>(),
+            [Some(r#"{"Failed":"oops"}"#), None, None]
+        );
+        assert_eq!(logs.null_count(), 2);
+
+        // the original columns are kept so the batch still converts to receipts.
+        assert_eq!(Receipt::from_arrow(&decoded), receipts);
+    }
+}
@@ -0,0 +1,793 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use anyhow::{Context, Result};
+use hyperfuel_format::ReceiptType;
+use polars_arrow::{
+    array::{
+        Array, ListArray, MutableArray, MutableBinaryArray, MutableBooleanArray,
+        MutablePrimitiveArray, MutableUtf8Array, UInt64Array, UInt8Array,
+    },
+    bitmap::MutableBitmap,
+    datatypes::{ArrowDataType as DataType, ArrowSchema as Schema, Field},
+    offset::Offsets,
+};
+
+use super::log_decoder::DecodeFailures;
+use super::{DecodedValue, LogDecoder, LoggedType, ParamType};
+use crate::{
+    util::{take_rows, BinaryColumn},
+    ArrowBatch, ArrowChunk,
+};
+
+/// Receipt columns that are copied into the decoded log tables, if they are in the receipts batch.
+///
+/// They are prefixed with `receipt_` if a column of the logged type has the same name.
+const CONTEXT_COLUMNS: &[&str] = &["block_height", "tx_id", "receipt_index", "root_contract_id"];
+
+impl LoggedType {
+    /// Name of the table that logs of this type are decoded into, e.g. `Transfer` for
+    /// `struct Transfer`. Characters that aren't alphanumeric are replaced with `_`.
+    pub fn table_name(&self) -> String {
+        let name = self
+            .name
+            .strip_prefix("struct ")
+            .or_else(|| self.name.strip_prefix("enum "))
+            .unwrap_or(&self.name);
+
+        name.chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect()
+    }
+
+    /// Returns the columns of the decoded logs table of this type, as `(path, data type)`.
+    ///
+    /// Struct fields, tuple elements and array elements are flattened into one column each,
+    /// named by their dot-separated path, e.g. `to.bits` or `pair.0`. A logged type that isn't a
+    /// struct is put into a `value` column.
+    ///
+    /// Options are flattened into their `Some` value, which is null for `None`. Other enums get a
+    /// Utf8 column with the variant name, plus the flattened columns of each variant's value.
+    /// Vectors of bools and integers become large lists, other vectors are stored as JSON strings.
+    /// `b256`, `u256` and bytes are stored as big endian Binary.
+    pub fn table_columns(&self) -> Vec<(String, DataType)> {
+        let mut columns = Vec::new();
+        table_columns(&self.param_type, root_path(&self.param_type), &mut columns);
+        columns
+            .into_iter()
+            .map(|(path, kind)| (path, kind.data_type()))
+            .collect()
+    }
+}
+
+impl LogDecoder {
+    /// Returns the name of the decoded logs table of each logged type, by log id.
+    ///
+    /// This is [`LoggedType::table_name`], with `_` and the log id appended if several logged
+    /// types have that name, e.g. `Transfer_1515152261580153489` for two `Transfer` structs from
+    /// different modules.
+    pub fn table_names(&self) -> BTreeMap<u64, String> {
+        let mut counts = HashMap::<String, usize>::new();
+        for logged_type in self.logged_types.values() {
+            *counts.entry(logged_type.table_name()).or_default() += 1;
+        }
+
+        self.logged_types
+            .values()
+            .map(|logged_type| {
+                let name = logged_type.table_name();
+                let name = if counts[&name] > 1 {
+                    format!("{}_{}", name, logged_type.log_id)
+                } else {
+                    name
+                };
+                (logged_type.log_id, name)
+            })
+            .collect()
+    }
+
+    /// Splits the LogData receipts in a batch of receipts into a decoded logs table per logged
+    /// type, keyed by [`LogDecoder::table_names`].
+    ///
+    /// Tables have the columns of [`LoggedType::table_columns`], after the `block_height`,
+    /// `tx_id`, `receipt_index` and `root_contract_id` columns of the receipts batch if it has
+    /// them. Those are renamed to e.g. `receipt_block_height` if the logged type has a
+    /// `block_height` column. Receipts that fail to decode are skipped and logged with a warning.
+    ///
+    /// The batch needs to have the `receipt_type`, `rb` and `data` columns, `data` can be
+    /// `Binary` or `BinaryView`.
+    pub fn decode_to_tables(&self, batch: &ArrowBatch) -> Result<BTreeMap<String, ArrowBatch>> {
+        let receipt_type = batch
+            .column::<UInt8Array>("receipt_type")
+            .context("get receipt_type column")?;
+        let rb = batch.column::<UInt64Array>("rb").context("get rb column")?;
+        let data = batch
+            .schema
+            .fields
+            .iter()
+            .position(|f| f.name == "data")
+            .context("get data column")?;
+        let data = BinaryColumn::new(batch.chunk.columns()[data].as_ref(), "data")?;
+
+        let mut rows = BTreeMap::<u64, (Vec<usize>, Vec<DecodedValue>)>::new();
+        let mut failures = DecodeFailures::default();
+
+        for (idx, ((receipt_type, rb), data)) in receipt_type
+            .iter()
+            .zip(rb.iter())
+            .zip(data.iter())
+            .enumerate()
+        {
+            let (Some(&receipt_type), Some(rb), Some(data)) = (receipt_type, rb, data) else {
+                continue;
+            };
+            if receipt_type != ReceiptType::LogData.to_u8() {
+                continue;
+            }
+            let Some(logged_type) = self.logged_types.get(rb) else {
+                continue;
+            };
+            let Some(value) = failures.check(logged_type, logged_type.param_type.decode(data))
+            else {
+                continue;
+            };
+
+            let (indices, values) = rows.entry(*rb).or_default();
+            indices.push(idx);
+            values.push(value);
+        }
+        failures.warn();
+
+        let mut table_names = self.table_names();
+        rows.into_iter()
+            .map(|(log_id, (indices, values))| {
+                let logged_type = &self.logged_types[&log_id];
+                let table = make_table(batch, logged_type, &indices, &values)
+                    .with_context(|| format!("make table for {}", logged_type.name))?;
+                Ok((table_names.remove(&log_id).unwrap(), table))
+            })
+            .collect()
+    }
+}
+
+fn make_table(
+    receipts: &ArrowBatch,
+    logged_type: &LoggedType,
+    indices: &[usize],
+    values: &[DecodedValue],
+) -> Result<ArrowBatch> {
+    let mut columns = Vec::new();
+    table_columns(
+        &logged_type.param_type,
+        root_path(&logged_type.param_type),
+        &mut columns,
+    );
+    let log_names = columns
+        .iter()
+        .map(|(path, _)| path.as_str())
+        .collect::<HashSet<_>>();
+
+    let mut fields = Vec::new();
+    let mut cols = Vec::new();
+
+    for name in CONTEXT_COLUMNS {
+        if let Some((field, col)) = receipts
+            .schema
+            .fields
+            .iter()
+            .zip(receipts.chunk.columns())
+            .find(|(field, _)| field.name == *name)
+        {
+            let mut name = field.name.clone();
+            while log_names.contains(name.as_str()) {
+                name = format!("receipt_{name}");
+            }
+            let col = take_rows(col.as_ref(), indices);
+            fields.push(Field::new(name, col.data_type().clone(), true));
+            cols.push(col);
+        }
+    }
+    let mut builders = columns
+        .iter()
+        .map(|(_, kind)| Builder::new(kind))
+        .collect::<Vec<_>>();
+
+    let mut row = Vec::with_capacity(builders.len());
+    for value in values {
+        row.clear();
+        column_values(&logged_type.param_type, Some(value), &mut row);
+        for (builder, value) in builders.iter_mut().zip(row.iter()) {
+            builder.push(*value)?;
+        }
+    }
+
+    for ((path, kind), builder) in columns.into_iter().zip(builders) {
+        fields.push(Field::new(path, kind.data_type(), true));
+        cols.push(builder.finish(&kind)?);
+    }
+
+    Ok(ArrowBatch {
+        chunk: ArrowChunk::new(cols).into(),
+        schema: Schema::from(fields).into(),
+    })
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Leaf {
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    Binary,
+    Utf8,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ColumnKind {
+    Leaf(Leaf),
+    List(Leaf),
+    Json,
+    Variant,
+}
+
+impl Leaf {
+    fn of(ty: &ParamType) -> Option<Self> {
+        match ty {
+            ParamType::Bool => Some(Self::Bool),
+            ParamType::U8 => Some(Self::U8),
+            ParamType::U16 => Some(Self::U16),
+            ParamType::U32 => Some(Self::U32),
+            ParamType::U64 => Some(Self::U64),
+            ParamType::U256 | ParamType::B256 | ParamType::Bytes | ParamType::RawSlice => {
+                Some(Self::Binary)
+            }
+            ParamType::String | ParamType::StringSlice | ParamType::StringArray(_) => {
+                Some(Self::Utf8)
+            }
+            _ => None,
+        }
+    }
+
+    fn data_type(self) -> DataType {
+        match self {
+            Self::Bool => DataType::Boolean,
+            Self::U8 => DataType::UInt8,
+            Self::U16 => DataType::UInt16,
+            Self::U32 => DataType::UInt32,
+            Self::U64 => DataType::UInt64,
+            Self::Binary => DataType::Binary,
+            Self::Utf8 => DataType::Utf8,
+        }
+    }
+}
+
+impl ColumnKind {
+    fn data_type(self) -> DataType {
+        match self {
+            Self::Leaf(leaf) => leaf.data_type(),
+            Self::List(leaf) => ListArray::<i64>::default_datatype(leaf.data_type()),
+            Self::Json | Self::Variant => DataType::Utf8,
+        }
+    }
+}
+
+fn root_path(ty: &ParamType) -> String {
+    match ty {
+        ParamType::Struct { .. } => String::new(),
+        _ => "value".to_owned(),
+    }
+}
+
+fn join_path(path: &str, name: impl std::fmt::Display) -> String {
+    if path.is_empty() {
+        name.to_string()
+    } else {
+        format!("{path}.{name}")
+    }
+}
+
+/// Returns the type of the `Some` variant if the enum is an `Option`.
+fn option_inner(ty: &ParamType) -> Option<&ParamType> {
+    match ty {
+        ParamType::Enum { name, variants } if name == "std::option::Option" => variants
+            .iter()
+            .find(|(variant, _)| variant == "Some")
+            .map(|(_, ty)| ty),
+        _ => None,
+    }
+}
+
+fn table_columns(ty: &ParamType, path: String, out: &mut Vec<(String, ColumnKind)>) {
+    if let Some(leaf) = Leaf::of(ty) {
+        out.push((path, ColumnKind::Leaf(leaf)));
+        return;
+    }
+    if let Some(inner) = option_inner(ty) {
+        table_columns(inner, path, out);
+        return;
+    }
+
+    match ty {
+        ParamType::Struct { fields, .. } => {
+            for (name, ty) in fields {
+                table_columns(ty, join_path(&path, name), out);
+            }
+        }
+        ParamType::Tuple(elems) => {
+            for (idx, ty) in elems.iter().enumerate() {
+                table_columns(ty, join_path(&path, idx), out);
+            }
+        }
+        ParamType::Array(ty, len) => {
+            for idx in 0..*len {
+                table_columns(ty, join_path(&path, idx), out);
+            }
+        }
+        ParamType::Vec(elem) => match Leaf::of(elem) {
+            Some(leaf @ (Leaf::Bool | Leaf::U8 | Leaf::U16 | Leaf::U32 | Leaf::U64)) => {
+                out.push((path, ColumnKind::List(leaf)))
+            }
+            _ => out.push((path, ColumnKind::Json)),
+        },
+        ParamType::Enum { variants, .. } => {
+            out.push((path.clone(), ColumnKind::Variant));
+            for (name, ty) in variants {
+                table_columns(ty, join_path(&path, name), out);
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Pushes the value of each column of the type, in the same order as [`table_columns`].
+fn column_values<'a>(
+    ty: &ParamType,
+    value: Option<&'a DecodedValue>,
+    out: &mut Vec<Option<&'a DecodedValue>>,
+) {
+    if Leaf::of(ty).is_some() || matches!(ty, ParamType::Vec(_)) {
+        out.push(value);
+        return;
+    }
+    if let Some(inner) = option_inner(ty) {
+        let value = match value {
+            Some(DecodedValue::Enum { variant, value }) if variant == "Some" => Some(&**value),
+            _ => None,
+        };
+        column_values(inner, value, out);
+        return;
+    }
+
+    match ty {
+        ParamType::Struct { fields, .. } => {
+            let values = match value {
+                Some(DecodedValue::Struct(values)) => Some(values),
+                _ => None,
+            };
+            for (idx, (_, ty)) in fields.iter().enumerate() {
+                let value = values.and_then(|v| v.get(idx)).map(|(_, v)| v);
+                column_values(ty, value, out);
+            }
+        }
+        ParamType::Tuple(elems) => {
+            let values = match value {
+                Some(DecodedValue::Tuple(values)) => Some(values),
+                _ => None,
+            };
+            for (idx, ty) in elems.iter().enumerate() {
+                column_values(ty, values.and_then(|v| v.get(idx)), out);
+            }
+        }
+        ParamType::Array(ty, len) => {
+            let values = match value {
+                Some(DecodedValue::Array(values)) => Some(values),
+                _ => None,
+            };
+            for idx in 0..*len {
+                column_values(ty, values.and_then(|v| v.get(idx)), out);
+            }
+        }
+        ParamType::Enum { variants, .. } => {
+            out.push(value);
+            for (name, ty) in variants {
+                let value = match value {
+                    Some(DecodedValue::Enum { variant, value }) if variant == name => {
+                        Some(&**value)
+                    }
+                    _ => None,
+                };
+                column_values(ty, value, out);
+            }
+        }
+        _ => (),
+    }
+}
+
+enum Builder {
+    Bool(MutableBooleanArray),
+    U8(MutablePrimitiveArray<u8>),
+    U16(MutablePrimitiveArray<u16>),
+    U32(MutablePrimitiveArray<u32>),
+    U64(MutablePrimitiveArray<u64>),
+    Binary(MutableBinaryArray<i32>),
+    Utf8(MutableUtf8Array<i32>),
+    List {
+        offsets: Offsets<i64>,
+        validity: MutableBitmap,
+        values: Box<Builder>,
+    },
+}
+
+impl Builder {
+    fn new(kind: &ColumnKind) -> Self {
+        match kind {
+            ColumnKind::Leaf(leaf) => Self::leaf(*leaf),
+            ColumnKind::List(leaf) => Self::List {
+                offsets: Offsets::new(),
+                validity: MutableBitmap::new(),
+                values: Box::new(Self::leaf(*leaf)),
+            },
+            ColumnKind::Json | ColumnKind::Variant => Self::Utf8(MutableUtf8Array::new()),
+        }
+    }
+
+    fn leaf(leaf: Leaf) -> Self {
+        match leaf {
+            Leaf::Bool => Self::Bool(MutableBooleanArray::new()),
+            Leaf::U8 => Self::U8(MutablePrimitiveArray::new()),
+            Leaf::U16 => Self::U16(MutablePrimitiveArray::new()),
+            Leaf::U32 => Self::U32(MutablePrimitiveArray::new()),
+            Leaf::U64 => Self::U64(MutablePrimitiveArray::new()),
+            Leaf::Binary => Self::Binary(MutableBinaryArray::new()),
+            Leaf::Utf8 => Self::Utf8(MutableUtf8Array::new()),
+        }
+    }
+
+    fn push(&mut self, value: Option<&DecodedValue>) -> Result<()> {
+        match (self, value) {
+            (Self::Bool(arr), Some(DecodedValue::Bool(v))) => arr.push(Some(*v)),
+            (Self::U8(arr), Some(DecodedValue::U8(v))) => arr.push(Some(*v)),
+            (Self::U16(arr), Some(DecodedValue::U16(v))) => arr.push(Some(*v)),
+            (Self::U32(arr), Some(DecodedValue::U32(v))) => arr.push(Some(*v)),
+            (Self::U64(arr), Some(DecodedValue::U64(v))) => arr.push(Some(*v)),
+            (Self::Binary(arr), Some(DecodedValue::B256(v))) => arr.push(Some(v.as_slice())),
+            (Self::Binary(arr), Some(DecodedValue::U256(v))) => {
+                arr.push(Some(v.to_be_bytes::<32>()))
+            }
+            (Self::Binary(arr), Some(DecodedValue::Bytes(v))) => arr.push(Some(v)),
+            (Self::Utf8(arr), Some(DecodedValue::String(v))) => arr.push(Some(v)),
+            (Self::Utf8(arr), Some(DecodedValue::Enum { variant, .. })) => arr.push(Some(variant)),
+            (Self::Utf8(arr), Some(value)) => arr.push(Some(value.to_json().to_string())),
+            (
+                Self::List {
+                    offsets,
+                    validity,
+                    values,
+                },
+                Some(DecodedValue::Vec(items)),
+            ) => {
+                for item in items {
+                    values.push(Some(item))?;
+                }
+                offsets.try_push(items.len()).context("push list offset")?;
+                validity.push(true);
+            }
+            (
+                Self::List {
+                    offsets, validity, ..
+                },
+                None,
+            ) => {
+                offsets.extend_constant(1);
+                validity.push(false);
+            }
+            (Self::Bool(arr), None) => arr.push_null(),
+            (Self::U8(arr), None) => arr.push_null(),
+            (Self::U16(arr), None) => arr.push_null(),
+            (Self::U32(arr), None) => arr.push_null(),
+            (Self::U64(arr), None) => arr.push_null(),
+            (Self::Binary(arr), None) => arr.push_null(),
+            (Self::Utf8(arr), None) => arr.push_null(),
+            (_, Some(value)) => {
+                return Err(anyhow::anyhow!(
+                    "decoded value {:?} doesn't match column type",
+                    value
+                ))
+            }
+        }
+
+        Ok(())
+    }
+
+    fn finish(self, kind: &ColumnKind) -> Result<Box<dyn Array>> {
+        let arr = match self {
+            Self::Bool(mut arr) => arr.as_box(),
+            Self::U8(mut arr) => arr.as_box(),
+            Self::U16(mut arr) => arr.as_box(),
+            Self::U32(mut arr) => arr.as_box(),
+            Self::U64(mut arr) => arr.as_box(),
+            Self::Binary(mut arr) => arr.as_box(),
+            Self::Utf8(mut arr) => arr.as_box(),
+            Self::List {
+                offsets,
+                validity,
+                values,
+            } => {
+                let ColumnKind::List(leaf) = kind else {
+                    unreachable!()
+                };
+                ListArray::<i64>::try_new(
+                    kind.data_type(),
+                    offsets.into(),
+                    values.finish(&ColumnKind::Leaf(*leaf))?,
+                    validity.into(),
+                )
+                .context("construct list array")?
+                .boxed()
+            }
+        };
+
+        Ok(arr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{util::map_batch_to_binary_view, ToArrow};
+    use hyperfuel_format::Receipt;
+    use polars_arrow::array::{ListArray, UInt64Array, Utf8Array};
+    use std::sync::Arc;
+
+    const TRANSFER_LOG_ID: u64 = 17859024950540227480;
+    const STATUS_LOG_ID: u64 = 4363211024530769387;
+
+    fn decoder() -> LogDecoder {
+        LogDecoder::from_json_abi(include_str!("../../test-data/logs-abi.json")).unwrap()
+    }
+
+    fn encoded_transfer(amount: u64, fee: Option<u64>) -> Vec<u8> {
+        let mut data = vec![7; 32];
+        data.extend(amount.to_be_bytes());
+        data.extend(2u64.to_be_bytes());
+        data.extend(b"hi");
+        data.extend(3u64.to_be_bytes());
+        data.extend([1, 2, 3]);
+        match fee {
+            Some(fee) => {
+                data.extend(1u64.to_be_bytes());
+                data.extend(fee.to_be_bytes());
+            }
+            None => data.extend(0u64.to_be_bytes()),
+        }
+        data.push(0);
+        data.extend(3u64.to_be_bytes());
+        data.extend(4u64.to_be_bytes());
+        data
+    }
+
+    #[test]
+    fn test_table_columns() {
+        let decoder = decoder();
+
+        let transfer = decoder.logged_type(TRANSFER_LOG_ID).unwrap();
+        assert_eq!(transfer.table_name(), "Transfer");
+        assert_eq!(
+            transfer.table_columns(),
+            [
+                ("to.bits".to_owned(), DataType::Binary),
+                ("amount".to_owned(), DataType::UInt64),
+                ("memo".to_owned(), DataType::Utf8),
+                (
+                    "tags".to_owned(),
+                    ListArray::<i64>::default_datatype(DataType::UInt8)
+                ),
+                ("fee".to_owned(), DataType::UInt64),
+                ("pair.0".to_owned(), DataType::Boolean),
+                ("pair.1.0".to_owned(), DataType::UInt64),
+                ("pair.1.1".to_owned(), DataType::UInt64),
+            ]
+        );
+
+        let status = decoder.logged_type(STATUS_LOG_ID).unwrap();
+        assert_eq!(status.table_name(), "Status");
+        assert_eq!(
+            status.table_columns(),
+            [
+                ("value".to_owned(), DataType::Utf8),
+                ("value.Done".to_owned(), DataType::UInt64),
+                ("value.Failed".to_owned(), DataType::Utf8),
+            ]
+        );
+
+        let value = decoder.logged_type(1515152261580153489).unwrap();
+        assert_eq!(value.table_name(), "u64");
+        assert_eq!(
+            value.table_columns(),
+            [("value".to_owned(), DataType::UInt64)]
+        );
+    }
+
+    #[test]
+    fn test_table_names() {
+        let mut decoder = decoder();
+        assert_eq!(
+            decoder.table_names().into_values().collect::<Vec<_>>(),
+            ["u64", "Status", "Transfer"]
+        );
+
+        // a struct with the same name from another module
+        let mut other = decoder.logged_types[&TRANSFER_LOG_ID].as_ref().clone();
+        other.log_id = 7;
+        other.name = "struct other::Transfer".to_owned();
+        decoder.logged_types.insert(7, Arc::new(other.clone()));
+        assert_eq!(decoder.table_names()[&7], "other__Transfer");
+
+        other.name = "struct Transfer".to_owned();
+        decoder.logged_types.insert(7, Arc::new(other));
+        let names = decoder.table_names();
+        assert_eq!(names[&7], "Transfer_7");
+        assert_eq!(
+            names[&TRANSFER_LOG_ID],
+            format!("Transfer_{TRANSFER_LOG_ID}")
+        );
+
+        let batch = Receipt::to_arrow(
+            &[
+                Receipt {
+                    receipt_type: ReceiptType::LogData,
+                    rb: Some(7.into()),
+                    data: Some(encoded_transfer(1, None).into()),
+                    ..Default::default()
+                },
+                Receipt {
+                    receipt_type: ReceiptType::LogData,
+                    rb: Some(TRANSFER_LOG_ID.into()),
+                    data: Some(encoded_transfer(2, None).into()),
+                    ..Default::default()
+                },
+            ],
+            None,
+        )
+        .unwrap();
+        let tables = decoder.decode_to_tables(&batch).unwrap();
+        assert_eq!(tables.len(), 2);
+        assert_eq!(tables["Transfer_7"].chunk.len(), 1);
+    }
+
+    #[test]
+    fn test_decode_to_tables() {
+        let log = |rb: u64, data: Vec<u8>| Receipt {
+            receipt_type: ReceiptType::LogData,
+            block_height: 5.into(),
+            rb: Some(rb.into()),
+            data: Some(data.into()),
+            ..Default::default()
+        };
+        let receipts = vec![
+            log(TRANSFER_LOG_ID, encoded_transfer(10, Some(1))),
+            log(STATUS_LOG_ID, 1u64.to_be_bytes().repeat(2)),
+            Receipt::default(),
+            log(TRANSFER_LOG_ID, encoded_transfer(20, None)),
+            log(STATUS_LOG_ID, 0u64.to_be_bytes().to_vec()),
+            // doesn't decode, skipped.
+            log(TRANSFER_LOG_ID, vec![1, 2, 3]),
+        ];
+        let batch = Receipt::to_arrow(&receipts, None).unwrap();
+
+        let tables = decoder().decode_to_tables(&batch).unwrap();
+        assert_eq!(tables.keys().collect::<Vec<_>>(), ["Status", "Transfer"]);
+
+        let transfers = &tables["Transfer"];
+        assert_eq!(transfers.chunk.len(), 2);
+        assert_eq!(
+            transfers
+                .schema
+                .fields
+                .iter()
+                .map(|f| f.name.as_str())
+                .take(5)
+                .collect::<Vec<_>>(),
+            [
+                "block_height",
+                "tx_id",
+                "receipt_index",
+                "root_contract_id",
+                "to.bits"
+            ]
+        );
+        let receipt_index = transfers.column::<UInt64Array>("receipt_index").unwrap();
+        assert_eq!(
+            receipt_index.iter().map(|v| v.copied()).collect::<Vec<_>>(),
+            [Some(0), Some(0)]
+        );
+        let amount = transfers.column::<UInt64Array>("amount").unwrap();
+        assert_eq!(
+            amount.iter().map(|v| v.copied()).collect::<Vec<_>>(),
+            [Some(10), Some(20)]
+        );
+        let fee = transfers.column::<UInt64Array>("fee").unwrap();
+        assert_eq!(
+            fee.iter().map(|v| v.copied()).collect::<Vec<_>>(),
+            [Some(1), None]
+        );
+        let tags = transfers.column::<ListArray<i64>>("tags").unwrap();
+        assert_eq!(tags.value(1).len(), 3);
+
+        let statuses = &tables["Status"];
+        let variant = statuses.column::<Utf8Array<i32>>("value").unwrap();
+        assert_eq!(
+            variant.iter().collect::<Vec<_>>(),
+            [Some("Done"), Some("Pending")]
+        );
+        let done = statuses.column::<UInt64Array>("value.Done").unwrap();
+        assert_eq!(
+            done.iter().map(|v| v.copied()).collect::<Vec<_>>(),
+            [Some(1), None]
+        );
+    }
+
+    #[test]
+    fn test_decode_to_tables_binary_view_and_name_collisions() {
+        let mut decoder = decoder();
+        decoder.logged_types.insert(
+            7,
+            Arc::new(LoggedType {
+                log_id: 7,
+                name: "struct Checkpoint".to_owned(),
+                param_type: ParamType::Struct {
+                    name: "Checkpoint".to_owned(),
+                    fields: vec![
+                        ("block_height".to_owned(), ParamType::U64),
+                        ("receipt_tx_id".to_owned(), ParamType::U64),
+                        ("tx_id".to_owned(), ParamType::U64),
+                    ],
+                },
+            }),
+        );
+        let receipt = Receipt {
+            receipt_type: ReceiptType::LogData,
+            block_height: 5.into(),
+            rb: Some(7.into()),
+            data: Some([1u64, 2, 3].map(u64::to_be_bytes).concat().into()),
+            ..Default::default()
+        };
+        let batch = map_batch_to_binary_view(Receipt::to_arrow(&[receipt], None).unwrap());
+        assert_eq!(
+            batch
+                .schema
+                .fields
+                .iter()
+                .find(|f| f.name == "data")
+                .unwrap()
+                .data_type,
+            DataType::BinaryView
+        );
+
+        let tables = decoder.decode_to_tables(&batch).unwrap();
+        let table = &tables["Checkpoint"];
+        assert_eq!(
+            table
+                .schema
+                .fields
+                .iter()
+                .map(|f| f.name.as_str())
+                .collect::<Vec<_>>(),
+            [
+                "receipt_block_height",
+                "receipt_receipt_tx_id",
+                "receipt_index",
+                "root_contract_id",
+                "block_height",
+                "receipt_tx_id",
+                "tx_id",
+            ]
+        );
+        let block_height = |name| {
+            let col = table.column::<UInt64Array>(name).unwrap();
+            col.iter().map(|v| v.copied()).collect::<Vec<_>>()
+        };
+        assert_eq!(block_height("receipt_block_height"), [Some(5)]);
+        assert_eq!(block_height("block_height"), [Some(1)]);
+    }
+}
@@ -0,0 +1,20 @@
+//! Decoding of Sway program data using the program's JSON ABI.
+
+mod abi_decode;
+mod call_decoder;
+mod failure;
+mod log_decoder;
+mod log_tables;
+mod param_type;
+mod program;
+
+pub use abi_decode::{AbiDecode, SizedStr};
+pub use call_decoder::{
+    decode_return_data, fn_selector, legacy_fn_selector, AbiFunction, CallDecoder, DecodedCall,
+};
+pub use failure::{DecodedFailure, FailureCause};
+pub use log_decoder::{LogDecoder, LoggedType};
+pub use param_type::{DecodedValue, ParamType};
+pub use ruint::aliases::U256;
+
+pub(crate) use call_decoder::{call_data, pair_calls};
@@ -0,0 +1,260 @@
+use anyhow::{anyhow, Context, Result};
+use hyperfuel_format::{Hash, Hex};
+use ruint::aliases::U256;
+
+/// Type of a value in a Sway program, resolved from the JSON ABI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParamType {
+    /// `()`
+    Unit,
+    /// `bool`
+    Bool,
+    /// `u8`
+    U8,
+    /// `u16`
+    U16,
+    /// `u32`
+    U32,
+    /// `u64`
+    U64,
+    /// `u256`
+    U256,
+    /// `b256`
+    B256,
+    /// `std::bytes::Bytes`
+    Bytes,
+    /// `raw untyped slice`
+    RawSlice,
+    /// `std::string::String`
+    String,
+    /// `str`
+    StringSlice,
+    /// `str[N]`
+    StringArray(usize),
+    /// `[T; N]`
+    Array(Box<ParamType>, usize),
+    /// `std::vec::Vec<T>`
+    Vec(Box<ParamType>),
+    /// `(T1, T2, ...)`
+    Tuple(Vec<ParamType>),
+    /// Struct with its fields in declaration order.
+    Struct {
+        /// Path of the struct, e.g. `std::address::Address`.
+        name: String,
+        /// Field names and types.
+        fields: Vec<(String, ParamType)>,
+    },
+    /// Enum with its variants in declaration order.
+    Enum {
+        /// Path of the enum, e.g. `std::option::Option`.
+        name: String,
+        /// Variant names and types, unit variants have type `()`.
+        variants: Vec<(String, ParamType)>,
+    },
+}
+
+/// Value decoded from a Sway program's data, e.g. the payload of a LogData receipt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodedValue {
+    /// `()`
+    Unit,
+    /// `bool`
+    Bool(bool),
+    /// `u8`
+    U8(u8),
+    /// `u16`
+    U16(u16),
+    /// `u32`
+    U32(u32),
+    /// `u64`
+    U64(u64),
+    /// `u256`
+    U256(U256),
+    /// `b256`
+    B256(Hash),
+    /// `Bytes` and `raw untyped slice`
+    Bytes(Vec<u8>),
+    /// `String`, `str` and `str[N]`
+    String(String),
+    /// `[T; N]`
+    Array(Vec<DecodedValue>),
+    /// `Vec<T>`
+    Vec(Vec<DecodedValue>),
+    /// `(T1, T2, ...)`
+    Tuple(Vec<DecodedValue>),
+    /// Struct fields in declaration order.
+    Struct(Vec<(String, DecodedValue)>),
+    /// Enum variant and its value.
+    Enum {
+        /// Name of the variant.
+        variant: String,
+        /// Value of the variant, `Unit` for unit variants.
+        value: Box<DecodedValue>,
+    },
+}
+
+impl ParamType {
+    /// Decodes a value of this type from `data`, which must contain exactly one encoded value.
+    ///
+    /// Only the encoding version 1 (the default since forc 0.60) is supported.
+    pub fn decode(&self, data: &[u8]) -> Result<DecodedValue> {
+        let mut buf = data;
+        let value = self.decode_from(&mut buf)?;
+
+        if !buf.is_empty() {
+            return Err(anyhow!("{} trailing bytes after decoding value", buf.len()));
+        }
+
+        Ok(value)
+    }
+
+    pub(crate) fn decode_from(&self, buf: &mut &[u8]) -> Result<DecodedValue> {
+        let value = match self {
+            Self::Unit => DecodedValue::Unit,
+            Self::Bool => match take::<1>(buf)? {
+                [0] => DecodedValue::Bool(false),
+                [1] => DecodedValue::Bool(true),
+                [b] => return Err(anyhow!("invalid bool value {b}")),
+            },
+            Self::U8 => DecodedValue::U8(take::<1>(buf)?[0]),
+            Self::U16 => DecodedValue::U16(u16::from_be_bytes(take(buf)?)),
+            Self::U32 => DecodedValue::U32(u32::from_be_bytes(take(buf)?)),
+            Self::U64 => DecodedValue::U64(u64::from_be_bytes(take(buf)?)),
+            Self::U256 => DecodedValue::U256(U256::from_be_bytes(take::<32>(buf)?)),
+            Self::B256 => DecodedValue::B256(take::<32>(buf)?.into()),
+            Self::Bytes | Self::RawSlice => {
+                let len = take_len(buf)?;
+                DecodedValue::Bytes(take_slice(buf, len)?.to_vec())
+            }
+            Self::String | Self::StringSlice => {
+                let len = take_len(buf)?;
+                DecodedValue::String(take_str(buf, len)?)
+            }
+            Self::StringArray(len) => DecodedValue::String(take_str(buf, *len)?),
+            Self::Array(elem, len) => DecodedValue::Array(
+                (0..*len)
+                    .map(|_| elem.decode_from(buf))
+                    .collect::<Result<_>>()?,
+            ),
+            Self::Vec(elem) => {
+                let len = take_vec_len(buf)?;
+                let mut values = Vec::with_capacity(len);
+                for _ in 0..len {
+                    values.push(elem.decode_from(buf)?);
+                }
+                DecodedValue::Vec(values)
+            }
+            Self::Tuple(elems) => DecodedValue::Tuple(
+                elems
+                    .iter()
+                    .map(|elem| elem.decode_from(buf))
+                    .collect::<Result<_>>()?,
+            ),
+            Self::Struct { name, fields } => DecodedValue::Struct(
+                fields
+                    .iter()
+                    .map(|(field, ty)| {
+                        let value = ty
+                            .decode_from(buf)
+                            .with_context(|| format!("decode field {name}.{field}"))?;
+                        Ok((field.clone(), value))
+                    })
+                    .collect::<Result<_>>()?,
+            ),
+            Self::Enum { name, variants } => {
+                let discriminant = u64::from_be_bytes(take(buf)?);
+                let (variant, ty) = usize::try_from(discriminant)
+                    .ok()
+                    .and_then(|idx| variants.get(idx))
+                    .with_context(|| format!("invalid discriminant {discriminant} for {name}"))?;
+                let value = ty
+                    .decode_from(buf)
+                    .with_context(|| format!("decode variant {name}::{variant}"))?;
+                DecodedValue::Enum {
+                    variant: variant.clone(),
+                    value: Box::new(value),
+                }
+            }
+        };
+
+        Ok(value)
+    }
+}
+
+impl DecodedValue {
+    /// Converts the value to JSON.
+    ///
+    /// Structs become objects, enums become `{"Variant": value}` objects, `u256` becomes a
+    /// decimal string and binary values become prefixed hex strings.
+    pub fn to_json(&self) -> serde_json::Value {
+        use serde_json::Value;
+
+        match self {
+            Self::Unit => Value::Null,
+            Self::Bool(v) => Value::Bool(*v),
+            Self::U8(v) => Value::from(*v),
+            Self::U16(v) => Value::from(*v),
+            Self::U32(v) => Value::from(*v),
+            Self::U64(v) => Value::from(*v),
+            Self::U256(v) => Value::String(v.to_string()),
+            Self::B256(v) => Value::String(v.encode_hex()),
+            Self::Bytes(v) => Value::String(format!("0x{}", faster_hex::hex_string(v))),
+            Self::String(v) => Value::String(v.clone()),
+            Self::Array(v) | Self::Vec(v) | Self::Tuple(v) => {
+                Value::Array(v.iter().map(Self::to_json).collect())
+            }
+            Self::Struct(fields) => Value::Object(
+                fields
+                    .iter()
+                    .map(|(name, value)| (name.clone(), value.to_json()))
+                    .collect(),
+            ),
+            Self::Enum { variant, value } => {
+                Value::Object([(variant.clone(), value.to_json())].into_iter().collect())
+            }
+        }
+    }
+}
+
+pub(super) fn take<const N: usize>(buf: &mut &[u8]) -> Result<[u8; N]> {
+    let bytes = take_slice(buf, N)?;
+    Ok(bytes.try_into().unwrap())
+}
+
+pub(super) fn take_len(buf: &mut &[u8]) -> Result<usize> {
+    let len = u64::from_be_bytes(take(buf)?);
+    usize::try_from(len).context("length prefix overflows usize")
+}
+
+/// Reads the length prefix of a vector, which can't be more than the bytes left since every
+/// element takes at least one byte. This also rejects vectors of `()`, which Sway programs don't
+/// log, so that a bad prefix can't make decoding spin or allocate a huge vector.
+pub(super) fn take_vec_len(buf: &mut &[u8]) -> Result<usize> {
+    let len = take_len(buf)?;
+    if len > buf.len() {
+        return Err(anyhow!(
+            "vector length {} is more than the {} bytes left",
+            len,
+            buf.len()
+        ));
+    }
+    Ok(len)
+}
+
+pub(super) fn take_slice<'a>(buf: &mut &'a [u8], len: usize) -> Result<&'a [u8]> {
+    if buf.len() < len {
+        return Err(anyhow!(
+            "unexpected end of data, need {} bytes but {} are left",
+            len,
+            buf.len()
+        ));
+    }
+    let (bytes, rest) = buf.split_at(len);
+    *buf = rest;
+    Ok(bytes)
+}
+
+pub(super) fn take_str(buf: &mut &[u8], len: usize) -> Result<String> {
+    let bytes = take_slice(buf, len)?;
+    String::from_utf8(bytes.to_vec()).context("decode utf8 string")
+}
@@ -0,0 +1,66 @@
+use anyhow::Result;
+use hyperfuel_abi::{MetadataType, Primitive, ProgramAbi, Resolver, TypeBuilder};
+
+use super::ParamType;
+
+/// Resolves the concrete type with the given id into a [`ParamType`], substituting
+/// generic parameters with the type arguments.
+pub(crate) fn resolve(abi: &ProgramAbi, concrete_type_id: &str) -> Result<ParamType> {
+    Ok(Resolver::new(abi).concrete(&mut ParamTypes, concrete_type_id)?)
+}
+
+struct ParamTypes;
+
+impl TypeBuilder for ParamTypes {
+    type Type = ParamType;
+
+    fn primitive(&mut self, primitive: Primitive) -> ParamType {
+        match primitive {
+            Primitive::Unit => ParamType::Unit,
+            Primitive::Bool => ParamType::Bool,
+            Primitive::U8 => ParamType::U8,
+            Primitive::U16 => ParamType::U16,
+            Primitive::U32 => ParamType::U32,
+            Primitive::U64 => ParamType::U64,
+            Primitive::U256 => ParamType::U256,
+            Primitive::B256 => ParamType::B256,
+            Primitive::Bytes => ParamType::Bytes,
+            Primitive::RawSlice => ParamType::RawSlice,
+            Primitive::String => ParamType::String,
+            Primitive::StringSlice => ParamType::StringSlice,
+            Primitive::StringArray(len) => ParamType::StringArray(len),
+        }
+    }
+
+    fn vec(&mut self, elem: ParamType) -> ParamType {
+        ParamType::Vec(Box::new(elem))
+    }
+
+    fn tuple(&mut self, elems: Vec<ParamType>) -> ParamType {
+        ParamType::Tuple(elems)
+    }
+
+    fn array(&mut self, elem: ParamType, len: usize) -> ParamType {
+        ParamType::Array(Box::new(elem), len)
+    }
+
+    fn named(
+        &mut self,
+        resolver: &Resolver<'_>,
+        ty: &MetadataType,
+        args: Vec<ParamType>,
+    ) -> hyperfuel_abi::Result<ParamType> {
+        let components = resolver.components(self, ty, args)?;
+
+        match ty.type_field.strip_prefix("struct ") {
+            Some(name) => Ok(ParamType::Struct {
+                name: name.to_owned(),
+                fields: components,
+            }),
+            None => Ok(ParamType::Enum {
+                name: ty.type_field.trim_start_matches("enum ").to_owned(),
+                variants: components,
+            }),
+        }
+    }
+}
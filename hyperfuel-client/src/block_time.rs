@@ -0,0 +1,193 @@
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::Mutex;
+
+use anyhow::{anyhow, Context, Result};
+use hyperfuel_format::Timestamp;
+use hyperfuel_net_types::{FieldSelection, Query};
+use polars_arrow::array::UInt64Array;
+
+use crate::{
+    util::{find_column, TimeColumn},
+    ArrowResponse,
+};
+
+/// Sparse index of the block times that were looked up, used to narrow later searches.
+///
+/// Block times never decrease with height, so a cached block bounds the search for any time.
+#[derive(Debug, Default)]
+pub(crate) struct BlockTimeIndex {
+    times: BTreeMap<u64, Timestamp>,
+}
+
+impl BlockTimeIndex {
+    /// Returns the range `[lo, hi)` that the first block that isn't `is_before` is in, given
+    /// that the last block is `head`.
+    fn bounds(&self, head: u64, is_before: &impl Fn(Timestamp) -> bool) -> (u64, u64) {
+        let mut lo = 0;
+        let mut hi = head + 1;
+
+        for (&height, &time) in self.times.range(..=head) {
+            if is_before(time) {
+                lo = height + 1;
+            } else {
+                hi = hi.min(height);
+                break;
+            }
+        }
+
+        (lo, hi)
+    }
+}
+
+/// Binary searches for the first block up to `head` whose time isn't `is_before`, returns
+/// `head + 1` if there is none. `fetch` looks up the time of a block.
+pub(crate) async fn partition_point<F, Fut>(
+    index: &Mutex<BlockTimeIndex>,
+    head: u64,
+    is_before: impl Fn(Timestamp) -> bool,
+    mut fetch: F,
+) -> Result<u64>
+where
+    F: FnMut(u64) -> Fut,
+    Fut: Future<Output = Result<Timestamp>>,
+{
+    let (mut lo, mut hi) = index.lock().unwrap().bounds(head, &is_before);
+
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        let time = fetch(mid)
+            .await
+            .with_context(|| format!("get time of block {mid}"))?;
+        index.lock().unwrap().times.insert(mid, time);
+
+        if is_before(time) {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+
+    Ok(lo)
+}
+
+/// Query for the height and time of a single block.
+pub(crate) fn block_time_query(height: u64) -> Query {
+    Query {
+        from_block: height,
+        to_block: Some(height + 1),
+        include_all_blocks: true,
+        field_selection: FieldSelection {
+            block: ["height", "time"].map(String::from).into(),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+/// Reads the time of block `height` from the response of [`block_time_query`].
+pub(crate) fn block_time_from_response(res: &ArrowResponse, height: u64) -> Result<Timestamp> {
+    for batch in res.data.blocks.iter() {
+        let heights = batch.column::<UInt64Array>("height")?;
+        let time = find_column(batch, "time").context("get time column")?;
+        let time = TimeColumn::new(time, "time")?;
+
+        let Some(row) = heights.iter().position(|h| h == Some(&height)) else {
+            continue;
+        };
+
+        let label = time.get(row);
+
+        return label
+            .map(Timestamp::from_tai64)
+            .context("time of block is null");
+    }
+
+    Err(anyhow!("block {height} is missing from the response"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn times() -> Vec<Timestamp> {
+        [10, 10, 12, 15, 15, 15, 20, 21]
+            .map(Timestamp::from_tai64)
+            .to_vec()
+    }
+
+    async fn search(
+        index: &Mutex<BlockTimeIndex>,
+        is_before: impl Fn(Timestamp) -> bool,
+        fetched: &mut Vec<u64>,
+    ) -> u64 {
+        let times = times();
+        partition_point(index, times.len() as u64 - 1, is_before, |h| {
+            fetched.push(h);
+            let time = times[h as usize];
+            async move { Ok(time) }
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_partition_point() {
+        let index = Mutex::default();
+        let mut fetched = Vec::new();
+        let at = Timestamp::from_tai64;
+
+        // first block at or after 15
+        assert_eq!(search(&index, |t| t < at(15), &mut fetched).await, 3);
+        // last block at or before 15 is the one before the first block after 15
+        assert_eq!(search(&index, |t| t <= at(15), &mut fetched).await, 6);
+        assert_eq!(search(&index, |t| t < at(9), &mut fetched).await, 0);
+        assert_eq!(search(&index, |t| t < at(22), &mut fetched).await, 8);
+
+        // the cache narrows later searches down to nothing
+        fetched.clear();
+        assert_eq!(search(&index, |t| t < at(15), &mut fetched).await, 3);
+        assert!(fetched.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_partition_point_error() {
+        let index = Mutex::default();
+        let res = partition_point(&index, 5, |_| true, |_| async { Err(anyhow!("timeout")) }).await;
+        assert!(res.is_err());
+        assert!(index.lock().unwrap().times.is_empty());
+    }
+
+    #[test]
+    fn test_block_time_from_response() {
+        use crate::{ArrowBatch, ArrowChunk};
+        use polars_arrow::array::Int64Array;
+        use polars_arrow::datatypes::{ArrowDataType as DataType, ArrowSchema as Schema, Field};
+
+        let mut data = crate::ArrowResponseData::default();
+        data.blocks.push(ArrowBatch {
+            chunk: ArrowChunk::new(vec![
+                UInt64Array::from_slice([7]).boxed(),
+                Int64Array::from_slice([42]).boxed(),
+            ])
+            .into(),
+            schema: Schema::from(vec![
+                Field::new("height", DataType::UInt64, false),
+                Field::new("time", DataType::Int64, false),
+            ])
+            .into(),
+        });
+        let res = ArrowResponse {
+            archive_height: Some(7),
+            next_block: 8,
+            total_execution_time: 0,
+            data,
+        };
+
+        assert_eq!(
+            block_time_from_response(&res, 7).unwrap(),
+            Timestamp::from_tai64(42)
+        );
+        assert!(block_time_from_response(&res, 8).is_err());
+    }
+}
@@ -1,19 +1,26 @@
+use std::any::type_name;
 use std::collections::BTreeMap;
+use std::fmt;
 
 use anyhow::{anyhow, Context, Result};
+use hyperfuel_format::{
+    parse_witnesses, unpack_hashes, InputType, OutputType, ReceiptType, Timestamp,
+    TransactionStatus, TransactionType,
+};
 use hyperfuel_schema::ArrowChunk;
 use polars_arrow::array::{
-    Array, BinaryArray, Float32Array, Float64Array, Int32Array, Int64Array, MutablePrimitiveArray,
-    PrimitiveArray, UInt32Array, UInt64Array,
+    Array, ArrayFromIter, BinaryArray, DictionaryArray, FixedSizeBinaryArray, Int64Array,
+    ListArray, PrimitiveArray, UInt64Array, UInt8Array, Utf8Array, Utf8ViewArray,
 };
-use polars_arrow::compute::cast::CastOptionsImpl as CastOptions;
-use polars_arrow::compute::{self, cast};
-use polars_arrow::datatypes::{ArrowDataType, ArrowSchema as Schema, Field};
+use polars_arrow::bitmap::MutableBitmap;
+use polars_arrow::datatypes::{ArrowDataType, ArrowSchema as Schema, Field, IntegerType, TimeUnit};
+use polars_arrow::offset::Offsets;
 use polars_arrow::types::NativeType;
 use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
 use ruint::aliases::U256;
 use serde::{Deserialize, Serialize};
 
+use crate::util::{hex_encode_prefixed, unpack_hash_list};
 use crate::ArrowBatch;
 
 /// Column mapping for stream function output.
@@ -40,6 +47,8 @@ pub struct ColumnMapping {
 #[allow(missing_docs)]
 /// `DataType` is an enumeration representing the different data types that can be used in the column mapping.
 /// Each variant corresponds to a specific data type.
+///
+/// Integer targets fail with the offending row if a value doesn't fit, instead of wrapping.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum DataType {
@@ -49,6 +58,31 @@ pub enum DataType {
     UInt32,
     Int64,
     Int32,
+    /// Variant names of enum columns, e.g. "Script", "Success" or "LogData". See
+    /// [`DataType::Dictionary`] for the enum columns.
+    Utf8,
+    /// Variant names of enum columns as a dictionary keyed by the `UInt8` discriminants. Enum
+    /// columns are `tx_type`, `tx_status`, `status`, `receipt_type`, `input_type` and
+    /// `output_type`.
+    Dictionary,
+    /// Decimal with the given precision and scale, for amounts. The integer value is the unscaled
+    /// value, so an amount of base units of an asset with 9 decimals maps to `[38, 9]`.
+    Decimal128(usize, usize),
+    /// Base 10 string of integers, exact for values that don't fit in 64 bits.
+    DecimalString,
+    /// Binary values of exactly the given number of bytes, e.g. 32 for hashes.
+    FixedSizeBinary(usize),
+    /// UTC timestamp with nanosecond precision of the TAI64 labels in `time` columns.
+    Timestamp,
+    /// `0x` prefixed hex string of binary columns.
+    Hex,
+    /// Base58 string of binary columns.
+    Base58,
+    /// List of 32 byte hashes, for the packed `input_asset_ids`, `input_contracts` and
+    /// `proof_set` transaction columns.
+    HashList,
+    /// List of the individual witnesses of the `witnesses` transaction column.
+    BinaryList,
 }
 
 impl From<DataType> for ArrowDataType {
@@ -60,10 +94,28 @@ impl From<DataType> for ArrowDataType {
             DataType::UInt32 => Self::UInt32,
             DataType::Int64 => Self::Int64,
             DataType::Int32 => Self::Int32,
+            DataType::Utf8 | DataType::DecimalString | DataType::Hex | DataType::Base58 => {
+                Self::Utf8
+            }
+            DataType::Dictionary => {
+                Self::Dictionary(IntegerType::UInt8, Box::new(Self::Utf8View), false)
+            }
+            DataType::Decimal128(precision, scale) => Self::Decimal(precision, scale),
+            DataType::FixedSizeBinary(size) => Self::FixedSizeBinary(size),
+            DataType::Timestamp => Self::Timestamp(TimeUnit::Nanosecond, Some("UTC".to_owned())),
+            DataType::HashList => {
+                ListArray::<i64>::default_datatype(Self::FixedSizeBinary(HASH_SIZE))
+            }
+            DataType::BinaryList => ListArray::<i64>::default_datatype(Self::Binary),
         }
     }
 }
 
+const HASH_SIZE: usize = 32;
+
+/// Largest precision of a 128 bit decimal.
+const MAX_DECIMAL128_PRECISION: usize = 38;
+
 pub fn apply_to_batch(
     batch: &ArrowBatch,
     mapping: &BTreeMap<String, DataType>,
@@ -79,15 +131,8 @@ pub fn apply_to_batch(
         .zip(batch.schema.fields.par_iter())
         .map(|(col, field)| {
             let col = match mapping.get(&field.name) {
-                Some(&dt) => {
-                    if field.name == "l1_fee_scalar" {
-                        map_l1_fee_scalar(&**col, dt)
-                            .context(format!("apply cast to column '{}'", field.name))?
-                    } else {
-                        map_column(&**col, dt)
-                            .context(format!("apply cast to column '{}'", field.name))?
-                    }
-                }
+                Some(&dt) => map_column(&**col, &field.name, dt)
+                    .context(format!("apply cast to column '{}'", field.name))?,
                 None => col.clone(),
             };
 
@@ -108,137 +153,584 @@ pub fn apply_to_batch(
     })
 }
 
-pub fn map_l1_fee_scalar(
+fn to_box<T: Array>(arr: T) -> Box<dyn Array> {
+    Box::new(arr)
+}
+
+fn map_column(
     col: &dyn Array,
+    name: &str,
     target_data_type: DataType,
 ) -> Result<Box<dyn Array + 'static>> {
-    let col = col.as_any().downcast_ref::<BinaryArray<i32>>().unwrap();
-    let col = Float64Array::from_iter(
-        col.iter()
-            .map(|v| v.map(|v| std::str::from_utf8(v).unwrap().parse().unwrap())),
-    );
-
-    let arr = compute::cast::cast(
-        &*to_box(col),
-        &target_data_type.into(),
-        CastOptions {
-            wrapped: false,
-            partial: false,
-        },
-    )
-    .with_context(|| anyhow!("failed to l1_fee_scalar to {:?}", target_data_type))?;
-
-    Ok(arr)
+    match target_data_type {
+        DataType::Float64 => map_to_primitive::<f64>(col).map(to_box),
+        DataType::Float32 => map_to_primitive::<f32>(col).map(to_box),
+        DataType::UInt64 => map_to_primitive::<u64>(col).map(to_box),
+        DataType::UInt32 => map_to_primitive::<u32>(col).map(to_box),
+        DataType::Int64 => map_to_primitive::<i64>(col).map(to_box),
+        DataType::Int32 => map_to_primitive::<i32>(col).map(to_box),
+        DataType::Utf8 => map_to_names(col, name),
+        DataType::Dictionary => map_to_dictionary(col, name),
+        DataType::Decimal128(precision, scale) => map_to_decimal(col, precision, scale),
+        DataType::DecimalString => map_to_decimal_string(col),
+        DataType::FixedSizeBinary(size) => map_to_fixed_size_binary(col, size),
+        DataType::Timestamp => map_to_timestamp(col),
+        DataType::Hex => map_binary_to_utf8(col, hex_encode_prefixed),
+        DataType::Base58 => map_binary_to_utf8(col, |v| bs58::encode(v).into_string()),
+        DataType::HashList | DataType::BinaryList => map_to_list(col, name, target_data_type),
+    }
 }
 
-fn to_box<T: Array>(arr: T) -> Box<dyn Array> {
-    Box::new(arr)
+/// Value of an integer column, or of a binary column holding big endian unsigned integers.
+#[derive(Debug, Clone, Copy)]
+enum Integer {
+    Signed(i64),
+    Unsigned(U256),
 }
 
-fn map_column(col: &dyn Array, target_data_type: DataType) -> Result<Box<dyn Array + 'static>> {
-    match target_data_type {
-        DataType::Float64 => map_to_f64(col).map(to_box),
-        DataType::Float32 => map_to_f32(col).map(to_box),
-        DataType::UInt64 => map_to_uint64(col).map(to_box),
-        DataType::UInt32 => map_to_uint32(col).map(to_box),
-        DataType::Int64 => map_to_int64(col).map(to_box),
-        DataType::Int32 => map_to_int32(col).map(to_box),
+impl fmt::Display for Integer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Signed(v) => v.fmt(f),
+            Self::Unsigned(v) => v.fmt(f),
+        }
     }
 }
 
-fn map_to_f64(col: &dyn Array) -> Result<Float64Array> {
-    match col.data_type() {
-        &ArrowDataType::Binary => {
-            binary_to_target_array(col.as_any().downcast_ref::<BinaryArray<i32>>().unwrap())
+/// Conversion of an [`Integer`] that fails if the value doesn't fit in the target type.
+trait FromInteger: NativeType {
+    fn from_integer(val: Integer) -> Option<Self>;
+}
+
+macro_rules! impl_from_integer {
+    ($($ty:ty),*) => {
+        $(
+            impl FromInteger for $ty {
+                fn from_integer(val: Integer) -> Option<Self> {
+                    match val {
+                        Integer::Signed(v) => Self::try_from(v).ok(),
+                        Integer::Unsigned(v) => Self::try_from(v).ok(),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(u32, u64, i32, i64, i128);
+
+impl FromInteger for f64 {
+    fn from_integer(val: Integer) -> Option<Self> {
+        match val {
+            Integer::Signed(v) => Some(v as f64),
+            Integer::Unsigned(v) => Some(v.into()),
         }
-        &ArrowDataType::UInt64 => Ok(cast::primitive_as_primitive(
-            col.as_any().downcast_ref::<UInt64Array>().unwrap(),
-            &ArrowDataType::Float64,
-        )),
-        dt => Err(anyhow!("Can't convert {:?} to f64", dt)),
     }
 }
 
-fn map_to_f32(col: &dyn Array) -> Result<Float32Array> {
-    match col.data_type() {
-        &ArrowDataType::Binary => {
-            binary_to_target_array(col.as_any().downcast_ref::<BinaryArray<i32>>().unwrap())
+impl FromInteger for f32 {
+    fn from_integer(val: Integer) -> Option<Self> {
+        match val {
+            Integer::Signed(v) => Some(v as f32),
+            Integer::Unsigned(v) => Some(v.into()),
         }
-        &ArrowDataType::UInt64 => Ok(cast::primitive_as_primitive(
-            col.as_any().downcast_ref::<UInt64Array>().unwrap(),
-            &ArrowDataType::Float32,
-        )),
-        dt => Err(anyhow!("Can't convert {:?} to f32", dt)),
     }
 }
 
-fn map_to_uint64(col: &dyn Array) -> Result<UInt64Array> {
+fn integers(col: &dyn Array) -> Result<Vec<Option<Integer>>> {
+    let unsigned = |v: u64| Integer::Unsigned(U256::from(v));
+
     match col.data_type() {
-        &ArrowDataType::Binary => {
-            binary_to_target_array(col.as_any().downcast_ref::<BinaryArray<i32>>().unwrap())
-        }
-        &ArrowDataType::UInt64 => Ok(cast::primitive_as_primitive(
-            col.as_any().downcast_ref::<UInt64Array>().unwrap(),
-            &ArrowDataType::UInt64,
-        )),
-        dt => Err(anyhow!("Can't convert {:?} to uint64", dt)),
+        ArrowDataType::Binary => col
+            .as_any()
+            .downcast_ref::<BinaryArray<i32>>()
+            .unwrap()
+            .iter()
+            .enumerate()
+            .map(|(row, v)| {
+                v.map(|v| {
+                    U256::try_from_be_slice(v)
+                        .map(Integer::Unsigned)
+                        .ok_or_else(|| anyhow!("value at row {row} has {} bytes, more than the 32 bytes of a 256 bit integer", v.len()))
+                })
+                .transpose()
+            })
+            .collect(),
+        ArrowDataType::UInt64 => Ok(col
+            .as_any()
+            .downcast_ref::<UInt64Array>()
+            .unwrap()
+            .iter()
+            .map(|v| v.map(|&v| unsigned(v)))
+            .collect()),
+        ArrowDataType::UInt8 => Ok(col
+            .as_any()
+            .downcast_ref::<UInt8Array>()
+            .unwrap()
+            .iter()
+            .map(|v| v.map(|&v| unsigned(v.into())))
+            .collect()),
+        ArrowDataType::Int64 => Ok(col
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap()
+            .iter()
+            .map(|v| v.map(|&v| Integer::Signed(v)))
+            .collect()),
+        dt => Err(anyhow!("{:?} is not an integer column", dt)),
     }
 }
 
-fn map_to_uint32(col: &dyn Array) -> Result<UInt32Array> {
-    match col.data_type() {
-        &ArrowDataType::Binary => {
-            binary_to_target_array(col.as_any().downcast_ref::<BinaryArray<i32>>().unwrap())
+fn map_to_primitive<T: FromInteger>(col: &dyn Array) -> Result<PrimitiveArray<T>> {
+    let values = integers(col)
+        .with_context(|| {
+            anyhow!(
+                "Can't convert {:?} to {}",
+                col.data_type(),
+                type_name::<T>()
+            )
+        })?
+        .into_iter()
+        .enumerate()
+        .map(|(row, v)| {
+            v.map(|v| {
+                T::from_integer(v)
+                    .ok_or_else(|| anyhow!("value {v} at row {row} overflows {}", type_name::<T>()))
+            })
+            .transpose()
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(PrimitiveArray::from(values))
+}
+
+fn map_to_decimal(
+    col: &dyn Array,
+    precision: usize,
+    scale: usize,
+) -> Result<Box<dyn Array + 'static>> {
+    if !(1..=MAX_DECIMAL128_PRECISION).contains(&precision) || scale > precision {
+        return Err(anyhow!(
+            "invalid decimal128({precision}, {scale}), precision has to be between 1 and \
+             {MAX_DECIMAL128_PRECISION} and scale can't exceed it"
+        ));
+    }
+    let max = 10i128.pow(precision as u32);
+
+    let values = map_to_primitive::<i128>(col)?;
+    for (row, v) in values.iter().enumerate() {
+        if let Some(v) = v.filter(|v| v.unsigned_abs() >= max.unsigned_abs()) {
+            return Err(anyhow!(
+                "value {v} at row {row} has more than {precision} digits"
+            ));
         }
-        &ArrowDataType::UInt64 => Ok(cast::primitive_as_primitive(
-            col.as_any().downcast_ref::<UInt64Array>().unwrap(),
-            &ArrowDataType::UInt32,
-        )),
-        dt => Err(anyhow!("Can't convert {:?} to uint32", dt)),
     }
+
+    Ok(to_box(values.to(ArrowDataType::Decimal(precision, scale))))
 }
 
-fn map_to_int64(col: &dyn Array) -> Result<Int64Array> {
-    match col.data_type() {
-        &ArrowDataType::Binary => {
-            binary_to_target_array(col.as_any().downcast_ref::<BinaryArray<i32>>().unwrap())
+fn map_to_decimal_string(col: &dyn Array) -> Result<Box<dyn Array + 'static>> {
+    let values = integers(col)
+        .with_context(|| anyhow!("Can't convert {:?} to a decimal string", col.data_type()))?;
+
+    Ok(to_box(
+        values
+            .iter()
+            .map(|v| v.map(|v| v.to_string()))
+            .collect::<Utf8Array<i32>>(),
+    ))
+}
+
+fn map_to_timestamp(col: &dyn Array) -> Result<Box<dyn Array + 'static>> {
+    let values = map_to_primitive::<u64>(col)?
+        .iter()
+        .enumerate()
+        .map(|(row, v)| {
+            v.map(|&v| {
+                Timestamp::from_tai64(v).unix_nanos().ok_or_else(|| {
+                    anyhow!(
+                        "TAI64 label {v} at row {row} is out of the range of nanosecond timestamps"
+                    )
+                })
+            })
+            .transpose()
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(to_box(
+        Int64Array::from(values).to(DataType::Timestamp.into()),
+    ))
+}
+
+fn binary_column(col: &dyn Array) -> Result<&BinaryArray<i32>> {
+    col.as_any()
+        .downcast_ref::<BinaryArray<i32>>()
+        .ok_or_else(|| anyhow!("{:?} is not a binary column", col.data_type()))
+}
+
+fn map_to_fixed_size_binary(col: &dyn Array, size: usize) -> Result<Box<dyn Array + 'static>> {
+    let col = binary_column(col)?;
+
+    let mut values = Vec::with_capacity(col.len() * size);
+    for (row, v) in col.iter().enumerate() {
+        match v {
+            Some(v) if v.len() != size => {
+                return Err(anyhow!(
+                    "value at row {row} has {} bytes, expected {size}",
+                    v.len()
+                ))
+            }
+            Some(v) => values.extend_from_slice(v),
+            None => values.resize(values.len() + size, 0),
         }
-        &ArrowDataType::UInt64 => Ok(cast::primitive_as_primitive(
-            col.as_any().downcast_ref::<UInt64Array>().unwrap(),
-            &ArrowDataType::Int64,
-        )),
-        dt => Err(anyhow!("Can't convert {:?} to int64", dt)),
     }
+
+    let arr = FixedSizeBinaryArray::try_new(
+        ArrowDataType::FixedSizeBinary(size),
+        values.into(),
+        col.validity().cloned(),
+    )
+    .context("construct fixed size binary array")?;
+
+    Ok(to_box(arr))
 }
 
-fn map_to_int32(col: &dyn Array) -> Result<Int32Array> {
-    match col.data_type() {
-        &ArrowDataType::Binary => {
-            binary_to_target_array(col.as_any().downcast_ref::<BinaryArray<i32>>().unwrap())
+fn map_binary_to_utf8(
+    col: &dyn Array,
+    encode: impl Fn(&[u8]) -> String,
+) -> Result<Box<dyn Array + 'static>> {
+    let col = binary_column(col)?;
+
+    Ok(to_box(
+        col.iter()
+            .map(|v| v.map(&encode))
+            .collect::<Utf8Array<i32>>(),
+    ))
+}
+
+/// Returns the function that names the discriminants of an enum column.
+fn variant_namer(name: &str) -> Result<fn(u8) -> String> {
+    let namer: fn(u8) -> String = match name {
+        "tx_type" => |v| TransactionType::from_u8(v).to_string(),
+        "tx_status" | "status" => |v| TransactionStatus::from_u8(v).to_string(),
+        "receipt_type" => |v| ReceiptType::from_u8(v).to_string(),
+        "input_type" => |v| InputType::from_u8(v).to_string(),
+        "output_type" => |v| OutputType::from_u8(v).to_string(),
+        _ => {
+            return Err(anyhow!(
+                "only tx_type, tx_status, status, receipt_type, input_type and output_type \
+                 columns can be mapped to variant names"
+            ))
         }
-        &ArrowDataType::UInt64 => Ok(cast::primitive_as_primitive(
-            col.as_any().downcast_ref::<UInt64Array>().unwrap(),
-            &ArrowDataType::Int32,
-        )),
-        dt => Err(anyhow!("Can't convert {:?} to int32", dt)),
-    }
+    };
+
+    Ok(namer)
+}
+
+fn enum_column(col: &dyn Array) -> Result<&UInt8Array> {
+    col.as_any()
+        .downcast_ref::<UInt8Array>()
+        .ok_or_else(|| anyhow!("Can't convert {:?} to variant names", col.data_type()))
+}
+
+fn map_to_names(col: &dyn Array, name: &str) -> Result<Box<dyn Array + 'static>> {
+    let namer = variant_namer(name)?;
+    let col = enum_column(col)?;
+
+    Ok(to_box(
+        col.iter()
+            .map(|v| v.map(|&v| namer(v)))
+            .collect::<Utf8Array<i32>>(),
+    ))
 }
 
-fn binary_to_target_array<T: NativeType + TryFrom<U256>>(
-    src: &BinaryArray<i32>,
-) -> Result<PrimitiveArray<T>> {
-    let mut out = MutablePrimitiveArray::with_capacity(src.len());
+fn map_to_dictionary(col: &dyn Array, name: &str) -> Result<Box<dyn Array + 'static>> {
+    let namer = variant_namer(name)?;
+    let col = enum_column(col)?;
+
+    let max = col.iter().flatten().copied().max().unwrap_or(0);
+    // parquet only supports view or large utf8 dictionary values
+    let names = Utf8ViewArray::arr_from_iter((0..=max).map(|v| Some(namer(v))));
+
+    let arr =
+        DictionaryArray::<u8>::try_new(DataType::Dictionary.into(), col.clone(), names.boxed())
+            .context("construct dictionary array")?;
+
+    Ok(to_box(arr))
+}
 
-    for val in src.iter() {
-        out.push(val.map(binary_to_target).transpose()?);
+/// Splits each value of a packed binary column into the items of a list column.
+fn map_to_list(
+    col: &dyn Array,
+    name: &str,
+    target_data_type: DataType,
+) -> Result<Box<dyn Array + 'static>> {
+    let col = col
+        .as_any()
+        .downcast_ref::<BinaryArray<i32>>()
+        .ok_or_else(|| anyhow!("Can't convert {:?} to a list", col.data_type()))?;
+
+    let split: fn(&[u8]) -> Result<Vec<Vec<u8>>> = match (target_data_type, name) {
+        (DataType::HashList, "input_asset_ids" | "input_contracts") => |v| {
+            Ok(unpack_hash_list(v)?
+                .iter()
+                .map(|h| h.as_slice().to_vec())
+                .collect())
+        },
+        (DataType::HashList, "proof_set") => |v| {
+            Ok(unpack_hashes(v)?
+                .iter()
+                .map(|h| h.as_slice().to_vec())
+                .collect())
+        },
+        (DataType::BinaryList, "witnesses") => |v| {
+            Ok(parse_witnesses(v)?
+                .iter()
+                .map(|w| w.as_ref().to_vec())
+                .collect())
+        },
+        _ => {
+            return Err(anyhow!(
+                "only input_asset_ids, input_contracts and proof_set columns can be mapped to \
+                 hashlist and only the witnesses column can be mapped to binarylist"
+            ))
+        }
+    };
+
+    let mut offsets = Offsets::<i64>::with_capacity(col.len());
+    let mut validity = MutableBitmap::with_capacity(col.len());
+    let mut items = Vec::new();
+
+    for val in col.iter() {
+        match val {
+            Some(val) => {
+                let val = split(val)?;
+                offsets.try_push(val.len()).context("push list offset")?;
+                validity.push(true);
+                items.extend(val);
+            }
+            None => {
+                offsets.extend_constant(1);
+                validity.push(false);
+            }
+        }
     }
 
-    Ok(out.into())
+    let values = match target_data_type {
+        DataType::HashList => FixedSizeBinaryArray::new(
+            ArrowDataType::FixedSizeBinary(HASH_SIZE),
+            items.concat().into(),
+            None,
+        )
+        .boxed(),
+        _ => BinaryArray::<i32>::from_iter_values(items.iter()).boxed(),
+    };
+
+    let arr = ListArray::<i64>::try_new(
+        target_data_type.into(),
+        offsets.into(),
+        values,
+        validity.into(),
+    )
+    .context("construct list array")?;
+
+    Ok(to_box(arr))
 }
 
-fn binary_to_target<T: TryFrom<U256>>(src: &[u8]) -> Result<T> {
-    let big_num = U256::from_be_slice(src);
-    big_num
-        .try_into()
-        .map_err(|_e| anyhow!("failed to cast number to requested type"))
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hyperfuel_format::TAI64_UNIX_EPOCH;
+
+    #[test]
+    fn test_map_to_names() {
+        let col = UInt8Array::from([Some(0), Some(5), None, Some(19)]);
+        let names = map_to_names(&col, "tx_type").unwrap();
+        let names = names.as_any().downcast_ref::<Utf8Array<i32>>().unwrap();
+        assert_eq!(
+            names.iter().collect::<Vec<_>>(),
+            [Some("Script"), Some("Blob"), None, Some("Unknown(19)")]
+        );
+
+        let col = UInt8Array::from_slice([1, 0, 3]);
+        let names = map_to_names(&col, "tx_status").unwrap();
+        let names = names.as_any().downcast_ref::<Utf8Array<i32>>().unwrap();
+        assert_eq!(
+            names.iter().collect::<Vec<_>>(),
+            [Some("Success"), Some("Failure"), Some("Unknown(3)")]
+        );
+
+        let names = map_to_names(&UInt8Array::from_slice([9]), "tx_status").unwrap();
+        let names = names.as_any().downcast_ref::<Utf8Array<i32>>().unwrap();
+        assert_eq!(names.value(0), "Unknown(9)");
+        assert!(map_to_names(&col, "amount").is_err());
+    }
+
+    #[test]
+    fn test_map_uint8() {
+        let col = UInt8Array::from_slice([2, 3]);
+        let out = map_column(&col, "receipt_type", DataType::Int64).unwrap();
+        let out = out.as_any().downcast_ref::<Int64Array>().unwrap();
+        assert_eq!(out.values().as_slice(), &[2, 3]);
+    }
+
+    #[test]
+    fn test_map_to_list() {
+        let hashes = [[1u8; 32], [2; 32]].map(hyperfuel_format::Hash::from);
+        let list = bincode::serialize(&hashes.to_vec()).unwrap();
+        let empty = bincode::serialize(&Vec::<hyperfuel_format::Hash>::new()).unwrap();
+        let col = BinaryArray::<i32>::from([Some(&list[..]), None, Some(&empty[..])]);
+
+        let out = map_to_list(&col, "input_asset_ids", DataType::HashList).unwrap();
+        assert_eq!(out.data_type(), &DataType::HashList.into());
+        let out = out.as_any().downcast_ref::<ListArray<i64>>().unwrap();
+        assert_eq!(out.offsets().as_slice(), &[0, 2, 2, 2]);
+        assert!(out.is_null(1));
+        let values = out
+            .values()
+            .as_any()
+            .downcast_ref::<FixedSizeBinaryArray>()
+            .unwrap();
+        assert_eq!(values.value(1), &[2; 32]);
+
+        let mut witnesses = 3u64.to_be_bytes().to_vec();
+        witnesses.extend_from_slice(&[7, 7, 7, 0, 0, 0, 0, 0]);
+        let col = BinaryArray::<i32>::from([Some(&witnesses[..])]);
+        let out = map_to_list(&col, "witnesses", DataType::BinaryList).unwrap();
+        let out = out.as_any().downcast_ref::<ListArray<i64>>().unwrap();
+        let values = out
+            .values()
+            .as_any()
+            .downcast_ref::<BinaryArray<i32>>()
+            .unwrap();
+        assert_eq!(values.iter().collect::<Vec<_>>(), [Some(&[7, 7, 7][..])]);
+
+        // proof sets are concatenated hashes
+        let packed = [[1u8; 32], [2; 32]].concat();
+        let col = BinaryArray::<i32>::from([Some(&packed[..])]);
+        let out = map_to_list(&col, "proof_set", DataType::HashList).unwrap();
+        assert_eq!(
+            out.as_any()
+                .downcast_ref::<ListArray<i64>>()
+                .unwrap()
+                .value(0)
+                .len(),
+            2
+        );
+        assert!(map_to_list(&col, "input_contracts", DataType::HashList).is_err());
+
+        let col = BinaryArray::<i32>::from([Some(&packed[..31])]);
+        assert!(map_to_list(&col, "proof_set", DataType::HashList).is_err());
+        assert!(map_to_list(&col, "script", DataType::HashList).is_err());
+    }
+
+    #[test]
+    fn test_map_names_and_dictionary() {
+        let col = UInt8Array::from([Some(6), None, Some(0), Some(42)]);
+        let names = map_to_names(&col, "receipt_type").unwrap();
+        let names = names.as_any().downcast_ref::<Utf8Array<i32>>().unwrap();
+        assert_eq!(
+            names.iter().collect::<Vec<_>>(),
+            [Some("LogData"), None, Some("Call"), Some("Unknown(42)")]
+        );
+
+        let col = UInt8Array::from([Some(2), None, Some(0)]);
+        let dict = map_to_dictionary(&col, "output_type").unwrap();
+        assert_eq!(dict.data_type(), &DataType::Dictionary.into());
+        let dict = dict.as_any().downcast_ref::<DictionaryArray<u8>>().unwrap();
+        assert_eq!(dict.keys(), &col);
+        let values = dict
+            .values()
+            .as_any()
+            .downcast_ref::<Utf8ViewArray>()
+            .unwrap();
+        assert_eq!(values.value(2), "ChangeOutput");
+
+        assert!(map_to_dictionary(&col, "amount").is_err());
+    }
+
+    #[test]
+    fn test_integer_overflow() {
+        let col = UInt64Array::from_slice([1, u64::MAX]);
+        let err = map_column(&col, "amount", DataType::UInt32).unwrap_err();
+        assert!(format!("{err:#}").contains("value 18446744073709551615 at row 1 overflows u32"));
+        assert!(map_column(&col, "amount", DataType::Int64).is_err());
+
+        let col = Int64Array::from_slice([-1]);
+        assert!(map_column(&col, "time", DataType::UInt64).is_err());
+
+        let col = BinaryArray::<i32>::from([Some(&[0u8; 33][..])]);
+        assert!(map_column(&col, "amount", DataType::Float64).is_err());
+    }
+
+    #[test]
+    fn test_map_to_decimal() {
+        let col = UInt64Array::from([Some(1_500_000_000), None]);
+        let out = map_column(&col, "amount", DataType::Decimal128(20, 9)).unwrap();
+        assert_eq!(out.data_type(), &ArrowDataType::Decimal(20, 9));
+        let out = out.as_any().downcast_ref::<PrimitiveArray<i128>>().unwrap();
+        assert_eq!(out.iter().collect::<Vec<_>>(), [Some(&1_500_000_000), None]);
+
+        assert!(map_column(&col, "amount", DataType::Decimal128(9, 0)).is_err());
+        assert!(map_column(&col, "amount", DataType::Decimal128(39, 0)).is_err());
+        assert!(map_column(&col, "amount", DataType::Decimal128(10, 11)).is_err());
+
+        let big = [0xff; 32];
+        let col = BinaryArray::<i32>::from([Some(&big[..]), Some(&[1, 0][..])]);
+        assert!(map_column(&col, "amount", DataType::Decimal128(38, 0)).is_err());
+        let out = map_column(&col, "amount", DataType::DecimalString).unwrap();
+        let out = out.as_any().downcast_ref::<Utf8Array<i32>>().unwrap();
+        assert_eq!(out.value(0), U256::MAX.to_string());
+        assert_eq!(out.value(1), "256");
+    }
+
+    #[test]
+    fn test_map_binary() {
+        let hash = [0xab; 32];
+        let col = BinaryArray::<i32>::from([Some(&hash[..]), None]);
+
+        let out = map_column(&col, "tx_id", DataType::FixedSizeBinary(32)).unwrap();
+        let out = out.as_any().downcast_ref::<FixedSizeBinaryArray>().unwrap();
+        assert_eq!(out.value(0), &hash);
+        assert!(out.is_null(1));
+        assert!(map_column(&col, "tx_id", DataType::FixedSizeBinary(20)).is_err());
+
+        let col = BinaryArray::<i32>::from([Some(&[0, 0, 1][..])]);
+        let out = map_column(&col, "data", DataType::Hex).unwrap();
+        let out = out.as_any().downcast_ref::<Utf8Array<i32>>().unwrap();
+        assert_eq!(out.value(0), "0x000001");
+        let out = map_column(&col, "data", DataType::Base58).unwrap();
+        let out = out.as_any().downcast_ref::<Utf8Array<i32>>().unwrap();
+        assert_eq!(out.value(0), "112");
+    }
+
+    #[test]
+    fn test_map_to_timestamp() {
+        let col = Int64Array::from([Some((TAI64_UNIX_EPOCH + 1_700_000_000) as i64), None]);
+        let out = map_column(&col, "time", DataType::Timestamp).unwrap();
+        assert_eq!(
+            out.data_type(),
+            &ArrowDataType::Timestamp(TimeUnit::Nanosecond, Some("UTC".to_owned()))
+        );
+        let out = out.as_any().downcast_ref::<Int64Array>().unwrap();
+        assert_eq!(
+            out.iter().collect::<Vec<_>>(),
+            [Some(&1_700_000_000_000_000_000), None]
+        );
+
+        // unix seconds aren't TAI64 labels
+        let col = Int64Array::from_slice([1_700_000_000]);
+        assert!(map_column(&col, "time", DataType::Timestamp).is_err());
+    }
+
+    #[test]
+    fn test_data_type_serde() {
+        let mapping: BTreeMap<String, DataType> = serde_json::from_str(
+            r#"{"amount": {"decimal128": [38, 9]}, "tx_id": {"fixedsizebinary": 32}, "receipt_type": "dictionary"}"#,
+        )
+        .unwrap();
+        assert_eq!(mapping["amount"], DataType::Decimal128(38, 9));
+        assert_eq!(mapping["tx_id"], DataType::FixedSizeBinary(32));
+        assert_eq!(mapping["receipt_type"], DataType::Dictionary);
+    }
 }
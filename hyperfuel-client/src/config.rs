@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use std::num::NonZeroU64;
+use std::{collections::BTreeSet, num::NonZeroU64};
 use url::Url;
 
 use crate::ColumnMapping;
@@ -21,6 +21,24 @@ pub struct ClientConfig {
     pub retry_base_ms: Option<u64>,
     /// Ceiling time for request backoff.
     pub retry_ceiling_ms: Option<u64>,
+    /// Experimental: send queries capnp encoded to `/query/arrow-ipc/capnp` instead of as JSON,
+    /// see [`Query::to_capnp_bytes`]. The server doesn't document this endpoint yet, so it is off
+    /// by default. Falls back to JSON if the server doesn't support binary queries.
+    ///
+    /// [`Query::to_capnp_bytes`]: crate::net_types::Query::to_capnp_bytes
+    pub binary_query: Option<bool>,
+    /// Maximum number of addresses, contract ids and asset ids in the selections of a single
+    /// request. Queries with more are split into several requests and the responses are merged.
+    /// Merged transactions, inputs and outputs are ordered by block but not within a block.
+    /// Defaults to 5000.
+    pub max_query_hashes: Option<usize>,
+    /// Allow type and status codes in queries that this version of the library doesn't know,
+    /// for codes that the server added since. Queries are checked with
+    /// [`Query::validate_lenient`] instead of [`Query::validate`]. Defaults to false.
+    ///
+    /// [`Query::validate_lenient`]: crate::net_types::Query::validate_lenient
+    /// [`Query::validate`]: crate::net_types::Query::validate
+    pub allow_unknown_codes: Option<bool>,
 }
 
 /// Config for HyperFuel streaming.
@@ -29,8 +47,27 @@ pub struct StreamConfig {
     /// Column mapping for stream function output.
     /// It lets you map columns you want into the DataTypes you want.
     pub column_mapping: Option<ColumnMapping>,
-    /// Event signature used to populate decode logs. Decode logs would be empty if set to None.
+    /// Not read. Fuel logs are matched by log id rather than by an event signature, set
+    /// `log_abi` to decode them.
+    #[deprecated(note = "not read, set `log_abi` to decode logs")]
     pub event_signature: Option<String>,
+    /// Sway JSON ABI used to decode LogData receipts. If set, `decoded_log_type` and
+    /// `decoded_log` columns are appended to receipts, see [`LogDecoder::decode_batch`]. The
+    /// `receipt_type`, `rb` and `data` receipt fields have to be selected.
+    ///
+    /// [`LogDecoder::decode_batch`]: crate::LogDecoder::decode_batch
+    pub log_abi: Option<String>,
+    /// Split LogData receipts of the types in `log_abi` into a decoded logs table per type, in
+    /// [`ArrowResponseData::decoded_logs`]. See [`LogDecoder::decode_to_tables`].
+    ///
+    /// [`ArrowResponseData::decoded_logs`]: crate::ArrowResponseData::decoded_logs
+    /// [`LogDecoder::decode_to_tables`]: crate::LogDecoder::decode_to_tables
+    pub decode_log_tables: Option<bool>,
+    /// Append a `signer` column to transactions, see [`add_signer_column`]. The `id` and
+    /// `witnesses` transaction fields have to be selected.
+    ///
+    /// [`add_signer_column`]: crate::add_signer_column
+    pub derive_signer: Option<bool>,
     /// Determines formatting of binary columns numbers into utf8 hex.
     #[serde(default)]
     pub hex_output: HexOutput,
@@ -60,6 +97,23 @@ pub struct StreamConfig {
     pub reverse: Option<bool>,
 }
 
+/// Config for the event API, selects the context joined to each event.
+///
+/// Fields listed here are added to the field selection of the query, on top of the fields it
+/// already selects. Fields needed for joining and for constructing the typed rows are always
+/// added when a table is joined.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct EventConfig {
+    /// Block header fields to fetch for each event. Only the fields needed for the join are
+    /// fetched if None, blocks aren't joined if the set and the query's block field selection
+    /// are empty.
+    pub block_fields: Option<BTreeSet<String>>,
+    /// Transaction fields to fetch for each event. Only the fields needed for the join are
+    /// fetched if None, transactions aren't joined if the set and the query's transaction field
+    /// selection are empty.
+    pub transaction_fields: Option<BTreeSet<String>>,
+}
+
 /// Determines format of Binary column
 #[derive(Default, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum HexOutput {
@@ -70,4 +124,6 @@ pub enum HexOutput {
     Prefixed,
     /// Binary column would be formatted as non prefixed hex i.e. deadbeef
     NonPrefixed,
+    /// Like `Prefixed`, but address columns are formatted as checksummed hex i.e. 0x9cfB2CAd…
+    Checksummed,
 }
@@ -0,0 +1,174 @@
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Context, Result};
+use hyperfuel_format::{disassemble, Opcode};
+use polars_arrow::array::{BinaryArray, BinaryViewArray, MutablePrimitiveArray, UInt32Array};
+use polars_arrow::datatypes::{ArrowDataType as DataType, ArrowSchema as Schema, Field};
+
+use crate::{ArrowBatch, ArrowChunk};
+
+/// Adds opcode histogram columns for the bytecode in `column`, e.g. the `script` column of
+/// transactions or the `predicate` column of inputs.
+///
+/// For each opcode in `opcodes` a `{column}_{mnemonic}_count` column is added with the number
+/// of times it occurs in the bytecode of the row, along with a `{column}_instruction_count`
+/// column with the total number of instructions. Pass [`Opcode::ALL`] to count every known
+/// opcode. Counts are null where the bytecode is null.
+pub fn opcode_histogram(
+    batch: &ArrowBatch,
+    column: &str,
+    opcodes: &[Opcode],
+) -> Result<ArrowBatch> {
+    let (idx, _) = batch
+        .schema
+        .fields
+        .iter()
+        .enumerate()
+        .find(|(_, f)| f.name == column)
+        .with_context(|| format!("column '{column}' not found"))?;
+    let col = batch.chunk.columns()[idx].as_any();
+    let (counts, totals) = if let Some(col) = col.downcast_ref::<BinaryArray<i32>>() {
+        count_opcodes(col.iter(), col.len(), opcodes)
+    } else if let Some(col) = col.downcast_ref::<BinaryViewArray>() {
+        count_opcodes(col.iter(), col.len(), opcodes)
+    } else {
+        return Err(anyhow!("column '{column}' is not a binary column"));
+    };
+
+    let mut fields = batch.schema.fields.clone();
+    let mut cols = batch.chunk.columns().to_vec();
+
+    for (opcode, count) in opcodes.iter().zip(counts) {
+        let name = format!("{column}_{}_count", opcode.mnemonic().to_lowercase());
+        fields.push(Field::new(name, DataType::UInt32, true));
+        cols.push(UInt32Array::from(count).boxed());
+    }
+    fields.push(Field::new(
+        format!("{column}_instruction_count"),
+        DataType::UInt32,
+        true,
+    ));
+    cols.push(UInt32Array::from(totals).boxed());
+
+    Ok(ArrowBatch {
+        chunk: ArrowChunk::new(cols).into(),
+        schema: Schema::from(fields).into(),
+    })
+}
+
+/// Returns the count of each opcode and the total number of instructions for each row.
+fn count_opcodes<'a>(
+    col: impl Iterator<Item = Option<&'a [u8]>>,
+    len: usize,
+    opcodes: &[Opcode],
+) -> (Vec<MutablePrimitiveArray<u32>>, MutablePrimitiveArray<u32>) {
+    let positions = opcodes
+        .iter()
+        .enumerate()
+        .map(|(i, opcode)| (*opcode, i))
+        .collect::<HashMap<_, _>>();
+
+    let mut counts = vec![MutablePrimitiveArray::<u32>::with_capacity(len); opcodes.len()];
+    let mut totals = MutablePrimitiveArray::<u32>::with_capacity(len);
+
+    for bytecode in col {
+        let Some(bytecode) = bytecode else {
+            counts.iter_mut().for_each(|c| c.push(None));
+            totals.push(None);
+            continue;
+        };
+
+        let instructions = disassemble(bytecode);
+        let mut row = vec![0u32; opcodes.len()];
+        for instruction in instructions.iter() {
+            if let Some(&pos) = positions.get(&instruction.opcode) {
+                row[pos] += 1;
+            }
+        }
+
+        for (count, val) in counts.iter_mut().zip(row) {
+            count.push(Some(val));
+        }
+        totals.push(Some(instructions.len() as u32));
+    }
+
+    (counts, totals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_opcode_histogram() {
+        // move $r16 $pc, ret $one, ret $one
+        let script = hex_literal::hex!("1a403000 24040000 24040000");
+        let batch = ArrowBatch {
+            chunk: ArrowChunk::new(vec![
+                BinaryArray::<i32>::from([Some(&script[..]), None]).boxed()
+            ])
+            .into(),
+            schema: Schema::from(vec![Field::new("script", DataType::Binary, true)]).into(),
+        };
+
+        let batch = opcode_histogram(&batch, "script", &[Opcode::Ret, Opcode::Ecal]).unwrap();
+
+        let names = batch
+            .schema
+            .fields
+            .iter()
+            .map(|f| f.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [
+                "script",
+                "script_ret_count",
+                "script_ecal_count",
+                "script_instruction_count"
+            ]
+        );
+
+        let col = |i: usize| {
+            batch.chunk.columns()[i]
+                .as_any()
+                .downcast_ref::<UInt32Array>()
+                .unwrap()
+                .iter()
+                .map(|v| v.copied())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(col(1), [Some(2), None]);
+        assert_eq!(col(2), [Some(0), None]);
+        assert_eq!(col(3), [Some(3), None]);
+
+        assert!(opcode_histogram(&batch, "missing", Opcode::ALL).is_err());
+        assert!(opcode_histogram(&batch, "script_ret_count", Opcode::ALL).is_err());
+    }
+
+    #[test]
+    fn test_opcode_histogram_binary_view() {
+        let script = hex_literal::hex!("1a403000 24040000 24040000");
+        let batch = ArrowBatch {
+            chunk: ArrowChunk::new(vec![
+                BinaryViewArray::from_slice([Some(&script[..]), None]).boxed()
+            ])
+            .into(),
+            schema: Schema::from(vec![Field::new("script", DataType::BinaryView, true)]).into(),
+        };
+
+        let batch = opcode_histogram(&batch, "script", &[Opcode::Ret]).unwrap();
+
+        let col = |i: usize| {
+            batch.chunk.columns()[i]
+                .as_any()
+                .downcast_ref::<UInt32Array>()
+                .unwrap()
+                .iter()
+                .map(|v| v.copied())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(col(1), [Some(2), None]);
+        assert_eq!(col(2), [Some(3), None]);
+    }
+}
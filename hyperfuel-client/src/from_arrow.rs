@@ -1,11 +1,20 @@
-use polars_arrow::array::{BinaryArray, StaticArray, UInt64Array, UInt8Array, Utf8Array};
+use anyhow::{anyhow, Result};
+use polars_arrow::array::{
+    Array, BinaryArray, Int64Array, StaticArray, UInt64Array, UInt8Array, Utf8Array,
+};
+use polars_arrow::datatypes::ArrowDataType as DataType;
 
-use crate::ArrowBatch;
+use crate::{
+    types::ResponseData,
+    util::{find_column, unpack_hash_list, BinaryColumn, TimeColumn, Utf8Column},
+    ArrowBatch, ArrowResponseData,
+};
 
 use hyperfuel_format::{
-    BlockHeader, Input, InputType, Output, OutputType, Receipt, ReceiptType, Transaction,
-    TransactionStatus, TransactionType,
+    BlockHeader, Data, FixedSizeData, Hash, Hex, Input, InputType, Output, OutputType, Quantity,
+    Receipt, ReceiptType, Transaction, TransactionStatus, TransactionType, UInt,
 };
+use hyperfuel_net_types::FieldSelection;
 
 /// Used to do ArrowBatch-Native Rust type conversions while consuming the input value.
 pub trait FromArrow: Sized {
@@ -13,6 +22,214 @@ pub trait FromArrow: Sized {
     fn from_arrow(batch: &ArrowBatch) -> Vec<Self>;
 }
 
+/// Associates a row type with the table of a response it is read from.
+///
+/// Implemented for the `hyperfuel_format` types and by `#[derive(FromArrow)]`.
+pub trait TableRow {
+    /// Returns the batches of the table this type is read from.
+    fn batches(data: &ArrowResponseData) -> &[ArrowBatch];
+}
+
+impl TableRow for BlockHeader {
+    fn batches(data: &ArrowResponseData) -> &[ArrowBatch] {
+        &data.blocks
+    }
+}
+
+impl TableRow for Transaction {
+    fn batches(data: &ArrowResponseData) -> &[ArrowBatch] {
+        &data.transactions
+    }
+}
+
+impl TableRow for Receipt {
+    fn batches(data: &ArrowResponseData) -> &[ArrowBatch] {
+        &data.receipts
+    }
+}
+
+impl TableRow for Input {
+    fn batches(data: &ArrowResponseData) -> &[ArrowBatch] {
+        &data.inputs
+    }
+}
+
+impl TableRow for Output {
+    fn batches(data: &ArrowResponseData) -> &[ArrowBatch] {
+        &data.outputs
+    }
+}
+
+/// Conversion of the data of a whole Arrow response into native Rust types.
+///
+/// Implemented for [`ResponseData`], which converts every table, and for `Vec<T>`, which
+/// converts only the table `T` is read from.
+pub trait FromArrowResponse: Default + Send + Sync + 'static {
+    /// Converts the response data.
+    fn from_arrow_response(data: &ArrowResponseData) -> Self;
+
+    /// Appends the data of a later response, used when collecting a stream.
+    fn append(&mut self, other: Self);
+}
+
+impl FromArrowResponse for ResponseData {
+    fn from_arrow_response(data: &ArrowResponseData) -> Self {
+        Self {
+            blocks: data.blocks.iter().map(BlockHeader::from_arrow).collect(),
+            transactions: data
+                .transactions
+                .iter()
+                .map(Transaction::from_arrow)
+                .collect(),
+            receipts: data.receipts.iter().map(Receipt::from_arrow).collect(),
+            inputs: data.inputs.iter().map(Input::from_arrow).collect(),
+            outputs: data.outputs.iter().map(Output::from_arrow).collect(),
+        }
+    }
+
+    fn append(&mut self, other: Self) {
+        self.blocks.extend(other.blocks);
+        self.transactions.extend(other.transactions);
+        self.receipts.extend(other.receipts);
+        self.inputs.extend(other.inputs);
+        self.outputs.extend(other.outputs);
+    }
+}
+
+impl<T> FromArrowResponse for Vec<T>
+where
+    T: FromArrow + TableRow + Send + Sync + 'static,
+{
+    fn from_arrow_response(data: &ArrowResponseData) -> Self {
+        T::batches(data).iter().flat_map(T::from_arrow).collect()
+    }
+
+    fn append(&mut self, other: Self) {
+        self.extend(other);
+    }
+}
+
+/// Reports the columns a type needs, so a query can be built that fetches exactly those.
+///
+/// Implemented by `#[derive(FromArrow)]`.
+pub trait SelectFields {
+    /// Field selection containing every column read by the type.
+    fn field_selection() -> FieldSelection;
+}
+
+/// A value that can be read from the rows of an Arrow column.
+///
+/// Used by `#[derive(FromArrow)]` to convert each column into the type of the struct field.
+pub trait FromArrowColumn: Sized {
+    /// Reads every row of the column, `None` for nulls and for values that don't fit the type,
+    /// like a hash that isn't 32 bytes long. Errors if the column has a type this can't be read
+    /// from.
+    fn read_column(col: &dyn Array, name: &str) -> Result<Vec<Option<Self>>>;
+
+    /// Reads the column `name` of the batch, returns `None` if the batch doesn't have it.
+    ///
+    /// # Panics
+    ///
+    /// If the column has a type this can't be read from.
+    fn from_batch(batch: &ArrowBatch, name: &str) -> Option<Vec<Option<Self>>> {
+        let col = find_column(batch, name)?;
+        Some(Self::read_column(col, name).unwrap_or_else(|e| panic!("{:#}", e)))
+    }
+}
+
+/// Reads a binary column, which is `Binary` or `BinaryView`, or prefixed hex `Utf8` or
+/// `Utf8View` if the response was hex encoded.
+fn read_binary<T>(
+    col: &dyn Array,
+    name: &str,
+    convert: impl Fn(&[u8]) -> Option<T>,
+) -> Result<Vec<Option<T>>> {
+    match col.data_type() {
+        DataType::Utf8 | DataType::Utf8View => {
+            let col = Utf8Column::new(col, name)?;
+            Ok(col
+                .iter()
+                .map(|v| v.and_then(|v| convert(&Data::decode_hex(v).ok()?)))
+                .collect())
+        }
+        _ => {
+            let col = BinaryColumn::new(col, name)?;
+            Ok(col.iter().map(|v| v.and_then(&convert)).collect())
+        }
+    }
+}
+
+fn read_primitive<A: 'static, T>(
+    col: &dyn Array,
+    name: &str,
+    read: impl Fn(&A) -> Vec<Option<T>>,
+) -> Result<Vec<Option<T>>> {
+    let arr = col.as_any().downcast_ref::<A>().ok_or_else(|| {
+        anyhow!(
+            "unexpected type of column '{}', it was {:?}",
+            name,
+            col.data_type()
+        )
+    })?;
+    Ok(read(arr))
+}
+
+impl<const N: usize> FromArrowColumn for FixedSizeData<N> {
+    fn read_column(col: &dyn Array, name: &str) -> Result<Vec<Option<Self>>> {
+        read_binary(col, name, |v| v.try_into().ok())
+    }
+}
+
+impl FromArrowColumn for Data {
+    fn read_column(col: &dyn Array, name: &str) -> Result<Vec<Option<Self>>> {
+        read_binary(col, name, |v| Some(Data::from(v)))
+    }
+}
+
+impl FromArrowColumn for Vec<u8> {
+    fn read_column(col: &dyn Array, name: &str) -> Result<Vec<Option<Self>>> {
+        read_binary(col, name, |v| Some(v.to_vec()))
+    }
+}
+
+impl FromArrowColumn for String {
+    fn read_column(col: &dyn Array, name: &str) -> Result<Vec<Option<Self>>> {
+        let col = Utf8Column::new(col, name)?;
+        Ok(col.iter().map(|v| v.map(str::to_owned)).collect())
+    }
+}
+
+macro_rules! impl_from_arrow_column {
+    ($array:ty, $($ty:ty => $convert:expr),*) => {
+        $(
+            impl FromArrowColumn for $ty {
+                fn read_column(col: &dyn Array, name: &str) -> Result<Vec<Option<Self>>> {
+                    read_primitive(col, name, |arr: &$array| {
+                        arr.iter().map(|v| v.copied().map($convert)).collect()
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_from_arrow_column!(
+    UInt64Array,
+    UInt => UInt::from,
+    u64 => std::convert::identity,
+    Quantity => Quantity::from
+);
+impl_from_arrow_column!(Int64Array, i64 => std::convert::identity);
+impl_from_arrow_column!(
+    UInt8Array,
+    u8 => std::convert::identity,
+    TransactionType => TransactionType::from,
+    TransactionStatus => TransactionStatus::from_u8,
+    ReceiptType => ReceiptType::from_u8,
+    InputType => InputType::from_u8,
+    OutputType => OutputType::from_u8
+);
+
 fn map_binary<'a, T>(i: usize, arr: Option<&'a BinaryArray<i32>>) -> Option<T>
 where
     T: TryFrom<&'a [u8]>,
@@ -21,6 +238,11 @@ where
     arr.and_then(|arr| arr.get(i).map(|v| v.try_into().unwrap()))
 }
 
+/// Decodes a value of a hash list column, panics with the error if it is malformed.
+fn hash_list(buf: &[u8], name: &str) -> Vec<Hash> {
+    unpack_hash_list(buf).unwrap_or_else(|e| panic!("Construct from_arrow {}: {:#}", name, e))
+}
+
 // Some unwraps etc that should be improved
 
 impl FromArrow for BlockHeader {
@@ -40,7 +262,7 @@ impl FromArrow for BlockHeader {
         let event_inbox_root = batch.column::<BinaryArray<i32>>("event_inbox_root").ok();
         let height = batch.column::<UInt64Array>("height").ok();
         let prev_root = batch.column::<BinaryArray<i32>>("prev_root").ok();
-        let time = batch.column::<UInt64Array>("time").ok();
+        let time = find_column(batch, "time").and_then(|col| TimeColumn::new(col, "time").ok());
         let application_hash = batch.column::<BinaryArray<i32>>("application_hash").ok();
 
         (0..batch.chunk.len())
@@ -72,7 +294,7 @@ impl FromArrow for BlockHeader {
                     .expect("Construct from_arrow height"),
                 prev_root: map_binary(idx, prev_root).expect("Construct from_arrow prev_root"),
                 time: time
-                    .and_then(|arr| arr.get(idx).map(|v| v.into()))
+                    .and_then(|col| col.get(idx).map(|v| v.into()))
                     .expect("Construct from_arrow time"),
                 application_hash: map_binary(idx, application_hash)
                     .expect("Construct from_arrow application_hash"),
@@ -127,7 +349,7 @@ impl FromArrow for Transaction {
         let witnesses = batch.column::<BinaryArray<i32>>("witnesses").ok();
         let receipts_root = batch.column::<BinaryArray<i32>>("receipts_root").ok();
         let status = batch.column::<UInt8Array>("status").ok();
-        let time = batch.column::<UInt64Array>("time").ok();
+        let time = find_column(batch, "time").and_then(|col| TimeColumn::new(col, "time").ok());
         let reason = batch.column::<Utf8Array<i32>>("reason").ok();
         let script = batch.column::<BinaryArray<i32>>("script").ok();
         let script_data = batch.column::<BinaryArray<i32>>("script_data").ok();
@@ -154,9 +376,9 @@ impl FromArrow for Transaction {
                     .expect("Construct from_arrow block_height"),
                 id: map_binary(idx, id).unwrap(),
                 input_asset_ids: input_asset_ids
-                    .and_then(|arr| arr.get(idx).map(|v| bincode::deserialize(v).unwrap())),
+                    .and_then(|arr| arr.get(idx).map(|v| hash_list(v, "input_asset_ids"))),
                 input_contracts: input_contracts
-                    .and_then(|arr| arr.get(idx).map(|v| bincode::deserialize(v).unwrap())),
+                    .and_then(|arr| arr.get(idx).map(|v| hash_list(v, "input_contracts"))),
                 input_contract_utxo_id: map_binary(idx, input_contract_utxo_id),
                 input_contract_balance_root: map_binary(idx, input_contract_balance_root),
                 input_contract_state_root: map_binary(idx, input_contract_state_root),
@@ -189,10 +411,10 @@ impl FromArrow for Transaction {
                 witnesses: map_binary(idx, witnesses),
                 receipts_root: map_binary(idx, receipts_root),
                 status: status
-                    .and_then(|arr| arr.get(idx).map(|v| TransactionStatus::from_u8(v).unwrap()))
+                    .and_then(|arr| arr.get(idx).map(TransactionStatus::from_u8))
                     .expect("Construct from_arrow status"),
                 time: time
-                    .and_then(|arr| arr.get(idx).map(|v| v.into()))
+                    .and_then(|col| col.get(idx).map(|v| v.into()))
                     .expect("Construct from_arrow time"),
                 reason: reason.and_then(|arr| arr.get(idx).map(|v| v.to_owned())),
                 script: map_binary(idx, script),
@@ -265,10 +487,10 @@ impl FromArrow for Receipt {
                 root_contract_id: map_binary(idx, root_contract_id),
                 tx_id: map_binary(idx, tx_id).expect("Construct from_arrow tx_id"),
                 tx_status: tx_status
-                    .and_then(|arr| arr.get(idx).map(|v| TransactionStatus::from_u8(v).unwrap()))
+                    .and_then(|arr| arr.get(idx).map(TransactionStatus::from_u8))
                     .expect("Construct from_arrow tx_status"),
                 tx_type: tx_type
-                    .and_then(|arr| arr.get(idx).map(TransactionType))
+                    .and_then(|arr| arr.get(idx).map(TransactionType::from))
                     .expect("Construct from_arrow tx_type"),
                 block_height: block_height
                     .and_then(|arr| arr.get(idx).map(|v| v.into()))
@@ -292,7 +514,7 @@ impl FromArrow for Receipt {
                 rd: rd.and_then(|arr| arr.get(idx).map(|v| v.into())),
                 len: len.and_then(|arr| arr.get(idx).map(|v| v.into())),
                 receipt_type: receipt_type
-                    .and_then(|arr| arr.get(idx).map(|v| ReceiptType::from_u8(v).unwrap()))
+                    .and_then(|arr| arr.get(idx).map(ReceiptType::from_u8))
                     .expect("Construct from_arrow receipt_type"),
                 result: result.and_then(|arr| arr.get(idx).map(|v| v.into())),
                 gas_used: gas_used.and_then(|arr| arr.get(idx).map(|v| v.into())),
@@ -336,16 +558,16 @@ impl FromArrow for Input {
             .map(|idx| Self {
                 tx_id: map_binary(idx, tx_id).unwrap(),
                 tx_status: tx_status
-                    .and_then(|arr| arr.get(idx).map(|v| TransactionStatus::from_u8(v).unwrap()))
+                    .and_then(|arr| arr.get(idx).map(TransactionStatus::from_u8))
                     .expect("Construct from_arrow tx_status"),
                 tx_type: tx_type
-                    .and_then(|arr| arr.get(idx).map(TransactionType))
+                    .and_then(|arr| arr.get(idx).map(TransactionType::from))
                     .expect("Construct from_arrow tx_type"),
                 block_height: block_height
                     .and_then(|arr| arr.get(idx).map(|v| v.into()))
                     .expect("Construct from_arrow block_height"),
                 input_type: input_type
-                    .and_then(|arr| arr.get(idx).map(|v| InputType::from_u8(v).unwrap()))
+                    .and_then(|arr| arr.get(idx).map(InputType::from_u8))
                     .expect("Construct from_arrow input_type"),
                 utxo_id: map_binary(idx, utxo_id),
                 owner: map_binary(idx, owner),
@@ -391,16 +613,16 @@ impl FromArrow for Output {
             .map(|idx| Self {
                 tx_id: map_binary(idx, tx_id).unwrap(),
                 tx_status: tx_status
-                    .and_then(|arr| arr.get(idx).map(|v| TransactionStatus::from_u8(v).unwrap()))
+                    .and_then(|arr| arr.get(idx).map(TransactionStatus::from_u8))
                     .expect("Construct from_arrow tx_status"),
                 tx_type: tx_type
-                    .and_then(|arr| arr.get(idx).map(TransactionType))
+                    .and_then(|arr| arr.get(idx).map(TransactionType::from))
                     .expect("Construct from_arrow tx_type"),
                 block_height: block_height
                     .and_then(|arr| arr.get(idx).map(|v| v.into()))
                     .expect("Construct from_arrow block_height"),
                 output_type: output_type
-                    .and_then(|arr| arr.get(idx).map(|v| OutputType::from_u8(v).unwrap()))
+                    .and_then(|arr| arr.get(idx).map(OutputType::from_u8))
                     .expect("Construct from_arrow output_type"),
                 to: map_binary(idx, to),
                 amount: amount.and_then(|arr| arr.get(idx).map(|v| v.into())),
@@ -1,39 +1,78 @@
 #![deny(missing_docs)]
 //! HyperFuel client library for querying a HyperFuel (HyperSync) server.
 
-use std::{collections::BTreeSet, num::NonZeroU64, sync::Arc, time::Duration};
+use std::{
+    collections::{BTreeSet, HashMap},
+    num::NonZeroU64,
+    ops::{Bound, RangeBounds},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
 
 use anyhow::{anyhow, Context, Result};
-use hyperfuel_format::Hash;
+use futures::{StreamExt, TryStreamExt};
+use hyperfuel_format::{Hash, ReceiptType, Timestamp, TransactionStatus};
 use hyperfuel_net_types::{ArchiveHeight, ChainId, FieldSelection, Query, ReceiptSelection};
 use polars_arrow::{array::Array, record_batch::RecordBatchT as Chunk};
-use reqwest::Method;
+use reqwest::{header, Method, StatusCode};
 
+pub mod abi;
+mod block_time;
 mod column_mapping;
 mod config;
+mod disasm;
 mod from_arrow;
 mod parquet_out;
 mod parse_response;
 mod rayon_async;
+mod shard;
+mod signer;
 mod stream;
+mod to_arrow;
 mod types;
 mod util;
+mod view;
 
-pub use from_arrow::FromArrow;
+pub use from_arrow::{FromArrow, FromArrowColumn, FromArrowResponse, SelectFields, TableRow};
+pub use hyperfuel_derive::{hyperfuel_abigen, FromArrow};
 pub use hyperfuel_format as format;
 pub use hyperfuel_net_types as net_types;
 pub use hyperfuel_schema as schema;
+pub use to_arrow::ToArrow;
 
 use parse_response::parse_query_response;
 use tokio::sync::mpsc;
 use url::Url;
 
+/// Maximum number of shard requests of a query that are in flight at once.
+const SHARD_CONCURRENCY: usize = 4;
+
+pub use abi::{
+    decode_return_data, fn_selector, legacy_fn_selector, AbiDecode, AbiFunction, CallDecoder,
+    DecodedCall, DecodedFailure, DecodedValue, FailureCause, LogDecoder, LoggedType, ParamType,
+    SizedStr,
+};
 pub use column_mapping::{ColumnMapping, DataType};
 pub use config::HexOutput;
-pub use config::{ClientConfig, StreamConfig};
+pub use config::{ClientConfig, EventConfig, StreamConfig};
+pub use disasm::opcode_histogram;
+pub use signer::add_signer_column;
 pub use types::{
-    ArrowBatch, ArrowResponse, ArrowResponseData, LogContext, LogResponse, QueryResponse,
+    ArrowBatch, ArrowResponse, ArrowResponseData, CallContext, CallResponse, Event, EventItem,
+    EventResponse, LogContext, LogResponse, QueryResponse, ResponseData,
 };
+pub use view::{
+    BlockHeaderView, BlockHeaderViews, InputView, InputViews, OutputView, OutputViews, ReceiptView,
+    ReceiptViews, TransactionView, TransactionViews,
+};
+
+#[doc(hidden)]
+pub mod __private {
+    pub use anyhow;
+}
 
 /// ArrowChunk
 pub type ArrowChunk = Chunk<Box<dyn Array>>;
@@ -55,6 +94,14 @@ pub struct Client {
     retry_base_ms: u64,
     /// Ceiling time for request backoff.
     retry_ceiling_ms: u64,
+    /// Block times looked up by `block_at_or_after`/`block_at_or_before`, shared by clones.
+    block_times: Arc<Mutex<block_time::BlockTimeIndex>>,
+    /// Whether to send queries capnp encoded, cleared if the server doesn't support it.
+    binary_query: Arc<AtomicBool>,
+    /// Queries with more hashes in their selections are split into several requests.
+    max_query_hashes: usize,
+    /// Whether queries may have type and status codes that the library doesn't know.
+    allow_unknown_codes: bool,
 }
 
 impl Client {
@@ -83,6 +130,10 @@ impl Client {
             retry_backoff_ms: cfg.retry_backoff_ms.unwrap_or(500),
             retry_base_ms: cfg.retry_base_ms.unwrap_or(200),
             retry_ceiling_ms: cfg.retry_ceiling_ms.unwrap_or(5_000),
+            block_times: Default::default(),
+            binary_query: Arc::new(AtomicBool::new(cfg.binary_query.unwrap_or(false))),
+            max_query_hashes: cfg.max_query_hashes.unwrap_or(5_000),
+            allow_unknown_codes: cfg.allow_unknown_codes.unwrap_or(false),
         })
     }
 
@@ -126,7 +177,7 @@ impl Client {
             receipts: vec![ReceiptSelection {
                 root_contract_id: emitting_contracts.clone(),
                 receipt_type: vec![5, 6],
-                tx_status: vec![1],
+                tx_status: vec![TransactionStatus::Success.to_u8()],
                 ..Default::default()
             }],
             field_selection: FieldSelection {
@@ -153,6 +204,93 @@ impl Client {
         })
     }
 
+    /// Returns calls to `function` on `contract` in the block range, with their return receipts.
+    ///
+    /// The server can't filter on the function, so all `Call` receipts to the contract are
+    /// fetched together with the script data of their transactions, and the calls of other
+    /// functions are dropped on the client. Only calls made by the SDK's call script of programs
+    /// built with encoding version 1 are found, see [`CallDecoder::decode_call`]. Calls from
+    /// failed transactions are not included.
+    ///
+    /// **Note:** experimental API; may change in future releases.
+    pub async fn preset_query_get_calls<H: Into<Hash>>(
+        &self,
+        contract: H,
+        function: &str,
+        from_block: u64,
+        to_block: Option<u64>,
+    ) -> Result<CallResponse> {
+        let receipt_field_selection = [
+            "block_height",
+            "tx_id",
+            "tx_status",
+            "tx_type",
+            "receipt_index",
+            "receipt_type",
+            "contract_id",
+            "root_contract_id",
+            "to",
+            "amount",
+            "asset_id",
+            "gas",
+            "param1",
+            "param2",
+            "pc",
+            "is",
+            "val",
+            "ptr",
+            "len",
+            "digest",
+            "data",
+        ];
+        let transaction_field_selection = [
+            "block_height",
+            "id",
+            "tx_type",
+            "status",
+            "time",
+            "script_data",
+        ];
+
+        let contract = contract.into();
+        let query = Query {
+            from_block,
+            to_block,
+            receipts: vec![
+                ReceiptSelection {
+                    to: vec![contract.clone()],
+                    receipt_type: vec![ReceiptType::Call.to_u8()],
+                    tx_status: vec![TransactionStatus::Success.to_u8()],
+                    ..Default::default()
+                },
+                ReceiptSelection {
+                    contract_id: vec![contract],
+                    receipt_type: vec![
+                        ReceiptType::Return.to_u8(),
+                        ReceiptType::ReturnData.to_u8(),
+                    ],
+                    tx_status: vec![TransactionStatus::Success.to_u8()],
+                    ..Default::default()
+                },
+            ],
+            field_selection: FieldSelection {
+                receipt: receipt_field_selection.map(str::to_owned).into(),
+                transaction: transaction_field_selection.map(str::to_owned).into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let res = self.get(&query).await.context("get data")?;
+
+        Ok(CallResponse {
+            archive_height: res.archive_height,
+            next_block: res.next_block,
+            total_execution_time: res.total_execution_time,
+            data: select_calls(res.data, function),
+        })
+    }
+
     /// Retrieves blocks, transactions, traces, and logs in Arrow format through a stream using
     /// the provided query and stream configuration.
     pub async fn collect_arrow(
@@ -187,6 +325,9 @@ impl Client {
             for batch in res.data.outputs {
                 data.outputs.push(batch);
             }
+            for (table, batches) in res.data.decoded_logs {
+                data.decoded_logs.entry(table).or_default().extend(batches);
+            }
 
             archive_height = res.archive_height;
             next_block = res.next_block;
@@ -201,8 +342,57 @@ impl Client {
         })
     }
 
+    /// Retrieves blocks, transactions, receipts, inputs and outputs through a stream using the
+    /// provided query and stream configuration, converted to native Rust types.
+    ///
+    /// Returns an error if `column_mapping` or `hex_output` is set in the config since the
+    /// converted columns couldn't be decoded.
+    pub async fn collect(
+        self: Arc<Self>,
+        query: Query,
+        config: StreamConfig,
+    ) -> Result<QueryResponse> {
+        self.collect_as(query, config).await
+    }
+
+    /// Same as [`collect`](Self::collect) but converts the data to the given type, for example
+    /// `Vec<Receipt>` or a `Vec` of a type that derives `FromArrow`.
+    pub async fn collect_as<T: FromArrowResponse>(
+        self: Arc<Self>,
+        query: Query,
+        config: StreamConfig,
+    ) -> Result<QueryResponse<T>> {
+        let mut recv = stream::stream::<T>(self, query, config)
+            .await
+            .context("start stream")?;
+
+        let mut data = T::default();
+        let mut archive_height = None;
+        let mut next_block = 0;
+        let mut total_execution_time = 0;
+
+        while let Some(res) = recv.recv().await {
+            let res = res.context("get response")?;
+
+            data.append(res.data);
+
+            archive_height = res.archive_height;
+            next_block = res.next_block;
+            total_execution_time += res.total_execution_time
+        }
+
+        Ok(QueryResponse {
+            archive_height,
+            next_block,
+            total_execution_time,
+            data,
+        })
+    }
+
     /// Writes parquet file getting data through a stream using the provided path, query,
     /// and stream configuration.
+    ///
+    /// Decoded logs tables are written to `decoded_logs/<table>.parquet` under the path.
     pub async fn collect_parquet(
         self: Arc<Self>,
         path: &str,
@@ -340,12 +530,34 @@ impl Client {
         Ok(QueryResponse::from(&arrow_response))
     }
 
-    /// Executes query once and returns the result in (Arrow, size) format.
-    async fn get_arrow_impl(&self, query: &Query) -> Result<(ArrowResponse, u64)> {
+    /// Executes query with retries and returns the receipts, inputs and outputs as events,
+    /// each joined with the transaction and block it belongs to.
+    ///
+    /// Fields needed for the join are added to the field selection of the query, the context
+    /// fields are selected by `event_config`.
+    pub async fn get_events(
+        &self,
+        query: &Query,
+        event_config: &EventConfig,
+    ) -> Result<EventResponse> {
+        let mut query = query.clone();
+        add_event_join_fields_to_selection(&mut query, event_config);
+        let arrow_response = self.get_arrow(&query).await.context("get data")?;
+        Ok(EventResponse::from(&arrow_response))
+    }
+
+    /// Sends the query as capnp if `binary` is set or as JSON otherwise.
+    ///
+    /// The capnp endpoint is experimental and only used when [`ClientConfig::binary_query`] is
+    /// enabled.
+    async fn send_query(&self, query: &Query, binary: bool) -> Result<reqwest::Response> {
         let mut url = self.url.clone();
         let mut segments = url.path_segments_mut().ok().context("get path segments")?;
         segments.push("query");
         segments.push("arrow-ipc");
+        if binary {
+            segments.push("capnp");
+        }
         std::mem::drop(segments);
         let mut req = self.http_client.request(Method::POST, url);
 
@@ -353,7 +565,29 @@ impl Client {
             req = req.bearer_auth(bearer_token);
         }
 
-        let res = req.json(&query).send().await.context("execute http req")?;
+        let req = if binary {
+            req.header(header::CONTENT_TYPE, "application/x-capnp")
+                .body(query.to_capnp_bytes())
+        } else {
+            req.json(&query)
+        };
+
+        req.send().await.context("execute http req")
+    }
+
+    /// Executes query once and returns the result in (Arrow, size) format.
+    async fn get_arrow_impl(&self, query: &Query) -> Result<(ArrowResponse, u64)> {
+        let binary = self.binary_query.load(Ordering::Relaxed);
+        let mut res = self.send_query(query, binary).await?;
+
+        if binary && binary_query_unsupported(res.status()) {
+            log::warn!(
+                "server doesn't support binary queries, status code {}. Falling back to JSON.",
+                res.status()
+            );
+            self.binary_query.store(false, Ordering::Relaxed);
+            res = self.send_query(query, false).await?;
+        }
 
         let status = res.status();
         if !status.is_success() {
@@ -385,6 +619,80 @@ impl Client {
 
     /// Internal implementation for get_arrow.
     async fn get_arrow_with_size(&self, query: &Query) -> Result<(ArrowResponse, u64)> {
+        // retrying doesn't help if the server is going to reject the query
+        self.validate_query(query)?;
+
+        match shard::shard_query(query, self.max_query_hashes) {
+            Some(sharded) => self.get_sharded(sharded).await,
+            None => self.get_arrow_with_retries(query).await,
+        }
+    }
+
+    /// Checks the query with [`Query::validate`], or [`Query::validate_lenient`] if unknown codes
+    /// are allowed.
+    fn validate_query(&self, query: &Query) -> Result<()> {
+        let res = if self.allow_unknown_codes {
+            query.validate_lenient()
+        } else {
+            query.validate()
+        };
+        res.context("validate query")
+    }
+
+    /// Executes the shards of a query and merges the responses.
+    ///
+    /// The first shard decides where the response ends, the others are run to the same block,
+    /// at most [`SHARD_CONCURRENCY`] at a time.
+    async fn get_sharded(&self, sharded: shard::ShardedQuery) -> Result<(ArrowResponse, u64)> {
+        let (first, rest) = sharded.queries.split_first().context("no shards")?;
+        let (res, mut size) = self
+            .get_arrow_with_retries(first)
+            .await
+            .context("get first shard")?;
+        let next_block = res.next_block;
+
+        let mut rest = futures::stream::iter(rest.iter().cloned().enumerate())
+            .map(|(i, mut query)| async move {
+                query.to_block = Some(next_block);
+                let mut pages = Vec::new();
+                let mut size = 0;
+                while query.from_block < next_block {
+                    let (res, res_size) = self.get_arrow_with_retries(&query).await?;
+                    if res.next_block <= query.from_block {
+                        return Err(anyhow!(
+                            "shard response didn't advance past block {}",
+                            query.from_block
+                        ));
+                    }
+                    query.from_block = res.next_block;
+                    size += res_size;
+                    pages.push(res);
+                }
+                Ok::<_, anyhow::Error>((i, pages, size))
+            })
+            .buffer_unordered(SHARD_CONCURRENCY)
+            .try_collect::<Vec<_>>()
+            .await
+            .context("get shards")?;
+        // merge expects the responses in shard order
+        rest.sort_unstable_by_key(|(i, _, _)| *i);
+
+        let mut responses = vec![vec![res]];
+        for (_, pages, pages_size) in rest {
+            responses.push(pages);
+            size += pages_size;
+        }
+
+        let res = tokio::task::spawn_blocking(move || sharded.merge(responses, next_block))
+            .await
+            .context("join merge task")?
+            .context("merge shard responses")?;
+
+        Ok((res, size))
+    }
+
+    /// Executes query with retries, without validating or splitting it.
+    async fn get_arrow_with_retries(&self, query: &Query) -> Result<(ArrowResponse, u64)> {
         let mut base = self.retry_base_ms;
 
         let mut err = anyhow!("");
@@ -424,58 +732,440 @@ impl Client {
         stream::stream_arrow(self, query, config).await
     }
 
+    /// Spawns task to execute query and return data via a channel, converted to native Rust types.
+    ///
+    /// Conversion runs on the rayon thread pool. Returns an error if `column_mapping` or
+    /// `hex_output` is set in the config since the converted columns couldn't be decoded.
+    pub async fn stream(
+        self: Arc<Self>,
+        query: Query,
+        config: StreamConfig,
+    ) -> Result<mpsc::Receiver<Result<QueryResponse>>> {
+        stream::stream(self, query, config).await
+    }
+
+    /// Same as [`stream`](Self::stream) but converts the data to the given type, for example
+    /// `Vec<Receipt>` or a `Vec` of a type that derives `FromArrow`.
+    pub async fn stream_as<T: FromArrowResponse>(
+        self: Arc<Self>,
+        query: Query,
+        config: StreamConfig,
+    ) -> Result<mpsc::Receiver<Result<QueryResponse<T>>>> {
+        stream::stream(self, query, config).await
+    }
+
+    /// Spawns task to execute query and return events via a channel. Each receipt, input and
+    /// output is joined with the transaction and block it belongs to.
+    ///
+    /// Fields needed for the join are added to the field selection of the query, the context
+    /// fields are selected by `event_config`.
+    pub async fn stream_events(
+        self: Arc<Self>,
+        mut query: Query,
+        config: StreamConfig,
+        event_config: EventConfig,
+    ) -> Result<mpsc::Receiver<Result<EventResponse>>> {
+        add_event_join_fields_to_selection(&mut query, &event_config);
+        stream::stream(self, query, config).await
+    }
+
+    /// Returns the first block whose time is at or after `time`, or `None` if there is no such
+    /// block yet.
+    ///
+    /// Binary searches block times with a query for a single block per step. The times are
+    /// cached on the client, so later searches take fewer queries.
+    pub async fn block_at_or_after(&self, time: Timestamp) -> Result<Option<u64>> {
+        let head = self.get_height().await.context("get height")?;
+        let height = self.first_block_not_before(head, |t| t < time).await?;
+        Ok((height <= head).then_some(height))
+    }
+
+    /// Returns the last block whose time is at or before `time`, or `None` if the first block
+    /// is later. See [`Client::block_at_or_after`].
+    pub async fn block_at_or_before(&self, time: Timestamp) -> Result<Option<u64>> {
+        let head = self.get_height().await.context("get height")?;
+        let height = self.first_block_not_before(head, |t| t <= time).await?;
+        Ok(height.checked_sub(1))
+    }
+
+    /// Sets the block range of `query` to the blocks with times in `range`.
+    ///
+    /// If the range ends after the chain head, `to_block` is set to the block after the head, so
+    /// the query doesn't pick up blocks that are added later. If no block is in the range, the
+    /// block range is empty and the query won't pass [`Query::validate`]. See
+    /// [`Client::block_at_or_after`] for how blocks are looked up.
+    pub async fn resolve_time_range(
+        &self,
+        mut query: Query,
+        range: impl RangeBounds<Timestamp>,
+    ) -> Result<Query> {
+        let head = self.get_height().await.context("get height")?;
+
+        query.from_block = match range.start_bound() {
+            Bound::Included(&from) => self.first_block_not_before(head, |t| t < from).await?,
+            Bound::Excluded(&from) => self.first_block_not_before(head, |t| t <= from).await?,
+            Bound::Unbounded => 0,
+        };
+        // first_block_not_before returns at most head + 1.
+        query.to_block = Some(match range.end_bound() {
+            Bound::Included(&to) => self.first_block_not_before(head, |t| t <= to).await?,
+            Bound::Excluded(&to) => self.first_block_not_before(head, |t| t < to).await?,
+            Bound::Unbounded => head + 1,
+        });
+
+        Ok(query)
+    }
+
+    /// Returns the first block up to `head` whose time isn't `is_before`, or `head + 1`.
+    async fn first_block_not_before(
+        &self,
+        head: u64,
+        is_before: impl Fn(Timestamp) -> bool,
+    ) -> Result<u64> {
+        block_time::partition_point(&self.block_times, head, is_before, |height| async move {
+            let res = self
+                .get_arrow(&block_time::block_time_query(height))
+                .await
+                .context("get block time")?;
+            block_time::block_time_from_response(&res, height)
+        })
+        .await
+    }
+
     /// Getter for url field.
     pub fn url(&self) -> &Url {
         &self.url
     }
 }
 
-#[allow(dead_code)]
-fn check_simple_stream_params(config: &StreamConfig) -> Result<()> {
-    if config.column_mapping.is_some() {
-        return Err(anyhow!("config.column_mapping can't be passed to single type function. User is expected to map values manually."));
-    }
-
-    Ok(())
+/// Whether the status code of a binary query means that the server doesn't support them, as
+/// opposed to rejecting the query itself.
+fn binary_query_unsupported(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::NOT_FOUND | StatusCode::METHOD_NOT_ALLOWED | StatusCode::UNSUPPORTED_MEDIA_TYPE
+    )
 }
 
-#[allow(dead_code)]
-fn add_event_join_fields_to_selection(query: &mut Query) {
+fn add_event_join_fields_to_selection(query: &mut Query, config: &EventConfig) {
     // Field lists for implementing event based API, these fields are used for joining
     // so they should always be added to the field selection.
-    const BLOCK_JOIN_FIELDS: &[&str] = &["height"]; // Or is it "block_height"?
-    const TX_JOIN_FIELDS: &[&str] = &["id"];
+    const BLOCK_JOIN_FIELDS: &[&str] = &["height"];
+    const TX_JOIN_FIELDS: &[&str] = &["block_height", "id"];
     const RECEIPT_JOIN_FIELDS: &[&str] = &["tx_id", "block_height"];
     const INPUT_JOIN_FIELDS: &[&str] = &["tx_id", "block_height"];
     const OUTPUT_JOIN_FIELDS: &[&str] = &["tx_id", "block_height"];
 
-    if !query.field_selection.block.is_empty() {
-        for field in BLOCK_JOIN_FIELDS.iter() {
-            query.field_selection.block.insert(field.to_string());
+    let field_selection = &mut query.field_selection;
+
+    add_context_fields(
+        &mut field_selection.block,
+        config.block_fields.as_ref(),
+        BLOCK_JOIN_FIELDS,
+    );
+    add_context_fields(
+        &mut field_selection.transaction,
+        config.transaction_fields.as_ref(),
+        TX_JOIN_FIELDS,
+    );
+
+    for (selection, join_fields, schema) in [
+        (
+            &mut field_selection.block,
+            BLOCK_JOIN_FIELDS,
+            schema::block_header(),
+        ),
+        (
+            &mut field_selection.transaction,
+            TX_JOIN_FIELDS,
+            schema::transaction(),
+        ),
+        (
+            &mut field_selection.receipt,
+            RECEIPT_JOIN_FIELDS,
+            schema::receipt(),
+        ),
+        (
+            &mut field_selection.input,
+            INPUT_JOIN_FIELDS,
+            schema::input(),
+        ),
+        (
+            &mut field_selection.output,
+            OUTPUT_JOIN_FIELDS,
+            schema::output(),
+        ),
+    ] {
+        if selection.is_empty() {
+            continue;
         }
-    }
 
-    if !query.field_selection.transaction.is_empty() {
-        for field in TX_JOIN_FIELDS.iter() {
-            query.field_selection.transaction.insert(field.to_string());
+        for field in join_fields.iter() {
+            selection.insert(field.to_string());
         }
-    }
 
-    if !query.field_selection.receipt.is_empty() {
-        for field in RECEIPT_JOIN_FIELDS.iter() {
-            query.field_selection.receipt.insert(field.to_string());
+        // Typed rows can't be constructed without the non-nullable columns.
+        for field in schema.fields.iter().filter(|f| !f.is_nullable) {
+            selection.insert(field.name.to_string());
         }
     }
+}
+
+/// Pairs the calls in the response with their return receipts and keeps the calls of `function`.
+fn select_calls(data: ResponseData, function: &str) -> Vec<CallContext> {
+    let script_data = data
+        .transactions
+        .into_iter()
+        .flatten()
+        .map(|tx| (tx.id, tx.script_data))
+        .collect::<HashMap<_, _>>();
+    let receipts = data.receipts.into_iter().flatten().collect::<Vec<_>>();
+
+    let mut calls = Vec::new();
+    for tx_receipts in receipts.chunk_by(|a, b| a.tx_id == b.tx_id) {
+        let Some(script_data) = script_data.get(&tx_receipts[0].tx_id).cloned().flatten() else {
+            continue;
+        };
 
-    if !query.field_selection.input.is_empty() {
-        for field in INPUT_JOIN_FIELDS.iter() {
-            query.field_selection.input.insert(field.to_string());
+        for (call, ret) in abi::pair_calls(tx_receipts) {
+            let call = &tx_receipts[call];
+            if abi::call_data(call, &script_data).map(|(name, _)| name) != Some(function) {
+                continue;
+            }
+
+            calls.push(CallContext {
+                call: call.clone(),
+                return_receipt: ret.map(|ret| tx_receipts[ret].clone()),
+                script_data: Some(script_data.clone()),
+            });
         }
     }
 
-    if !query.field_selection.output.is_empty() {
-        for field in OUTPUT_JOIN_FIELDS.iter() {
-            query.field_selection.output.insert(field.to_string());
-        }
+    calls
+}
+
+fn add_context_fields(
+    selection: &mut BTreeSet<String>,
+    fields: Option<&BTreeSet<String>>,
+    join_fields: &[&str],
+) {
+    match fields {
+        Some(fields) => selection.extend(fields.iter().cloned()),
+        None => selection.extend(join_fields.iter().map(|f| f.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hyperfuel_format::Receipt;
+
+    #[test]
+    fn test_add_event_join_fields_to_selection() {
+        let mut query = Query {
+            field_selection: FieldSelection {
+                receipt: ["data".to_owned()].into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let config = EventConfig {
+            block_fields: Some(BTreeSet::new()),
+            transaction_fields: Some(["script".to_owned()].into()),
+        };
+
+        add_event_join_fields_to_selection(&mut query, &config);
+
+        let selection = query.field_selection;
+        assert!(selection.block.is_empty());
+        assert!(selection.input.is_empty());
+        assert_eq!(
+            selection.transaction,
+            ["block_height", "id", "script", "status", "time", "tx_type"]
+                .map(String::from)
+                .into()
+        );
+        assert!(["tx_id", "block_height", "receipt_type", "data"]
+            .iter()
+            .all(|f| selection.receipt.contains(*f)));
+    }
+
+    #[test]
+    fn test_add_event_join_fields_to_selection_defaults() {
+        let mut query = Query::default();
+
+        add_event_join_fields_to_selection(&mut query, &EventConfig::default());
+
+        // only the join fields and the ones typed rows need, all block header fields are needed
+        let selection = query.field_selection;
+        assert_eq!(selection.block.len(), schema::block_header().fields.len());
+        assert_eq!(
+            selection.transaction,
+            ["block_height", "id", "status", "time", "tx_type"]
+                .map(String::from)
+                .into()
+        );
+        assert!(selection.receipt.is_empty());
+    }
+
+    #[test]
+    fn test_select_calls() {
+        let contract: Hash = [3; 32].into();
+        let call = |tx: u8, param1: u64, name: &str| Receipt {
+            tx_id: [tx; 32].into(),
+            receipt_type: ReceiptType::Call,
+            to: Some(contract.clone()),
+            param1: Some(param1.into()),
+            param2: Some((param1 + 8 + name.len() as u64).into()),
+            ..Default::default()
+        };
+        let ret = |tx: u8| Receipt {
+            tx_id: [tx; 32].into(),
+            receipt_type: ReceiptType::ReturnData,
+            contract_id: Some(contract.clone()),
+            data: Some(vec![1].into()),
+            ..Default::default()
+        };
+        let script_data = |calls: &[(u64, &str)]| {
+            let mut data = Vec::new();
+            for (param1, name) in calls {
+                data.extend(param1.to_be_bytes());
+                data.extend((param1 + 8 + name.len() as u64).to_be_bytes());
+                data.extend(fn_selector(name));
+            }
+            Some(data.into())
+        };
+
+        let data = ResponseData {
+            transactions: vec![vec![
+                hyperfuel_format::Transaction {
+                    id: [1; 32].into(),
+                    script_data: script_data(&[(100, "transfer"), (200, "mint")]),
+                    ..Default::default()
+                },
+                hyperfuel_format::Transaction {
+                    id: [2; 32].into(),
+                    script_data: script_data(&[(100, "transfer")]),
+                    ..Default::default()
+                },
+            ]],
+            receipts: vec![
+                vec![call(1, 100, "transfer"), ret(1), call(1, 200, "mint")],
+                vec![ret(1), call(2, 100, "transfer")],
+            ],
+            ..Default::default()
+        };
+
+        let calls = select_calls(data, "transfer");
+        assert_eq!(calls.len(), 2);
+        assert_eq!(calls[0].call, call(1, 100, "transfer"));
+        assert_eq!(calls[0].return_receipt, Some(ret(1)));
+        assert_eq!(calls[1].call, call(2, 100, "transfer"));
+        assert_eq!(calls[1].return_receipt, None);
+    }
+
+    #[test]
+    fn test_validate_query_unknown_codes() {
+        let query = Query {
+            receipts: vec![ReceiptSelection {
+                receipt_type: vec![200],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let client = |allow_unknown_codes| {
+            Client::new(ClientConfig {
+                allow_unknown_codes,
+                ..Default::default()
+            })
+            .unwrap()
+        };
+
+        assert!(client(None).validate_query(&query).is_err());
+        client(Some(true)).validate_query(&query).unwrap();
+    }
+
+    /// Serves one canned response per request on localhost and returns the requests' request
+    /// lines and content types.
+    fn serve(
+        responses: Vec<&'static str>,
+    ) -> (Url, std::thread::JoinHandle<Vec<(String, String)>>) {
+        use std::io::{BufRead, BufReader, Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = std::thread::spawn(move || {
+            let mut requests = Vec::new();
+            for response in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut content_type = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    let line = line.trim_end().to_ascii_lowercase();
+                    if line.is_empty() {
+                        break;
+                    }
+                    if let Some(v) = line.strip_prefix("content-type: ") {
+                        content_type = v.to_owned();
+                    }
+                    if let Some(v) = line.strip_prefix("content-length: ") {
+                        content_length = v.parse().unwrap();
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+
+                reader
+                    .get_mut()
+                    .write_all(
+                        format!(
+                            "HTTP/1.1 {response}\r\ncontent-length: 0\r\nconnection: close\r\n\r\n"
+                        )
+                        .as_bytes(),
+                    )
+                    .unwrap();
+                requests.push((request_line.trim_end().to_owned(), content_type));
+            }
+            requests
+        });
+
+        (url.parse().unwrap(), handle)
+    }
+
+    #[tokio::test]
+    async fn test_binary_query_fallback() {
+        let (url, server) = serve(vec!["404 Not Found", "400 Bad Request"]);
+        let client = Client::new(ClientConfig {
+            url: Some(url),
+            binary_query: Some(true),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let err = client.get_arrow_impl(&Query::default()).await.unwrap_err();
+        assert!(err.to_string().contains("400"));
+        assert!(!client.binary_query.load(Ordering::Relaxed));
+
+        let requests = server.join().unwrap();
+        assert_eq!(
+            requests,
+            [
+                (
+                    "POST /query/arrow-ipc/capnp HTTP/1.1".to_owned(),
+                    "application/x-capnp".to_owned()
+                ),
+                (
+                    "POST /query/arrow-ipc HTTP/1.1".to_owned(),
+                    "application/json".to_owned()
+                ),
+            ]
+        );
     }
 }
@@ -1,9 +1,16 @@
-use std::{collections::VecDeque, path::PathBuf, sync::Arc};
+use std::{
+    collections::{btree_map::Entry, BTreeMap, VecDeque},
+    path::PathBuf,
+    sync::Arc,
+};
 
 use anyhow::{Context, Result};
 use hyperfuel_net_types::Query;
 use hyperfuel_schema::concat_chunks;
-use polars_arrow::{datatypes::ArrowSchema as Schema, legacy::error::PolarsError};
+use polars_arrow::{
+    datatypes::{ArrowDataType as DataType, ArrowSchema as Schema},
+    legacy::error::PolarsError,
+};
 use polars_parquet::parquet::write::FileStreamer;
 use polars_parquet::write::StatisticsOptions;
 use polars_parquet::{
@@ -53,16 +60,42 @@ pub async fn collect_parquet(
     outputs_path.push("outputs.parquet");
     let (mut outputs_sender, outputs_join) = spawn_writer(outputs_path)?;
 
+    let mut decoded_logs_path = path.clone();
+    decoded_logs_path.push("decoded_logs");
+    // Decoded logs writers are spawned when a table shows up first.
+    let mut decoded_logs_writers = BTreeMap::new();
+
     let mut rx = client
         .stream_arrow(query, config)
         .await
         .context("start stream")?;
 
     while let Some(resp) = rx.recv().await {
-        let resp = resp.context("get query response")?;
+        let mut resp = resp.context("get query response")?;
 
         log::trace!("got data up to block {}", resp.next_block);
 
+        for (table, batches) in std::mem::take(&mut resp.data.decoded_logs) {
+            let (sender, _) = match decoded_logs_writers.entry(table) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    tokio::fs::create_dir_all(&decoded_logs_path)
+                        .await
+                        .context("create decoded logs dir")?;
+                    let mut table_path = decoded_logs_path.clone();
+                    table_path.push(format!("{}.parquet", entry.key()));
+                    entry.insert(spawn_writer(table_path)?)
+                }
+            };
+
+            for batch in batches {
+                sender
+                    .send(batch)
+                    .await
+                    .context("write decoded logs chunk to parquet")?;
+            }
+        }
+
         let blocks_fut = async move {
             for batch in resp.data.blocks {
                 blocks_sender
@@ -155,6 +188,13 @@ pub async fn collect_parquet(
         .context("join outputs task")?
         .context("finish outputs file")?;
 
+    for (table, (sender, join)) in decoded_logs_writers {
+        std::mem::drop(sender);
+        join.await
+            .with_context(|| format!("join decoded logs {table} task"))?
+            .with_context(|| format!("finish decoded logs {table} file"))?;
+    }
+
     Ok(())
 }
 
@@ -317,7 +357,13 @@ fn encode_row_group(
         .schema
         .fields
         .iter()
-        .map(|f| transverse(&f.data_type, |_| Encoding::Plain))
+        .map(|f| {
+            transverse(&f.data_type, |dt| match dt {
+                // dictionary arrays, like enum columns mapped to names, can't be plain encoded
+                DataType::Dictionary(..) => Encoding::RleDictionary,
+                _ => Encoding::Plain,
+            })
+        })
         .collect::<Vec<_>>();
 
     let data = batch
@@ -381,3 +427,116 @@ impl FallibleStreamingIterator for CompressedPageIter {
 }
 
 const ROW_GROUP_MAX_ROWS: usize = 10_000;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{column_mapping, DataType as MappedType, LogDecoder, ToArrow};
+    use hyperfuel_format::{Receipt, ReceiptType, Transaction, TAI64_UNIX_EPOCH};
+
+    #[tokio::test]
+    async fn test_write_decoded_logs_table() {
+        let decoder =
+            LogDecoder::from_json_abi(include_str!("../test-data/logs-abi.json")).unwrap();
+
+        let mut data = vec![7; 32];
+        data.extend(10u64.to_be_bytes());
+        data.extend(0u64.to_be_bytes());
+        data.extend(1u64.to_be_bytes());
+        data.push(1);
+        data.extend(0u64.to_be_bytes());
+        data.push(1);
+        data.extend([0; 16]);
+        let receipts = vec![Receipt {
+            receipt_type: ReceiptType::LogData,
+            rb: Some(17859024950540227480.into()),
+            data: Some(data.into()),
+            ..Default::default()
+        }];
+        let batch = Receipt::to_arrow(&receipts, None).unwrap();
+        let table = decoder
+            .decode_to_tables(&batch)
+            .unwrap()
+            .remove("Transfer")
+            .unwrap();
+
+        let mut path = std::env::temp_dir();
+        path.push(format!(
+            "hyperfuel-decoded-logs-{}.parquet",
+            std::process::id()
+        ));
+
+        let (sender, join) = spawn_writer(path.clone()).unwrap();
+        sender.send(table).await.unwrap();
+        std::mem::drop(sender);
+        join.await.unwrap().unwrap();
+
+        let len = tokio::fs::metadata(&path).await.unwrap().len();
+        tokio::fs::remove_file(&path).await.unwrap();
+        assert!(len > 0);
+    }
+
+    #[tokio::test]
+    async fn test_write_mapped_columns() {
+        let receipts = vec![
+            Receipt {
+                receipt_type: ReceiptType::LogData,
+                tx_id: [1; 32].into(),
+                amount: Some(7.into()),
+                ..Default::default()
+            },
+            Receipt {
+                receipt_type: ReceiptType::Call,
+                tx_id: [2; 32].into(),
+                ..Default::default()
+            },
+        ];
+        let batch = Receipt::to_arrow(&receipts, None).unwrap();
+        let mapping = [
+            ("receipt_type".to_owned(), MappedType::Dictionary),
+            ("tx_id".to_owned(), MappedType::FixedSizeBinary(32)),
+            ("amount".to_owned(), MappedType::Decimal128(20, 9)),
+        ]
+        .into_iter()
+        .collect();
+        let receipts = column_mapping::apply_to_batch(&batch, &mapping).unwrap();
+
+        let txs = vec![Transaction {
+            input_asset_ids: Some(vec![[3; 32].into(), [4; 32].into()]),
+            witnesses: Some([&1u64.to_be_bytes()[..], &[5; 8]].concat().into()),
+            time: (TAI64_UNIX_EPOCH + 1_700_000_000).into(),
+            ..Default::default()
+        }];
+        let batch = Transaction::to_arrow(&txs, None).unwrap();
+        let mapping = [
+            ("input_asset_ids".to_owned(), MappedType::HashList),
+            ("witnesses".to_owned(), MappedType::BinaryList),
+            ("tx_type".to_owned(), MappedType::Utf8),
+            ("time".to_owned(), MappedType::Timestamp),
+        ]
+        .into_iter()
+        .collect();
+        let txs = column_mapping::apply_to_batch(&batch, &mapping).unwrap();
+
+        for (name, batch) in [("receipts", receipts), ("transactions", txs)] {
+            write_and_remove(batch, name).await;
+        }
+    }
+
+    async fn write_and_remove(batch: ArrowBatch, name: &str) {
+        let mut path = std::env::temp_dir();
+        path.push(format!(
+            "hyperfuel-mapped-{name}-{}.parquet",
+            std::process::id()
+        ));
+
+        let (sender, join) = spawn_writer(path.clone()).unwrap();
+        sender.send(batch).await.unwrap();
+        std::mem::drop(sender);
+        join.await.unwrap().unwrap();
+
+        let len = tokio::fs::metadata(&path).await.unwrap().len();
+        tokio::fs::remove_file(&path).await.unwrap();
+        assert!(len > 0);
+    }
+}
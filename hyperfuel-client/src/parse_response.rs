@@ -94,6 +94,7 @@ pub fn parse_query_response(bytes: &[u8]) -> Result<ArrowResponse> {
             receipts,
             inputs,
             outputs,
+            decoded_logs: Default::default(),
         },
         // rollback_guard,
     })
@@ -1,3 +1,5 @@
+use std::panic::{self, AssertUnwindSafe};
+
 use tokio::sync::oneshot;
 
 pub fn spawn<F, T>(func: F) -> oneshot::Receiver<T>
@@ -8,9 +10,22 @@ where
     let (tx, rx) = oneshot::channel();
 
     rayon::spawn(move || {
-        let res = func();
-        tx.send(res).ok();
+        // if `func` panics `tx` is dropped, so the receiver errors instead of the pool aborting
+        if let Ok(res) = panic::catch_unwind(AssertUnwindSafe(func)) {
+            tx.send(res).ok();
+        }
     });
 
     rx
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_spawn_panic() {
+        assert_eq!(spawn(|| 1).await.unwrap(), 1);
+        assert!(spawn(|| -> u8 { panic!("decode failed") }).await.is_err());
+    }
+}
@@ -0,0 +1,669 @@
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context, Result};
+use hyperfuel_format::Hash;
+use hyperfuel_net_types::{InputSelection, OutputSelection, Query, ReceiptSelection};
+use hyperfuel_schema::concat_chunks;
+use polars_arrow::array::{BinaryArray, BinaryViewArray, UInt64Array, UInt8Array};
+use polars_arrow::datatypes::ArrowSchema as Schema;
+
+use crate::util::take_rows;
+use crate::{ArrowBatch, ArrowChunk, ArrowResponse, ArrowResponseData};
+
+/// Selections with lists of hashes, which are what makes queries too big to send.
+trait HashLists: Clone {
+    fn hash_lists(&self) -> Vec<&Vec<Hash>>;
+    fn hash_lists_mut(&mut self) -> Vec<&mut Vec<Hash>>;
+
+    fn num_hashes(&self) -> usize {
+        self.hash_lists().iter().map(|l| l.len()).sum()
+    }
+}
+
+macro_rules! impl_hash_lists {
+    ($ty:ty, $($field:ident),*) => {
+        impl HashLists for $ty {
+            fn hash_lists(&self) -> Vec<&Vec<Hash>> {
+                vec![$(&self.$field),*]
+            }
+
+            fn hash_lists_mut(&mut self) -> Vec<&mut Vec<Hash>> {
+                vec![$(&mut self.$field),*]
+            }
+        }
+    };
+}
+
+impl_hash_lists!(
+    ReceiptSelection,
+    root_contract_id,
+    to,
+    to_address,
+    asset_id,
+    sender,
+    recipient,
+    contract_id
+);
+impl_hash_lists!(InputSelection, owner, asset_id, contract, sender, recipient);
+impl_hash_lists!(OutputSelection, to, asset_id, contract);
+
+/// A table of the response and the columns that identify its rows.
+struct Table {
+    /// Key columns, the first `sort_len` of them order the rows.
+    ///
+    /// Inputs and outputs don't have an index column, so they're identified by their contents.
+    /// Transactions, inputs and outputs have no column that orders them within a block, so only
+    /// their block order is kept when merging.
+    key: &'static [&'static str],
+    sort_len: usize,
+}
+
+const BLOCKS: Table = Table {
+    key: &["height"],
+    sort_len: 1,
+};
+const TRANSACTIONS: Table = Table {
+    key: &["block_height", "id"],
+    sort_len: 1,
+};
+// receipt indexes count within a transaction
+const RECEIPTS: Table = Table {
+    key: &["block_height", "tx_id", "receipt_index"],
+    sort_len: 3,
+};
+const INPUTS: Table = Table {
+    key: &["block_height", "tx_id", "input_type", "utxo_id", "nonce"],
+    sort_len: 1,
+};
+const OUTPUTS: Table = Table {
+    key: &[
+        "block_height",
+        "tx_id",
+        "output_type",
+        "to",
+        "amount",
+        "asset_id",
+        "contract",
+        "input_index",
+        "balance_root",
+        "state_root",
+    ],
+    sort_len: 1,
+};
+
+/// A query split into several queries that each have at most `max_hashes` hashes.
+pub(crate) struct ShardedQuery {
+    pub(crate) queries: Vec<Query>,
+    /// Key columns that were added to the field selections to merge the responses, they are
+    /// removed from the merged response.
+    added: [BTreeSet<String>; 5],
+}
+
+/// Splits the selections of `query` over several queries if it has more than `max_hashes`
+/// hashes, returns `None` if it doesn't have to be split.
+///
+/// A selection matches any of the hashes in a list, so splitting a list splits the rows that
+/// the selection matches. Matching rows of the shards together are the rows `query` matches.
+pub(crate) fn shard_query(query: &Query, max_hashes: usize) -> Option<ShardedQuery> {
+    let num_hashes = query.receipts.iter().map(|s| s.num_hashes()).sum::<usize>()
+        + query.inputs.iter().map(|s| s.num_hashes()).sum::<usize>()
+        + query.outputs.iter().map(|s| s.num_hashes()).sum::<usize>();
+    if num_hashes <= max_hashes {
+        return None;
+    }
+
+    let mut template = query.clone();
+    template.receipts.clear();
+    template.inputs.clear();
+    template.outputs.clear();
+
+    let selection = &mut template.field_selection;
+    let added = [
+        (&mut selection.block, &BLOCKS),
+        (&mut selection.transaction, &TRANSACTIONS),
+        (&mut selection.receipt, &RECEIPTS),
+        (&mut selection.input, &INPUTS),
+        (&mut selection.output, &OUTPUTS),
+    ]
+    .map(|(fields, table)| {
+        // tables without fields aren't returned, so there is nothing to merge
+        if fields.is_empty() {
+            return BTreeSet::new();
+        }
+        let added = table
+            .key
+            .iter()
+            .filter(|name| !fields.contains(**name))
+            .map(|name| name.to_string())
+            .collect::<BTreeSet<_>>();
+        fields.extend(added.iter().cloned());
+        added
+    });
+
+    let mut sharder = Sharder {
+        template,
+        max_hashes,
+        queries: Vec::new(),
+        num_hashes: 0,
+    };
+    sharder.add(&query.receipts, |q| &mut q.receipts);
+    sharder.add(&query.inputs, |q| &mut q.inputs);
+    sharder.add(&query.outputs, |q| &mut q.outputs);
+
+    Some(ShardedQuery {
+        queries: sharder.queries,
+        added,
+    })
+}
+
+/// Packs selections into queries.
+struct Sharder {
+    template: Query,
+    max_hashes: usize,
+    queries: Vec<Query>,
+    /// Hashes in the last query.
+    num_hashes: usize,
+}
+
+impl Sharder {
+    fn add<S: HashLists>(&mut self, selections: &[S], table: fn(&mut Query) -> &mut Vec<S>) {
+        for selection in selections {
+            let mut pieces = Vec::new();
+            split_selection(selection.clone(), self.max_hashes, &mut pieces);
+
+            for piece in pieces {
+                let n = piece.num_hashes();
+                if self.queries.is_empty() || self.num_hashes + n > self.max_hashes {
+                    self.queries.push(self.template.clone());
+                    self.num_hashes = 0;
+                }
+                self.num_hashes += n;
+                table(self.queries.last_mut().unwrap()).push(piece);
+            }
+        }
+    }
+}
+
+/// Splits the longest hash list of `selection` into chunks that fit into `max_hashes` together
+/// with the other lists, which are kept whole.
+///
+/// A selection has to match every list, so only one list can be split without sending every
+/// combination of chunks. Pieces have more than `max_hashes` hashes only if the other lists
+/// alone have that many, then the chunks have a single hash.
+fn split_selection<S: HashLists>(mut selection: S, max_hashes: usize, out: &mut Vec<S>) {
+    let lens = selection
+        .hash_lists()
+        .iter()
+        .map(|l| l.len())
+        .collect::<Vec<_>>();
+    let (longest, &len) = lens
+        .iter()
+        .enumerate()
+        .max_by_key(|(_, len)| **len)
+        .unwrap();
+    let total = lens.iter().sum::<usize>();
+
+    if total <= max_hashes || len < 2 {
+        out.push(selection);
+        return;
+    }
+
+    let chunk_len = max_hashes.saturating_sub(total - len).max(1);
+    let list = std::mem::take(selection.hash_lists_mut().swap_remove(longest));
+    for chunk in list.chunks(chunk_len) {
+        let mut piece = selection.clone();
+        *piece.hash_lists_mut().swap_remove(longest) = chunk.to_vec();
+        out.push(piece);
+    }
+}
+
+impl ShardedQuery {
+    /// Merges the responses of each shard into one response that ends at `next_block`.
+    ///
+    /// Rows that several shards returned are kept once and rows are ordered by block. Receipts
+    /// of a block are grouped by transaction id and ordered by their index within the
+    /// transaction. Transactions, inputs and outputs within a block, and the transactions that
+    /// receipts are grouped by, are not in the order of the chain.
+    pub(crate) fn merge(
+        &self,
+        responses: Vec<Vec<ArrowResponse>>,
+        next_block: u64,
+    ) -> Result<ArrowResponse> {
+        let all = || responses.iter().flatten();
+        let archive_height = all().filter_map(|r| r.archive_height).max();
+        let total_execution_time = all().map(|r| r.total_execution_time).sum();
+
+        let table = |get: fn(&ArrowResponseData) -> &Vec<ArrowBatch>| {
+            responses
+                .iter()
+                .map(|pages| pages.iter().flat_map(|r| get(&r.data)).cloned().collect())
+                .collect::<Vec<_>>()
+        };
+        let [blocks, transactions, receipts, inputs, outputs] = &self.added;
+
+        Ok(ArrowResponse {
+            archive_height,
+            next_block,
+            total_execution_time,
+            data: ArrowResponseData {
+                blocks: merge_table(&BLOCKS, table(|d| &d.blocks), blocks)
+                    .context("merge blocks")?,
+                transactions: merge_table(&TRANSACTIONS, table(|d| &d.transactions), transactions)
+                    .context("merge transactions")?,
+                receipts: merge_table(&RECEIPTS, table(|d| &d.receipts), receipts)
+                    .context("merge receipts")?,
+                inputs: merge_table(&INPUTS, table(|d| &d.inputs), inputs)
+                    .context("merge inputs")?,
+                outputs: merge_table(&OUTPUTS, table(|d| &d.outputs), outputs)
+                    .context("merge outputs")?,
+                decoded_logs: Default::default(),
+            },
+        })
+    }
+}
+
+/// Merges the batches that each shard returned for a table into one batch and drops the
+/// `added` columns.
+fn merge_table(
+    table: &Table,
+    shards: Vec<Vec<ArrowBatch>>,
+    added: &BTreeSet<String>,
+) -> Result<Vec<ArrowBatch>> {
+    let batches = shards
+        .into_iter()
+        .enumerate()
+        .flat_map(|(shard, batches)| batches.into_iter().map(move |b| (shard, b)))
+        .filter(|(_, b)| !b.chunk.is_empty())
+        .collect::<Vec<_>>();
+    let Some((_, first)) = batches.first() else {
+        return Ok(Vec::new());
+    };
+
+    let schema = first.schema.clone();
+    let chunks = batches
+        .iter()
+        .map(|(_, b)| b.chunk.clone())
+        .collect::<Vec<_>>();
+    let batch = ArrowBatch {
+        chunk: Arc::new(concat_chunks(&chunks).context("concat batches")?),
+        schema,
+    };
+    let shard_of_row = batches
+        .iter()
+        .flat_map(|(shard, b)| std::iter::repeat_n(*shard, b.chunk.len()))
+        .collect::<Vec<_>>();
+
+    let (keys, sort_lens) = row_keys(&batch, table)?;
+
+    // a shard returns every row at most once, but rows without an index column can look the
+    // same, so a key is kept as many times as the shard that returned it most often has it
+    let mut kept = HashMap::<&[u8], usize>::new();
+    let mut in_shard = HashMap::<(usize, &[u8]), usize>::new();
+    let mut rows = Vec::new();
+    for (row, key) in keys.iter().enumerate() {
+        let n = in_shard.entry((shard_of_row[row], key)).or_default();
+        *n += 1;
+        let kept = kept.entry(key).or_default();
+        if *n > *kept {
+            *kept = *n;
+            rows.push(row);
+        }
+    }
+
+    rows.sort_by(|&a, &b| keys[a][..sort_lens[a]].cmp(&keys[b][..sort_lens[b]]));
+
+    let (fields, cols): (Vec<_>, Vec<_>) = batch
+        .schema
+        .fields
+        .iter()
+        .zip(batch.chunk.columns())
+        .filter(|(field, _)| !added.contains(&field.name))
+        .map(|(field, col)| (field.clone(), take_rows(col.as_ref(), &rows)))
+        .unzip();
+
+    Ok(vec![ArrowBatch {
+        chunk: Arc::new(ArrowChunk::new(cols)),
+        schema: Arc::new(Schema::from(fields)),
+    }])
+}
+
+/// Encodes the key columns of each row into bytes that are equal if the keys are equal, and
+/// returns them with the length of the bytes of the sort columns.
+///
+/// Every value is a validity byte followed by the value, numbers are big endian and binary
+/// values are length prefixed, so the bytes of the sort columns order like the values.
+fn row_keys(batch: &ArrowBatch, table: &Table) -> Result<(Vec<Vec<u8>>, Vec<usize>)> {
+    let mut keys = vec![Vec::new(); batch.chunk.len()];
+    let mut sort_lens = vec![0; batch.chunk.len()];
+
+    for (i, name) in table.key.iter().enumerate() {
+        if i == table.sort_len {
+            sort_lens = keys.iter().map(|k| k.len()).collect();
+        }
+
+        let idx = batch
+            .schema
+            .fields
+            .iter()
+            .position(|f| f.name == *name)
+            .with_context(|| format!("key column {name} is missing"))?;
+        let col = batch.chunk.columns()[idx].as_any();
+
+        if let Some(col) = col.downcast_ref::<UInt64Array>() {
+            push_values(&mut keys, col.iter(), |v| v.to_be_bytes().to_vec());
+        } else if let Some(col) = col.downcast_ref::<UInt8Array>() {
+            push_values(&mut keys, col.iter(), |v| vec![*v]);
+        } else if let Some(col) = col.downcast_ref::<BinaryViewArray>() {
+            push_values(&mut keys, col.iter(), length_prefixed);
+        } else if let Some(col) = col.downcast_ref::<BinaryArray<i32>>() {
+            push_values(&mut keys, col.iter(), length_prefixed);
+        } else {
+            return Err(anyhow!(
+                "unexpected type of key column {name}: {:?}",
+                batch.chunk.columns()[idx].data_type()
+            ));
+        }
+    }
+    if table.sort_len == table.key.len() {
+        sort_lens = keys.iter().map(|k| k.len()).collect();
+    }
+
+    Ok((keys, sort_lens))
+}
+
+fn push_values<'a, T: ?Sized + 'a>(
+    keys: &mut [Vec<u8>],
+    values: impl Iterator<Item = Option<&'a T>>,
+    encode: impl Fn(&T) -> Vec<u8>,
+) {
+    for (key, value) in keys.iter_mut().zip(values) {
+        match value {
+            Some(value) => {
+                key.push(1);
+                key.extend(encode(value));
+            }
+            None => key.push(0),
+        }
+    }
+}
+
+fn length_prefixed(bytes: &[u8]) -> Vec<u8> {
+    let mut out = (bytes.len() as u32).to_be_bytes().to_vec();
+    out.extend_from_slice(bytes);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hyperfuel_format::ReceiptType;
+    use hyperfuel_net_types::FieldSelection;
+    use polars_arrow::array::ArrayFromIter;
+    use polars_arrow::datatypes::{ArrowDataType as DataType, Field};
+
+    fn ids(range: std::ops::Range<u8>) -> Vec<[u8; 32]> {
+        range.map(|i| [i; 32]).collect()
+    }
+
+    fn query() -> Query {
+        Query::builder()
+            .from_block(10)
+            .receipts(
+                ReceiptSelection::default()
+                    .root_contract_id(ids(0..25))
+                    .receipt_type([ReceiptType::Log]),
+            )
+            .receipts(ReceiptSelection::default().receipt_type([ReceiptType::Mint]))
+            .inputs(InputSelection::default().owner(ids(100..103)))
+            .receipt_fields([hyperfuel_net_types::ReceiptField::Data])
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_shard_query() {
+        let query = query();
+        assert!(shard_query(&query, 28).is_none());
+
+        let sharded = shard_query(&query, 10).unwrap();
+        assert!(sharded.queries.len() >= 3);
+
+        let mut root_contract_ids = Vec::new();
+        for q in sharded.queries.iter() {
+            let num_hashes = q.receipts.iter().map(|s| s.num_hashes()).sum::<usize>()
+                + q.inputs.iter().map(|s| s.num_hashes()).sum::<usize>();
+            assert!(num_hashes <= 10);
+            assert_eq!(q.from_block, 10);
+            assert!(q.receipts.iter().all(|s| !s.receipt_type.is_empty()));
+            assert_eq!(
+                q.field_selection.receipt,
+                ["block_height", "data", "receipt_index", "tx_id"]
+                    .map(String::from)
+                    .into()
+            );
+            assert!(q.field_selection.block.is_empty());
+            root_contract_ids.extend(q.receipts.iter().flat_map(|s| s.root_contract_id.clone()));
+        }
+
+        let expected = ids(0..25).into_iter().map(Hash::from).collect::<Vec<_>>();
+        assert_eq!(root_contract_ids, expected);
+        // the selection without hashes is sent once
+        let mints = sharded
+            .queries
+            .iter()
+            .flat_map(|q| q.receipts.iter())
+            .filter(|s| s.root_contract_id.is_empty())
+            .count();
+        assert_eq!(mints, 1);
+        assert_eq!(
+            sharded.added[2],
+            ["block_height", "receipt_index", "tx_id"]
+                .map(String::from)
+                .into()
+        );
+    }
+
+    #[test]
+    fn test_split_selection() {
+        let split = |num_owners: u8| {
+            let selection = InputSelection::default()
+                .owner(ids(0..num_owners))
+                .asset_id(ids(200..202));
+            let mut pieces = Vec::new();
+            split_selection(selection, 5, &mut pieces);
+            pieces
+        };
+
+        let pieces = split(30);
+        assert!(pieces.iter().all(|p| p.num_hashes() <= 5));
+        let hashes = |range| ids(range).into_iter().map(Hash::from).collect::<Vec<_>>();
+        assert!(pieces.iter().all(|p| p.asset_id == hashes(200..202)));
+        let owners = pieces
+            .iter()
+            .flat_map(|p| p.owner.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(owners, hashes(0..30));
+        // chunks of 3 owners
+        assert_eq!(pieces.len(), 10);
+        assert_eq!(split(60).len(), 20);
+    }
+
+    #[test]
+    fn test_split_selection_many_lists() {
+        let selection = InputSelection::default()
+            .owner(ids(0..4))
+            .asset_id(ids(4..8))
+            .contract(ids(8..12));
+
+        let mut pieces = Vec::new();
+        split_selection(selection, 2, &mut pieces);
+
+        // the other lists alone are too big, so the longest is split into single hashes
+        assert_eq!(pieces.len(), 4);
+        assert!(pieces.iter().all(|p| p.num_hashes() == 9));
+    }
+
+    /// Receipts as `(block_height, tx_id, receipt_index)`, the data is the index plus 10 times
+    /// the tx id.
+    fn receipts(rows: &[(u64, u8, u64)]) -> ArrowResponse {
+        let batch = ArrowBatch {
+            chunk: Arc::new(ArrowChunk::new(vec![
+                UInt64Array::from_iter(rows.iter().map(|r| Some(r.0))).boxed(),
+                BinaryViewArray::arr_from_iter(rows.iter().map(|r| Some(vec![r.1; 32]))).boxed(),
+                UInt64Array::from_iter(rows.iter().map(|r| Some(r.2))).boxed(),
+                BinaryViewArray::arr_from_iter(
+                    rows.iter().map(|r| Some(vec![r.2 as u8 + 10 * r.1])),
+                )
+                .boxed(),
+            ])),
+            schema: Arc::new(Schema::from(vec![
+                Field::new("block_height", DataType::UInt64, false),
+                Field::new("tx_id", DataType::BinaryView, false),
+                Field::new("receipt_index", DataType::UInt64, false),
+                Field::new("data", DataType::BinaryView, true),
+            ])),
+        };
+
+        ArrowResponse {
+            archive_height: Some(100),
+            next_block: 0,
+            total_execution_time: 5,
+            data: ArrowResponseData {
+                receipts: vec![batch],
+                ..Default::default()
+            },
+        }
+    }
+
+    fn sharded_receipts() -> ShardedQuery {
+        ShardedQuery {
+            queries: Vec::new(),
+            added: [
+                BTreeSet::new(),
+                BTreeSet::new(),
+                ["receipt_index".to_owned(), "tx_id".to_owned()].into(),
+                BTreeSet::new(),
+                BTreeSet::new(),
+            ],
+        }
+    }
+
+    fn receipt_data(res: &ArrowResponse) -> Vec<u8> {
+        let data = res.data.receipts[0]
+            .column::<BinaryViewArray>("data")
+            .unwrap();
+        data.values_iter().map(|d| d[0]).collect()
+    }
+
+    #[test]
+    fn test_merge() {
+        let res = sharded_receipts()
+            .merge(
+                vec![
+                    vec![receipts(&[(1, 0, 0), (1, 0, 3), (4, 0, 1)])],
+                    vec![
+                        receipts(&[(1, 0, 1), (1, 0, 3)]),
+                        receipts(&[(2, 0, 0), (4, 0, 1)]),
+                    ],
+                ],
+                5,
+            )
+            .unwrap();
+
+        assert_eq!(res.next_block, 5);
+        assert_eq!(res.archive_height, Some(100));
+        assert_eq!(res.total_execution_time, 15);
+        assert!(res.data.blocks.is_empty());
+
+        let batch = &res.data.receipts[0];
+        assert_eq!(
+            batch
+                .schema
+                .fields
+                .iter()
+                .map(|f| f.name.as_str())
+                .collect::<Vec<_>>(),
+            ["block_height", "data"]
+        );
+        let heights = batch.column::<UInt64Array>("block_height").unwrap();
+        assert_eq!(
+            heights.values_iter().copied().collect::<Vec<_>>(),
+            [1, 1, 1, 2, 4]
+        );
+        assert_eq!(receipt_data(&res), [0, 1, 3, 0, 1]);
+    }
+
+    #[test]
+    fn test_merge_receipts_of_several_transactions() {
+        // both transactions of block 1 have a receipt with index 0
+        let res = sharded_receipts()
+            .merge(
+                vec![
+                    vec![receipts(&[(1, 2, 0), (1, 2, 1), (3, 1, 0)])],
+                    vec![receipts(&[(1, 1, 0), (1, 2, 0), (3, 1, 0)])],
+                ],
+                5,
+            )
+            .unwrap();
+
+        assert_eq!(receipt_data(&res), [10, 20, 21, 10]);
+    }
+
+    #[test]
+    fn test_merge_same_looking_rows() {
+        // outputs without an index look the same, each shard returns both
+        let outputs = |n: usize| {
+            let col = |v: u64| UInt64Array::from_vec(vec![v; n]).boxed();
+            let bin =
+                || BinaryViewArray::arr_from_iter(std::iter::repeat_n(Some(vec![7u8]), n)).boxed();
+            let fields = OUTPUTS
+                .key
+                .iter()
+                .map(|name| {
+                    let dt = match *name {
+                        "block_height" | "amount" | "input_index" => DataType::UInt64,
+                        "output_type" => DataType::UInt8,
+                        _ => DataType::BinaryView,
+                    };
+                    Field::new(name.to_string(), dt, true)
+                })
+                .collect::<Vec<_>>();
+            let cols = fields
+                .iter()
+                .map(|f| match f.data_type() {
+                    DataType::UInt64 => col(3),
+                    DataType::UInt8 => UInt8Array::from_vec(vec![0; n]).boxed(),
+                    _ => bin(),
+                })
+                .collect();
+            vec![ArrowBatch {
+                chunk: Arc::new(ArrowChunk::new(cols)),
+                schema: Arc::new(Schema::from(fields)),
+            }]
+        };
+
+        let merged = merge_table(&OUTPUTS, vec![outputs(2), outputs(2)], &BTreeSet::new()).unwrap();
+        assert_eq!(merged[0].chunk.len(), 2);
+        assert!(merge_table(&OUTPUTS, vec![Vec::new()], &BTreeSet::new())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_shard_query_key_fields() {
+        let query = Query {
+            inputs: vec![InputSelection::default().owner(ids(0..5))],
+            field_selection: FieldSelection {
+                input: ["amount".to_owned()].into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let sharded = shard_query(&query, 2).unwrap();
+        assert_eq!(sharded.queries.len(), 3);
+        assert!(sharded.added[3].contains("utxo_id"));
+        assert!(sharded.added[4].is_empty());
+    }
+}
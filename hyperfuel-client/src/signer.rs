@@ -0,0 +1,128 @@
+use anyhow::{Context, Result};
+use hyperfuel_format::{parse_witnesses, recover_signer, Hash};
+use polars_arrow::array::{ArrayFromIter, BinaryArray, BinaryViewArray};
+use polars_arrow::datatypes::{ArrowSchema as Schema, Field};
+
+use crate::{
+    util::{find_column, BinaryColumn},
+    ArrowBatch, ArrowChunk,
+};
+
+/// Appends a `signer` column to a transactions batch, the address recovered from the first
+/// witness that is a valid signature of the transaction id. See [`Transaction::signers`].
+///
+/// The batch has to have the `id` and `witnesses` columns. The signer is null for transactions
+/// without a signature, like mints or transactions that only spend predicates. It is also null
+/// if the witnesses of a transaction can't be parsed, which is logged as a warning.
+///
+/// The columns can be `Binary` or `BinaryView`, the `signer` column has the type of the `id`
+/// column.
+///
+/// [`Transaction::signers`]: hyperfuel_format::Transaction::signers
+pub fn add_signer_column(batch: &ArrowBatch) -> Result<ArrowBatch> {
+    let column = |name: &str| {
+        find_column(batch, name)
+            .with_context(|| format!("get {name} column"))
+            .and_then(|col| BinaryColumn::new(col, name))
+    };
+    let ids = column("id")?;
+    let witnesses = column("witnesses")?;
+
+    let mut signers = Vec::with_capacity(ids.len());
+    for (id, witnesses) in ids.iter().zip(witnesses.iter()) {
+        let signer = match (id, witnesses) {
+            (Some(id), Some(witnesses)) => {
+                let id = Hash::try_from(id).context("parse transaction id")?;
+                match parse_witnesses(witnesses)
+                    .with_context(|| format!("parse witnesses of transaction {id:?}"))
+                {
+                    Ok(witnesses) => witnesses
+                        .iter()
+                        .find_map(|witness| recover_signer(witness.as_ref(), &id).ok()),
+                    Err(e) => {
+                        log::warn!("{:?}", e);
+                        None
+                    }
+                }
+            }
+            _ => None,
+        };
+        signers.push(signer);
+    }
+
+    let signers = signers.iter().map(|s| s.as_ref().map(|s| s.as_slice()));
+    let signers = match ids {
+        BinaryColumn::Binary(_) => BinaryArray::<i32>::from_iter(signers).boxed(),
+        BinaryColumn::View(_) => BinaryViewArray::arr_from_iter(signers).boxed(),
+    };
+
+    let mut fields = batch.schema.fields.clone();
+    fields.push(Field::new("signer", signers.data_type().clone(), true));
+    let mut cols = batch.chunk.columns().to_vec();
+    cols.push(signers);
+
+    Ok(ArrowBatch {
+        chunk: ArrowChunk::new(cols).into(),
+        schema: Schema::from(fields).into(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::map_batch_to_binary_view;
+    use k256::ecdsa::SigningKey;
+    use polars_arrow::datatypes::ArrowDataType as DataType;
+
+    fn transactions() -> ArrowBatch {
+        let id = [0x42; 32];
+        let key = SigningKey::from_slice(&[0x11; 32]).unwrap();
+        let (sig, recovery_id) = key.sign_prehash_recoverable(&id).unwrap();
+        let mut sig: [u8; 64] = sig.to_bytes().into();
+        sig[32] |= recovery_id.to_byte() << 7;
+
+        let mut witnesses = 64u64.to_be_bytes().to_vec();
+        witnesses.extend_from_slice(&sig);
+
+        ArrowBatch {
+            chunk: ArrowChunk::new(vec![
+                BinaryArray::<i32>::from([Some(&id[..]), Some(&id[..]), Some(&id[..])]).boxed(),
+                // the last witnesses are truncated.
+                BinaryArray::<i32>::from([Some(&witnesses[..]), None, Some(&witnesses[..20])])
+                    .boxed(),
+            ])
+            .into(),
+            schema: Schema::from(vec![
+                Field::new("id", DataType::Binary, false),
+                Field::new("witnesses", DataType::Binary, true),
+            ])
+            .into(),
+        }
+    }
+
+    fn check_signers(batch: &ArrowBatch) {
+        let signers = BinaryColumn::new(find_column(batch, "signer").unwrap(), "signer").unwrap();
+
+        // sha256 of the uncompressed public key of the secret key 0x11..11
+        let expected =
+            hex_literal::hex!("b4e84a53cd74a3ed9bf15e1edd92c2a69e567462c58aa8265f89d24c2204e1d7");
+        assert_eq!(
+            signers.iter().collect::<Vec<_>>(),
+            [Some(&expected[..]), None, None]
+        );
+    }
+
+    #[test]
+    fn test_add_signer_column() {
+        let batch = add_signer_column(&transactions()).unwrap();
+        assert!(batch.column::<BinaryArray<i32>>("signer").is_ok());
+        check_signers(&batch);
+    }
+
+    #[test]
+    fn test_add_signer_column_binary_view() {
+        let batch = add_signer_column(&map_batch_to_binary_view(transactions())).unwrap();
+        assert!(batch.column::<BinaryViewArray>("signer").is_ok());
+        check_signers(&batch);
+    }
+}
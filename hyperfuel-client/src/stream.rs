@@ -19,10 +19,12 @@ use tokio::task::JoinSet;
 
 use crate::{
     config::HexOutput,
+    from_arrow::FromArrowResponse,
     rayon_async,
-    types::ArrowResponse,
-    util::{hex_encode_batch, hex_encode_prefixed},
-    ArrowBatch, ArrowResponseData, StreamConfig,
+    signer::add_signer_column,
+    types::{ArrowResponse, QueryResponse},
+    util::{checksum_encode_batch, hex_encode_batch, hex_encode_prefixed, take_rows},
+    ArrowBatch, ArrowResponseData, LogDecoder, StreamConfig,
 };
 
 pub async fn stream_arrow(
@@ -38,6 +40,30 @@ pub async fn stream_arrow(
     let response_size_floor = config.response_bytes_floor.unwrap_or(250_000);
     let reverse = config.reverse.unwrap_or_default();
 
+    client.validate_query(&query)?;
+
+    #[allow(deprecated)]
+    if config.event_signature.is_some() {
+        log::warn!("config.event_signature is not read, set config.log_abi to decode logs");
+    }
+
+    let log_decoder = config
+        .log_abi
+        .as_deref()
+        .map(LogDecoder::from_json_abi)
+        .transpose()
+        .context("parse log abi")?
+        .map(Arc::new);
+
+    if config.decode_log_tables.unwrap_or_default() && log_decoder.is_none() {
+        return Err(anyhow!(
+            "config.decode_log_tables requires config.log_abi to be set"
+        ));
+    }
+    if log_decoder.is_some() {
+        check_log_fields(&query)?;
+    }
+
     let step = Arc::new(AtomicU64::new(batch_size));
 
     let (tx, rx) = mpsc::channel(concurrency * 2);
@@ -54,7 +80,14 @@ pub async fn stream_arrow(
             let initial_res = client.get_arrow(&query).await.context("get initial data");
             match initial_res {
                 Ok(res) => {
-                    let res = match map_responses(config.clone(), vec![res], reverse).await {
+                    let res = match map_responses(
+                        config.clone(),
+                        log_decoder.clone(),
+                        vec![res],
+                        reverse,
+                    )
+                    .await
+                    {
                         Ok(mut resps) => resps.remove(0),
                         Err(e) => {
                             tx.send(Err(e)).await.ok();
@@ -152,13 +185,14 @@ pub async fn stream_arrow(
             };
 
             let (resps, resps_size) = resps;
-            let resps = match map_responses(config.clone(), resps, reverse).await {
-                Ok(resps) => resps,
-                Err(e) => {
-                    tx.send(Err(e)).await.ok();
-                    return;
-                }
-            };
+            let resps =
+                match map_responses(config.clone(), log_decoder.clone(), resps, reverse).await {
+                    Ok(resps) => resps,
+                    Err(e) => {
+                        tx.send(Err(e)).await.ok();
+                        return;
+                    }
+                };
 
             if generation == next_generation {
                 next_generation += 1;
@@ -214,6 +248,70 @@ pub async fn stream_arrow(
     Ok(rx)
 }
 
+pub async fn stream<T: FromArrowResponse>(
+    client: Arc<crate::Client>,
+    query: Query,
+    config: StreamConfig,
+) -> Result<mpsc::Receiver<Result<QueryResponse<T>>>> {
+    check_simple_stream_params(&config)?;
+
+    let concurrency = config.concurrency.unwrap_or(10);
+
+    let mut rx = stream_arrow(client, query, config)
+        .await
+        .context("start arrow stream")?;
+
+    let (tx, ret) = mpsc::channel(concurrency * 2);
+
+    tokio::spawn(async move {
+        while let Some(resp) = rx.recv().await {
+            let resp = match resp {
+                Ok(resp) => resp,
+                Err(e) => {
+                    tx.send(Err(e)).await.ok();
+                    return;
+                }
+            };
+
+            let data = match rayon_async::spawn(move || T::from_arrow_response(&resp.data))
+                .await
+                .context("join decode task")
+            {
+                Ok(data) => data,
+                Err(e) => {
+                    tx.send(Err(e)).await.ok();
+                    return;
+                }
+            };
+
+            let resp = QueryResponse {
+                archive_height: resp.archive_height,
+                next_block: resp.next_block,
+                total_execution_time: resp.total_execution_time,
+                data,
+            };
+
+            if tx.send(Ok(resp)).await.is_err() {
+                return;
+            }
+        }
+    });
+
+    Ok(ret)
+}
+
+fn check_simple_stream_params(config: &StreamConfig) -> Result<()> {
+    if config.column_mapping.is_some() {
+        return Err(anyhow!("config.column_mapping can't be passed to single type function. User is expected to map values manually."));
+    }
+
+    if !matches!(config.hex_output, HexOutput::NoEncode) {
+        return Err(anyhow!("config.hex_output can't be passed to single type function. Binary columns have to stay binary to be decoded."));
+    }
+
+    Ok(())
+}
+
 fn count_rows(batches: &[ArrowBatch]) -> usize {
     batches.iter().map(|b| b.chunk.len()).sum()
 }
@@ -228,6 +326,7 @@ fn check_entity_limit(val: usize, limit: Option<usize>) -> bool {
 
 async fn map_responses(
     cfg: StreamConfig,
+    log_decoder: Option<Arc<LogDecoder>>,
     mut responses: Vec<ArrowResponse>,
     reverse: bool,
 ) -> Result<Vec<ArrowResponse>> {
@@ -246,6 +345,14 @@ async fn map_responses(
         responses
             .into_iter()
             .map(|resp| {
+                let decoded_logs = match &log_decoder {
+                    Some(decoder) if cfg.decode_log_tables.unwrap_or_default() => {
+                        map_decoded_logs(decoder, &resp.data.receipts, cfg.hex_output, reverse)
+                            .context("decode log tables")?
+                    }
+                    _ => BTreeMap::new(),
+                };
+
                 Ok(ArrowResponse {
                     data: ArrowResponseData {
                         blocks: resp
@@ -266,6 +373,11 @@ async fn map_responses(
                             .transactions
                             .into_iter()
                             .map(|batch| {
+                                let batch = if cfg.derive_signer.unwrap_or_default() {
+                                    add_signer_column(&batch).context("derive signer")?
+                                } else {
+                                    batch
+                                };
                                 map_batch(
                                     cfg.column_mapping.as_ref().map(|cm| &cm.transaction),
                                     cfg.hex_output,
@@ -279,6 +391,12 @@ async fn map_responses(
                             .receipts
                             .into_iter()
                             .map(|batch| {
+                                let batch = match &log_decoder {
+                                    Some(decoder) => {
+                                        decoder.decode_batch(&batch).context("decode logs")?
+                                    }
+                                    None => batch,
+                                };
                                 map_batch(
                                     cfg.column_mapping.as_ref().map(|cm| &cm.receipt),
                                     cfg.hex_output,
@@ -313,6 +431,7 @@ async fn map_responses(
                                 )
                             })
                             .collect::<Result<Vec<_>>>()?,
+                        decoded_logs,
                     },
                     ..resp
                 })
@@ -320,7 +439,74 @@ async fn map_responses(
             .collect()
     })
     .await
-    .unwrap()
+    .context("join map responses task")?
+}
+
+/// Errors if receipts are selected without the fields that log decoding needs.
+fn check_log_fields(query: &Query) -> Result<()> {
+    let selection = &query.field_selection.receipt;
+    if selection.is_empty() {
+        return Ok(());
+    }
+
+    let missing = LogDecoder::REQUIRED_FIELDS
+        .iter()
+        .filter(|f| !selection.contains(**f))
+        .copied()
+        .collect::<Vec<_>>();
+    if !missing.is_empty() {
+        return Err(anyhow!(
+            "config.log_abi requires the {} receipt fields to be selected",
+            missing.join(", ")
+        ));
+    }
+
+    Ok(())
+}
+
+fn map_decoded_logs(
+    decoder: &LogDecoder,
+    receipts: &[ArrowBatch],
+    hex_output: HexOutput,
+    reverse: bool,
+) -> Result<BTreeMap<String, Vec<ArrowBatch>>> {
+    let mut tables = BTreeMap::<String, Vec<ArrowBatch>>::new();
+
+    for batch in receipts {
+        for (table, mut batch) in decoder.decode_to_tables(batch)? {
+            if reverse {
+                batch = reverse_batch(&batch);
+            }
+
+            match hex_output {
+                HexOutput::NonPrefixed => batch = hex_encode_batch(&batch, faster_hex::hex_string),
+                HexOutput::Prefixed => batch = hex_encode_batch(&batch, hex_encode_prefixed),
+                HexOutput::Checksummed => batch = checksum_encode_batch(&batch),
+                HexOutput::NoEncode => (),
+            }
+
+            tables.entry(table).or_default().push(batch);
+        }
+    }
+
+    Ok(tables)
+}
+
+/// Reverses the rows of a batch, supports columns of any type unlike [`reverse_array`].
+fn reverse_batch(batch: &ArrowBatch) -> ArrowBatch {
+    let indices = (0..batch.chunk.len()).rev().collect::<Vec<_>>();
+
+    let cols = batch
+        .chunk
+        .columns()
+        .iter()
+        .map(|col| take_rows(col.as_ref(), &indices))
+        .collect();
+
+    ArrowBatch {
+        chunk: Arc::new(RecordBatch::new(cols)),
+        schema: batch.schema.clone(),
+    }
 }
 
 fn map_batch(
@@ -352,6 +538,7 @@ fn map_batch(
     match hex_output {
         HexOutput::NonPrefixed => batch = hex_encode_batch(&batch, faster_hex::hex_string),
         HexOutput::Prefixed => batch = hex_encode_batch(&batch, hex_encode_prefixed),
+        HexOutput::Checksummed => batch = checksum_encode_batch(&batch),
         HexOutput::NoEncode => (),
     }
 
@@ -499,3 +686,69 @@ impl Iterator for BlockRangeIterator {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ToArrow;
+    use hyperfuel_format::{Receipt, ReceiptType};
+    use hyperfuel_net_types::FieldSelection;
+
+    #[test]
+    fn test_check_log_fields() {
+        let mut query = Query::default();
+        check_log_fields(&query).unwrap();
+
+        query.field_selection = FieldSelection {
+            receipt: ["tx_id", "rb"].map(String::from).into(),
+            ..Default::default()
+        };
+        let err = check_log_fields(&query).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "config.log_abi requires the receipt_type, data receipt fields to be selected"
+        );
+
+        query
+            .field_selection
+            .receipt
+            .extend(["receipt_type", "data"].map(String::from));
+        check_log_fields(&query).unwrap();
+    }
+
+    #[test]
+    fn test_map_decoded_logs_reverse() {
+        let decoder =
+            LogDecoder::from_json_abi(include_str!("../test-data/logs-abi.json")).unwrap();
+
+        let status = |variant: u64, receipt_index: u64| {
+            let mut data = variant.to_be_bytes().to_vec();
+            if variant == 2 {
+                data.extend(b"oops");
+            }
+            Receipt {
+                receipt_index: receipt_index.into(),
+                receipt_type: ReceiptType::LogData,
+                rb: Some(4363211024530769387.into()),
+                data: Some(data.into()),
+                ..Default::default()
+            }
+        };
+        let receipts = vec![status(0, 0), status(2, 1)];
+        let batch = Receipt::to_arrow(&receipts, None).unwrap();
+
+        let tables = map_decoded_logs(&decoder, &[batch], HexOutput::Prefixed, true).unwrap();
+        let table = &tables["Status"][0];
+
+        let receipt_index = table.column::<UInt64Array>("receipt_index").unwrap();
+        assert_eq!(
+            receipt_index.iter().map(|v| v.copied()).collect::<Vec<_>>(),
+            [Some(1), Some(0)]
+        );
+        let failed = table.column::<Utf8Array<i32>>("value.Failed").unwrap();
+        assert_eq!(failed.iter().collect::<Vec<_>>(), [Some("oops"), None]);
+        // binary columns are hex encoded like the other tables.
+        let tx_id = table.column::<Utf8Array<i32>>("tx_id").unwrap();
+        assert_eq!(tx_id.value(0), format!("0x{}", "00".repeat(32)));
+    }
+}
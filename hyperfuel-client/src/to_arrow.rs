@@ -0,0 +1,516 @@
+use std::collections::BTreeSet;
+
+use anyhow::{anyhow, Context, Result};
+use hyperfuel_format::{BlockHeader, Hash, Input, Output, Receipt, Transaction};
+use hyperfuel_schema::try_project_schema;
+use polars_arrow::array::{Array, BinaryArray, Int64Array, UInt64Array, UInt8Array, Utf8Array};
+use polars_arrow::datatypes::{ArrowSchema as Schema, Field, SchemaRef};
+
+use crate::{util::pack_hash_list, ArrowBatch, ArrowChunk};
+
+/// Used to do Native Rust type-ArrowBatch conversions, the reverse of [`FromArrow`](crate::FromArrow).
+///
+/// Columns are named and ordered as in the matching `hyperfuel_schema` table. Binary and utf8
+/// columns are built as `Binary`/`Utf8` arrays, the same as in server responses, so the output
+/// can be read back with `FromArrow` or written with the parquet writer.
+pub trait ToArrow: Sized {
+    /// Converts the given items to an ArrowBatch.
+    ///
+    /// If `field_selection` is given, only the selected columns are included. Returns an error if
+    /// the selection contains a name that is not in the table schema.
+    fn to_arrow(items: &[Self], field_selection: Option<&BTreeSet<String>>) -> Result<ArrowBatch>;
+}
+
+fn make_batch<T>(
+    items: &[T],
+    schema: SchemaRef,
+    field_selection: Option<&BTreeSet<String>>,
+    make_column: impl Fn(&[T], &str) -> Option<Result<Box<dyn Array>>>,
+) -> Result<ArrowBatch> {
+    let schema = match field_selection {
+        Some(selection) => try_project_schema(&schema, selection).context("project schema")?,
+        None => Schema::clone(&schema),
+    };
+
+    let (fields, cols) = schema
+        .fields
+        .iter()
+        .map(|field| {
+            let col = make_column(items, &field.name)
+                .ok_or_else(|| anyhow!("no conversion for column '{}'", field.name))?
+                .with_context(|| format!("convert column '{}'", field.name))?;
+            Ok((
+                Field::new(
+                    field.name.clone(),
+                    col.data_type().clone(),
+                    field.is_nullable,
+                ),
+                col,
+            ))
+        })
+        .collect::<Result<(Vec<_>, Vec<_>)>>()?;
+
+    Ok(ArrowBatch {
+        chunk: ArrowChunk::new(cols).into(),
+        schema: Schema::from(fields).into(),
+    })
+}
+
+/// A converted column, `None` if there is no conversion for the column name.
+type Column = Option<Result<Box<dyn Array>>>;
+
+fn binary<T>(items: &[T], f: impl for<'a> Fn(&'a T) -> Option<&'a [u8]>) -> Column {
+    Some(Ok(
+        BinaryArray::<i32>::from_iter(items.iter().map(f)).boxed()
+    ))
+}
+
+fn uint64<T>(items: &[T], f: impl Fn(&T) -> Option<u64>) -> Column {
+    Some(Ok(UInt64Array::from_iter(items.iter().map(f)).boxed()))
+}
+
+fn try_uint64<T>(items: &[T], f: impl Fn(&T) -> Result<u64>) -> Column {
+    Some(
+        items
+            .iter()
+            .map(|i| f(i).map(Some))
+            .collect::<Result<Vec<_>>>()
+            .map(|values| UInt64Array::from_iter(values).boxed()),
+    )
+}
+
+fn try_int64<T>(items: &[T], f: impl Fn(&T) -> Result<i64>) -> Column {
+    Some(
+        items
+            .iter()
+            .map(|i| f(i).map(Some))
+            .collect::<Result<Vec<_>>>()
+            .map(|values| Int64Array::from_iter(values).boxed()),
+    )
+}
+
+fn uint8<T>(items: &[T], f: impl Fn(&T) -> u8) -> Column {
+    Some(Ok(
+        UInt8Array::from_iter(items.iter().map(|i| Some(f(i)))).boxed()
+    ))
+}
+
+fn utf8<T>(items: &[T], f: impl for<'a> Fn(&'a T) -> Option<&'a str>) -> Column {
+    Some(Ok(Utf8Array::<i32>::from_iter(items.iter().map(f)).boxed()))
+}
+
+fn hash_list<T>(items: &[T], f: impl Fn(&T) -> Option<&[Hash]>) -> Column {
+    Some(Ok(BinaryArray::<i32>::from_iter(
+        items.iter().map(|i| f(i).map(pack_hash_list)),
+    )
+    .boxed()))
+}
+
+impl ToArrow for BlockHeader {
+    fn to_arrow(items: &[Self], field_selection: Option<&BTreeSet<String>>) -> Result<ArrowBatch> {
+        make_batch(
+            items,
+            hyperfuel_schema::block_header(),
+            field_selection,
+            |items, name| match name {
+                "id" => binary(items, |b| Some(b.id.as_ref())),
+                "da_height" => uint64(items, |b| Some(*b.da_height)),
+                "consensus_parameters_version" => {
+                    uint64(items, |b| Some(*b.consensus_parameters_version))
+                }
+                "state_transition_bytecode_version" => {
+                    uint64(items, |b| Some(*b.state_transition_bytecode_version))
+                }
+                "transactions_count" => {
+                    try_uint64(items, |b| Ok((&b.transactions_count).try_into()?))
+                }
+                "message_receipt_count" => {
+                    try_uint64(items, |b| Ok((&b.message_receipt_count).try_into()?))
+                }
+                "transactions_root" => binary(items, |b| Some(b.transactions_root.as_ref())),
+                "message_outbox_root" => binary(items, |b| Some(b.message_outbox_root.as_ref())),
+                "event_inbox_root" => binary(items, |b| Some(b.event_inbox_root.as_ref())),
+                "height" => uint64(items, |b| Some(*b.height)),
+                "prev_root" => binary(items, |b| Some(b.prev_root.as_ref())),
+                "time" => try_int64(items, |b| Ok(i64::try_from(*b.time)?)),
+                "application_hash" => binary(items, |b| Some(b.application_hash.as_ref())),
+                _ => None,
+            },
+        )
+    }
+}
+
+impl ToArrow for Transaction {
+    fn to_arrow(items: &[Self], field_selection: Option<&BTreeSet<String>>) -> Result<ArrowBatch> {
+        make_batch(
+            items,
+            hyperfuel_schema::transaction(),
+            field_selection,
+            |items, name| match name {
+                "block_height" => uint64(items, |t| Some(*t.block_height)),
+                "id" => binary(items, |t| Some(t.id.as_ref())),
+                "input_asset_ids" => hash_list(items, |t| t.input_asset_ids.as_deref()),
+                "input_contracts" => hash_list(items, |t| t.input_contracts.as_deref()),
+                "input_contract_utxo_id" => binary(items, |t| {
+                    t.input_contract_utxo_id.as_ref().map(|v| v.as_ref())
+                }),
+                "input_contract_balance_root" => binary(items, |t| {
+                    t.input_contract_balance_root.as_ref().map(|v| v.as_ref())
+                }),
+                "input_contract_state_root" => binary(items, |t| {
+                    t.input_contract_state_root.as_ref().map(|v| v.as_ref())
+                }),
+                "input_contract_tx_pointer_block_height" => uint64(items, |t| {
+                    t.input_contract_tx_pointer_block_height.map(|v| *v)
+                }),
+                "input_contract_tx_pointer_tx_index" => {
+                    uint64(items, |t| t.input_contract_tx_pointer_tx_index.map(|v| *v))
+                }
+                "input_contract" => {
+                    binary(items, |t| t.input_contract.as_ref().map(|v| v.as_ref()))
+                }
+                "policies_tip" => uint64(items, |t| t.policies_tip.map(|v| *v)),
+                "policies_witness_limit" => uint64(items, |t| t.policies_witness_limit.map(|v| *v)),
+                "policies_maturity" => uint64(items, |t| t.policies_maturity.map(|v| *v)),
+                "policies_max_fee" => uint64(items, |t| t.policies_max_fee.map(|v| *v)),
+                "script_gas_limit" => uint64(items, |t| t.script_gas_limit.map(|v| *v)),
+                "maturity" => uint64(items, |t| t.maturity.map(|v| *v)),
+                "mint_amount" => uint64(items, |t| t.mint_amount.map(|v| *v)),
+                "mint_asset_id" => binary(items, |t| t.mint_asset_id.as_ref().map(|v| v.as_ref())),
+                "mint_gas_price" => uint64(items, |t| t.mint_gas_price.map(|v| *v)),
+                "tx_pointer_block_height" => {
+                    uint64(items, |t| t.tx_pointer_block_height.map(|v| *v))
+                }
+                "tx_pointer_tx_index" => uint64(items, |t| t.tx_pointer_tx_index.map(|v| *v)),
+                "tx_type" => uint8(items, |t| t.tx_type.to_u8()),
+                "output_contract_input_index" => {
+                    uint64(items, |t| t.output_contract_input_index.map(|v| *v))
+                }
+                "output_contract_balance_root" => binary(items, |t| {
+                    t.output_contract_balance_root.as_ref().map(|v| v.as_ref())
+                }),
+                "output_contract_state_root" => binary(items, |t| {
+                    t.output_contract_state_root.as_ref().map(|v| v.as_ref())
+                }),
+                "witnesses" => binary(items, |t| t.witnesses.as_ref().map(|v| v.as_ref())),
+                "receipts_root" => binary(items, |t| t.receipts_root.as_ref().map(|v| v.as_ref())),
+                "status" => uint8(items, |t| t.status.to_u8()),
+                "time" => try_int64(items, |t| Ok(i64::try_from(*t.time)?)),
+                "reason" => utf8(items, |t| t.reason.as_deref()),
+                "script" => binary(items, |t| t.script.as_ref().map(|v| v.as_ref())),
+                "script_data" => binary(items, |t| t.script_data.as_ref().map(|v| v.as_ref())),
+                "bytecode_witness_index" => uint64(items, |t| t.bytecode_witness_index.map(|v| *v)),
+                "bytecode_root" => binary(items, |t| t.bytecode_root.as_ref().map(|v| v.as_ref())),
+                "subsection_index" => uint64(items, |t| t.subsection_index.map(|v| *v)),
+                "subsections_number" => uint64(items, |t| t.subsections_number.map(|v| *v)),
+                "proof_set" => binary(items, |t| t.proof_set.as_ref().map(|v| v.as_ref())),
+                "consensus_parameters_upgrade_purpose_witness_index" => uint64(items, |t| {
+                    t.consensus_parameters_upgrade_purpose_witness_index
+                        .map(|v| *v)
+                }),
+                "consensus_parameters_upgrade_purpose_checksum" => binary(items, |t| {
+                    t.consensus_parameters_upgrade_purpose_checksum
+                        .as_ref()
+                        .map(|v| v.as_ref())
+                }),
+                "state_transition_upgrade_purpose_root" => binary(items, |t| {
+                    t.state_transition_upgrade_purpose_root
+                        .as_ref()
+                        .map(|v| v.as_ref())
+                }),
+                "salt" => binary(items, |t| t.salt.as_ref().map(|v| v.as_ref())),
+                _ => None,
+            },
+        )
+    }
+}
+
+impl ToArrow for Receipt {
+    fn to_arrow(items: &[Self], field_selection: Option<&BTreeSet<String>>) -> Result<ArrowBatch> {
+        make_batch(
+            items,
+            hyperfuel_schema::receipt(),
+            field_selection,
+            |items, name| match name {
+                "receipt_index" => uint64(items, |r| Some(*r.receipt_index)),
+                "root_contract_id" => {
+                    binary(items, |r| r.root_contract_id.as_ref().map(|v| v.as_ref()))
+                }
+                "tx_id" => binary(items, |r| Some(r.tx_id.as_ref())),
+                "tx_status" => uint8(items, |r| r.tx_status.to_u8()),
+                "tx_type" => uint8(items, |r| r.tx_type.to_u8()),
+                "block_height" => uint64(items, |r| Some(*r.block_height)),
+                "pc" => uint64(items, |r| r.pc.map(|v| *v)),
+                "is" => uint64(items, |r| r.is.map(|v| *v)),
+                "to" => binary(items, |r| r.to.as_ref().map(|v| v.as_ref())),
+                "to_address" => binary(items, |r| r.to_address.as_ref().map(|v| v.as_ref())),
+                "amount" => uint64(items, |r| r.amount.map(|v| *v)),
+                "asset_id" => binary(items, |r| r.asset_id.as_ref().map(|v| v.as_ref())),
+                "gas" => uint64(items, |r| r.gas.map(|v| *v)),
+                "param1" => uint64(items, |r| r.param1.map(|v| *v)),
+                "param2" => uint64(items, |r| r.param2.map(|v| *v)),
+                "val" => uint64(items, |r| r.val.map(|v| *v)),
+                "ptr" => uint64(items, |r| r.ptr.map(|v| *v)),
+                "digest" => binary(items, |r| r.digest.as_ref().map(|v| v.as_ref())),
+                "reason" => uint64(items, |r| r.reason.map(|v| *v)),
+                "ra" => uint64(items, |r| r.ra.map(|v| *v)),
+                "rb" => uint64(items, |r| r.rb.map(|v| *v)),
+                "rc" => uint64(items, |r| r.rc.map(|v| *v)),
+                "rd" => uint64(items, |r| r.rd.map(|v| *v)),
+                "len" => uint64(items, |r| r.len.map(|v| *v)),
+                "receipt_type" => uint8(items, |r| r.receipt_type.to_u8()),
+                "result" => uint64(items, |r| r.result.map(|v| *v)),
+                "gas_used" => uint64(items, |r| r.gas_used.map(|v| *v)),
+                "data" => binary(items, |r| r.data.as_ref().map(|v| v.as_ref())),
+                "sender" => binary(items, |r| r.sender.as_ref().map(|v| v.as_ref())),
+                "recipient" => binary(items, |r| r.recipient.as_ref().map(|v| v.as_ref())),
+                "nonce" => binary(items, |r| r.nonce.as_ref().map(|v| v.as_ref())),
+                "contract_id" => binary(items, |r| r.contract_id.as_ref().map(|v| v.as_ref())),
+                "sub_id" => binary(items, |r| r.sub_id.as_ref().map(|v| v.as_ref())),
+                _ => None,
+            },
+        )
+    }
+}
+
+impl ToArrow for Input {
+    fn to_arrow(items: &[Self], field_selection: Option<&BTreeSet<String>>) -> Result<ArrowBatch> {
+        make_batch(
+            items,
+            hyperfuel_schema::input(),
+            field_selection,
+            |items, name| match name {
+                "tx_id" => binary(items, |i| Some(i.tx_id.as_ref())),
+                "tx_status" => uint8(items, |i| i.tx_status.to_u8()),
+                "tx_type" => uint8(items, |i| i.tx_type.to_u8()),
+                "block_height" => uint64(items, |i| Some(*i.block_height)),
+                "input_type" => uint8(items, |i| i.input_type.as_u8()),
+                "utxo_id" => binary(items, |i| i.utxo_id.as_ref().map(|v| v.as_ref())),
+                "owner" => binary(items, |i| i.owner.as_ref().map(|v| v.as_ref())),
+                "amount" => uint64(items, |i| i.amount.map(|v| *v)),
+                "asset_id" => binary(items, |i| i.asset_id.as_ref().map(|v| v.as_ref())),
+                "tx_pointer_block_height" => {
+                    uint64(items, |i| i.tx_pointer_block_height.map(|v| *v))
+                }
+                "tx_pointer_tx_index" => uint64(items, |i| i.tx_pointer_tx_index.map(|v| *v)),
+                "witness_index" => uint64(items, |i| i.witness_index.map(|v| *v)),
+                "predicate_gas_used" => uint64(items, |i| i.predicate_gas_used.map(|v| *v)),
+                "predicate" => binary(items, |i| i.predicate.as_ref().map(|v| v.as_ref())),
+                "predicate_data" => {
+                    binary(items, |i| i.predicate_data.as_ref().map(|v| v.as_ref()))
+                }
+                "balance_root" => binary(items, |i| i.balance_root.as_ref().map(|v| v.as_ref())),
+                "state_root" => binary(items, |i| i.state_root.as_ref().map(|v| v.as_ref())),
+                "contract" => binary(items, |i| i.contract.as_ref().map(|v| v.as_ref())),
+                "sender" => binary(items, |i| i.sender.as_ref().map(|v| v.as_ref())),
+                "recipient" => binary(items, |i| i.recipient.as_ref().map(|v| v.as_ref())),
+                "nonce" => binary(items, |i| i.nonce.as_ref().map(|v| v.as_ref())),
+                "data" => binary(items, |i| i.data.as_ref().map(|v| v.as_ref())),
+                _ => None,
+            },
+        )
+    }
+}
+
+impl ToArrow for Output {
+    fn to_arrow(items: &[Self], field_selection: Option<&BTreeSet<String>>) -> Result<ArrowBatch> {
+        make_batch(
+            items,
+            hyperfuel_schema::output(),
+            field_selection,
+            |items, name| match name {
+                "tx_id" => binary(items, |o| Some(o.tx_id.as_ref())),
+                "tx_status" => uint8(items, |o| o.tx_status.to_u8()),
+                "tx_type" => uint8(items, |o| o.tx_type.to_u8()),
+                "block_height" => uint64(items, |o| Some(*o.block_height)),
+                "output_type" => uint8(items, |o| o.output_type.as_u8()),
+                "to" => binary(items, |o| o.to.as_ref().map(|v| v.as_ref())),
+                "amount" => uint64(items, |o| o.amount.map(|v| *v)),
+                "asset_id" => binary(items, |o| o.asset_id.as_ref().map(|v| v.as_ref())),
+                "input_index" => uint64(items, |o| o.input_index.map(|v| *v)),
+                "balance_root" => binary(items, |o| o.balance_root.as_ref().map(|v| v.as_ref())),
+                "state_root" => binary(items, |o| o.state_root.as_ref().map(|v| v.as_ref())),
+                "contract" => binary(items, |o| o.contract.as_ref().map(|v| v.as_ref())),
+                _ => None,
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{util::map_batch_to_binary_view, FromArrow};
+    use hyperfuel_format::{ReceiptType, TransactionStatus, TransactionType};
+
+    fn receipts() -> Vec<Receipt> {
+        vec![
+            Receipt {
+                receipt_index: 0.into(),
+                root_contract_id: Some([1; 32].into()),
+                tx_id: [2; 32].into(),
+                tx_status: TransactionStatus::Success,
+                tx_type: TransactionType::from(0),
+                block_height: 10.into(),
+                receipt_type: ReceiptType::LogData,
+                ra: Some(0.into()),
+                rb: Some(42.into()),
+                data: Some(vec![1, 2, 3].into()),
+                ..Default::default()
+            },
+            Receipt {
+                receipt_index: 1.into(),
+                tx_id: [3; 32].into(),
+                tx_status: TransactionStatus::Failure,
+                tx_type: TransactionType::from(0),
+                block_height: 11.into(),
+                receipt_type: ReceiptType::Call,
+                amount: Some(100.into()),
+                ..Default::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn test_receipt_round_trip() {
+        let receipts = receipts();
+        let batch = Receipt::to_arrow(&receipts, None).unwrap();
+
+        assert_eq!(
+            batch.schema.fields.len(),
+            hyperfuel_schema::receipt().fields.len()
+        );
+        assert_eq!(Receipt::from_arrow(&batch), receipts);
+    }
+
+    #[test]
+    fn test_projection() {
+        let selection = ["tx_id", "receipt_type", "data"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let batch = Receipt::to_arrow(&receipts(), Some(&selection)).unwrap();
+
+        let names = batch
+            .schema
+            .fields
+            .iter()
+            .map(|f| f.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["tx_id", "receipt_type", "data"]);
+        assert_eq!(batch.chunk.len(), 2);
+    }
+
+    #[test]
+    fn test_unknown_column() {
+        let selection = ["tx_idd".to_owned()].into_iter().collect();
+        assert!(Receipt::to_arrow(&receipts(), Some(&selection)).is_err());
+    }
+
+    #[test]
+    fn test_transaction_round_trip() {
+        let txs = vec![Transaction {
+            block_height: 5.into(),
+            id: [7; 32].into(),
+            input_asset_ids: Some(vec![[8; 32].into(), [9; 32].into()]),
+            tx_type: TransactionType::from(0),
+            status: TransactionStatus::Success,
+            time: 1_700_000_000.into(),
+            reason: Some("ok".to_owned()),
+            script: Some(vec![0x24, 0x04, 0x00, 0x00].into()),
+            ..Default::default()
+        }];
+        let batch = Transaction::to_arrow(&txs, None).unwrap();
+
+        assert_eq!(Transaction::from_arrow(&batch), txs);
+        let asset_ids = batch.column::<BinaryArray<i32>>("input_asset_ids").unwrap();
+        assert_eq!(
+            asset_ids.value(0),
+            bincode::serialize(txs[0].input_asset_ids.as_ref().unwrap()).unwrap()
+        );
+        assert_eq!(
+            batch.column::<Int64Array>("time").unwrap().value(0),
+            1_700_000_000
+        );
+    }
+
+    #[test]
+    fn test_schema_types() {
+        // binary and utf8 columns are built like the server sends them, other types match the
+        // schema as is.
+        let check = |batch: ArrowBatch, schema: SchemaRef| {
+            let batch = map_batch_to_binary_view(batch);
+            for (field, expected) in batch.schema.fields.iter().zip(schema.fields.iter()) {
+                assert_eq!(field.name, expected.name);
+                assert_eq!(field.data_type, expected.data_type, "{}", field.name);
+            }
+        };
+
+        check(
+            BlockHeader::to_arrow(&[BlockHeader::default()], None).unwrap(),
+            hyperfuel_schema::block_header(),
+        );
+        check(
+            Transaction::to_arrow(&[Transaction::default()], None).unwrap(),
+            hyperfuel_schema::transaction(),
+        );
+        check(
+            Receipt::to_arrow(&receipts(), None).unwrap(),
+            hyperfuel_schema::receipt(),
+        );
+        check(
+            Input::to_arrow(&[Input::default()], None).unwrap(),
+            hyperfuel_schema::input(),
+        );
+        check(
+            Output::to_arrow(&[Output::default()], None).unwrap(),
+            hyperfuel_schema::output(),
+        );
+    }
+
+    #[test]
+    fn test_time_overflow() {
+        let blocks = vec![BlockHeader {
+            time: u64::MAX.into(),
+            ..Default::default()
+        }];
+        let err = BlockHeader::to_arrow(&blocks, None).unwrap_err();
+        assert!(format!("{err:#}").contains("time"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Construct from_arrow input_asset_ids")]
+    fn test_malformed_hash_list() {
+        let batch = Transaction::to_arrow(&[Transaction::default()], None).unwrap();
+        let idx = batch
+            .schema
+            .fields
+            .iter()
+            .position(|f| f.name == "input_asset_ids")
+            .unwrap();
+        let mut cols = batch.chunk.columns().to_vec();
+        cols[idx] = BinaryArray::<i32>::from_slice([[1; 32]]).boxed();
+        let batch = ArrowBatch {
+            chunk: ArrowChunk::new(cols).into(),
+            schema: batch.schema,
+        };
+
+        Transaction::from_arrow(&batch);
+    }
+
+    #[test]
+    fn test_block_quantity_overflow() {
+        let mut blocks = vec![BlockHeader {
+            transactions_count: 3u64.into(),
+            ..Default::default()
+        }];
+        let selection = ["transactions_count".to_owned()].into_iter().collect();
+        let batch = BlockHeader::to_arrow(&blocks, Some(&selection)).unwrap();
+        let col = batch.column::<UInt64Array>("transactions_count").unwrap();
+        assert_eq!(col.value(0), 3);
+
+        blocks[0].transactions_count = [1; 9].into();
+        let err = BlockHeader::to_arrow(&blocks, Some(&selection)).unwrap_err();
+        assert!(format!("{err:#}").contains("transactions_count"));
+    }
+}
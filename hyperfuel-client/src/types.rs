@@ -1,6 +1,9 @@
-use std::sync::Arc;
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
 
-use crate::{ArrowChunk, FromArrow};
+use crate::{from_arrow::FromArrowResponse, ArrowChunk, FromArrow};
 use anyhow::{anyhow, Context, Result};
 use hyperfuel_format::{
     BlockHeader, Data, Hash, Input, Output, Receipt, ReceiptType, Transaction, UInt,
@@ -20,6 +23,9 @@ pub struct ArrowResponseData {
     pub inputs: Vec<ArrowBatch>,
     /// Query outputs response
     pub outputs: Vec<ArrowBatch>,
+    /// Decoded logs tables, keyed by table name. Only populated by streams with
+    /// `StreamConfig::decode_log_tables` set.
+    pub decoded_logs: BTreeMap<String, Vec<ArrowBatch>>,
 }
 
 /// Query response data in Rust native format
@@ -37,50 +43,13 @@ pub struct ResponseData {
     pub outputs: Vec<Vec<Output>>,
 }
 
-impl From<&'_ ArrowResponse> for QueryResponse {
+impl<T: FromArrowResponse> From<&'_ ArrowResponse> for QueryResponse<T> {
     fn from(arrow_response: &ArrowResponse) -> Self {
-        let blocks = arrow_response
-            .data
-            .blocks
-            .iter()
-            .map(BlockHeader::from_arrow)
-            .collect();
-        let transactions = arrow_response
-            .data
-            .transactions
-            .iter()
-            .map(Transaction::from_arrow)
-            .collect();
-        let receipts = arrow_response
-            .data
-            .receipts
-            .iter()
-            .map(Receipt::from_arrow)
-            .collect();
-        let inputs = arrow_response
-            .data
-            .inputs
-            .iter()
-            .map(Input::from_arrow)
-            .collect();
-        let outputs = arrow_response
-            .data
-            .outputs
-            .iter()
-            .map(Output::from_arrow)
-            .collect();
-
         QueryResponse {
             archive_height: arrow_response.archive_height,
             next_block: arrow_response.next_block,
             total_execution_time: arrow_response.total_execution_time,
-            data: ResponseData {
-                blocks,
-                transactions,
-                receipts,
-                inputs,
-                outputs,
-            },
+            data: T::from_arrow_response(&arrow_response.data),
             // rollback_guard: arrow_response.rollback_guard.clone(),
         }
     }
@@ -107,6 +76,102 @@ pub struct QueryResponse<T = ResponseData> {
 /// Alias for Arrow Query response
 pub type ArrowResponse = QueryResponse<ArrowResponseData>;
 
+/// Alias for event based Query response
+pub type EventResponse = QueryResponse<Vec<Event>>;
+
+/// A receipt, input or output joined with the transaction and block it belongs to.
+#[derive(Debug, Clone)]
+pub struct Event {
+    /// Transaction that the item belongs to. None if transactions weren't selected.
+    pub transaction: Option<Arc<Transaction>>,
+    /// Block that the item belongs to. None if blocks weren't selected.
+    pub block: Option<Arc<BlockHeader>>,
+    /// The receipt, input or output.
+    pub item: EventItem,
+}
+
+/// Row that an [`Event`] is built around.
+#[derive(Debug, Clone)]
+pub enum EventItem {
+    /// Receipt row
+    Receipt(Receipt),
+    /// Input row
+    Input(Input),
+    /// Output row
+    Output(Output),
+}
+
+impl EventItem {
+    /// Id of the transaction the item belongs to.
+    pub fn tx_id(&self) -> &Hash {
+        match self {
+            Self::Receipt(r) => &r.tx_id,
+            Self::Input(i) => &i.tx_id,
+            Self::Output(o) => &o.tx_id,
+        }
+    }
+
+    /// Height of the block the item belongs to.
+    pub fn block_height(&self) -> u64 {
+        match self {
+            Self::Receipt(r) => *r.block_height,
+            Self::Input(i) => *i.block_height,
+            Self::Output(o) => *o.block_height,
+        }
+    }
+}
+
+/// Events are returned in the order of the response, receipts first then inputs then outputs.
+impl FromArrowResponse for Vec<Event> {
+    fn from_arrow_response(data: &ArrowResponseData) -> Self {
+        let blocks = data
+            .blocks
+            .iter()
+            .flat_map(BlockHeader::from_arrow)
+            .map(|b| (*b.height, Arc::new(b)))
+            .collect::<HashMap<_, _>>();
+        let transactions = data
+            .transactions
+            .iter()
+            .flat_map(Transaction::from_arrow)
+            .map(|tx| (tx.id.clone(), Arc::new(tx)))
+            .collect::<HashMap<_, _>>();
+
+        let receipts = data
+            .receipts
+            .iter()
+            .flat_map(Receipt::from_arrow)
+            .map(EventItem::Receipt);
+        let inputs = data
+            .inputs
+            .iter()
+            .flat_map(Input::from_arrow)
+            .map(EventItem::Input);
+        let outputs = data
+            .outputs
+            .iter()
+            .flat_map(Output::from_arrow)
+            .map(EventItem::Output);
+
+        receipts
+            .chain(inputs)
+            .chain(outputs)
+            .map(|item| Event {
+                transaction: transactions
+                    .get(item.tx_id())
+                    .filter(|tx| *tx.block_height == item.block_height())
+                    .cloned(),
+                block: blocks.get(&item.block_height()).cloned(),
+                item,
+            })
+            .collect()
+    }
+
+    fn append(&mut self, other: Self) {
+        self.extend(other);
+    }
+}
+
 /// Arrow chunk with schema
 #[derive(Debug, Clone)]
 pub struct ArrowBatch {
@@ -218,3 +283,87 @@ impl From<Receipt> for LogContext {
         }
     }
 }
+
+/// Response of [`Client::preset_query_get_calls`](crate::Client::preset_query_get_calls).
+#[derive(Debug, Clone)]
+pub struct CallResponse {
+    /// Current archive height of the HyperFuel server, if reported.
+    pub archive_height: Option<u64>,
+    /// Next block to query when paginating.
+    pub next_block: u64,
+    /// Server-side query execution time in milliseconds.
+    pub total_execution_time: u64,
+    /// Matching calls, in the order they were made.
+    pub data: Vec<CallContext>,
+}
+
+/// A contract call returned by the preset calls query.
+///
+/// Use [`CallDecoder`](crate::CallDecoder) to decode the arguments and the return value.
+#[derive(Debug, Clone)]
+pub struct CallContext {
+    /// The `Call` receipt.
+    pub call: Receipt,
+    /// The `Return` or `ReturnData` receipt of the called contract, `None` if the call
+    /// didn't return.
+    pub return_receipt: Option<Receipt>,
+    /// Script data of the transaction, which holds the call data.
+    pub script_data: Option<Data>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ToArrow;
+
+    #[test]
+    fn test_events_join() {
+        let blocks = vec![BlockHeader {
+            height: 10.into(),
+            ..Default::default()
+        }];
+        let transactions = vec![Transaction {
+            block_height: 10.into(),
+            id: [1; 32].into(),
+            ..Default::default()
+        }];
+        let receipts = vec![
+            Receipt {
+                tx_id: [1; 32].into(),
+                block_height: 10.into(),
+                ..Default::default()
+            },
+            Receipt {
+                tx_id: [2; 32].into(),
+                block_height: 11.into(),
+                ..Default::default()
+            },
+        ];
+        let outputs = vec![Output {
+            tx_id: [1; 32].into(),
+            block_height: 10.into(),
+            ..Default::default()
+        }];
+
+        let data = ArrowResponseData {
+            blocks: vec![BlockHeader::to_arrow(&blocks, None).unwrap()],
+            transactions: vec![Transaction::to_arrow(&transactions, None).unwrap()],
+            receipts: vec![Receipt::to_arrow(&receipts, None).unwrap()],
+            outputs: vec![Output::to_arrow(&outputs, None).unwrap()],
+            ..Default::default()
+        };
+
+        let events = Vec::<Event>::from_arrow_response(&data);
+        assert_eq!(events.len(), 3);
+
+        assert!(matches!(events[0].item, EventItem::Receipt(_)));
+        assert_eq!(events[0].transaction.as_deref(), Some(&transactions[0]));
+        assert_eq!(events[0].block.as_deref(), Some(&blocks[0]));
+
+        assert!(events[1].transaction.is_none());
+        assert!(events[1].block.is_none());
+
+        assert!(matches!(events[2].item, EventItem::Output(_)));
+        assert_eq!(events[2].transaction.as_deref(), Some(&transactions[0]));
+    }
+}
@@ -1,9 +1,15 @@
 use std::sync::Arc;
 
+use anyhow::{anyhow, Context, Result};
+use bincode::Options;
+use hyperfuel_format::Hash;
 use polars_arrow::{
     array::{
-        ArrayFromIter, BinaryArray, BinaryViewArray, MutableUtf8Array, Utf8Array, Utf8ViewArray,
+        growable::make_growable, Array, ArrayFromIter, BinaryArray, BinaryViewArray,
+        FixedSizeBinaryArray, Int64Array, ListArray, MutableUtf8Array, StaticArray, UInt64Array,
+        Utf8Array, Utf8ViewArray,
     },
+    compute::cast::binary_to_utf8,
     datatypes::{ArrowDataType as DataType, ArrowSchema as Schema, Field},
 };
 use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
@@ -21,10 +27,226 @@ pub fn hex_encode_prefixed(bytes: &[u8]) -> String {
     unsafe { String::from_utf8_unchecked(out) }
 }
 
+/// Options of the bincode encoding of `input_asset_ids` and `input_contracts`, the same as
+/// `bincode::serialize` but without trailing bytes.
+fn hash_list_options() -> impl Options {
+    bincode::DefaultOptions::new()
+        .with_fixint_encoding()
+        .reject_trailing_bytes()
+}
+
+/// Decodes a value of the `input_asset_ids` or `input_contracts` column, which are bincode
+/// encoded lists of hashes.
+pub fn unpack_hash_list(buf: &[u8]) -> Result<Vec<Hash>> {
+    hash_list_options()
+        .deserialize(buf)
+        .context("decode bincode hash list")
+}
+
+/// Encodes a list of hashes like the `input_asset_ids` and `input_contracts` columns, the
+/// inverse of [`unpack_hash_list`].
+pub fn pack_hash_list(hashes: &[Hash]) -> Vec<u8> {
+    hash_list_options()
+        .serialize(hashes)
+        .expect("serializing hashes into a vec can't fail")
+}
+
+/// Binary column that is either `Binary` or `BinaryView`.
+#[derive(Clone, Copy)]
+pub enum BinaryColumn<'a> {
+    Binary(&'a BinaryArray<i32>),
+    View(&'a BinaryViewArray),
+}
+
+impl<'a> BinaryColumn<'a> {
+    pub fn new(col: &'a dyn Array, name: &str) -> Result<Self> {
+        match col.data_type() {
+            DataType::Binary => Ok(Self::Binary(col.as_any().downcast_ref().unwrap())),
+            DataType::BinaryView => Ok(Self::View(col.as_any().downcast_ref().unwrap())),
+            dt => Err(anyhow!(
+                "expected binary column for '{}', it was {:?}",
+                name,
+                dt
+            )),
+        }
+    }
+
+    /// Like [`BinaryColumn::new`], but also checks that every value is a 32 byte hash.
+    pub fn new_hash(col: &'a dyn Array, name: &str) -> Result<Self> {
+        let col = Self::new(col, name)?;
+        if let Some(val) = (0..col.len())
+            .filter_map(|idx| col.get(idx))
+            .find(|v| v.len() != 32)
+        {
+            return Err(anyhow!(
+                "expected 32 byte values in hash column '{}', found a {} byte value",
+                name,
+                val.len()
+            ));
+        }
+        Ok(col)
+    }
+
+    pub fn get(&self, idx: usize) -> Option<&'a [u8]> {
+        match self {
+            Self::Binary(arr) => arr.get(idx),
+            Self::View(arr) => arr.get(idx),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Binary(arr) => arr.len(),
+            Self::View(arr) => arr.len(),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Option<&'a [u8]>> + '_ {
+        (0..self.len()).map(|idx| self.get(idx))
+    }
+}
+
+/// Utf8 column that is either `Utf8` or `Utf8View`.
+#[derive(Clone, Copy)]
+pub enum Utf8Column<'a> {
+    Utf8(&'a Utf8Array<i32>),
+    View(&'a Utf8ViewArray),
+}
+
+impl<'a> Utf8Column<'a> {
+    pub fn new(col: &'a dyn Array, name: &str) -> Result<Self> {
+        match col.data_type() {
+            DataType::Utf8 => Ok(Self::Utf8(col.as_any().downcast_ref().unwrap())),
+            DataType::Utf8View => Ok(Self::View(col.as_any().downcast_ref().unwrap())),
+            dt => Err(anyhow!(
+                "expected utf8 column for '{}', it was {:?}",
+                name,
+                dt
+            )),
+        }
+    }
+
+    pub fn get(&self, idx: usize) -> Option<&'a str> {
+        match self {
+            Self::Utf8(arr) => arr.get(idx),
+            Self::View(arr) => arr.get(idx),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Utf8(arr) => arr.len(),
+            Self::View(arr) => arr.len(),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Option<&'a str>> + '_ {
+        (0..self.len()).map(|idx| self.get(idx))
+    }
+}
+
+/// The `time` column, which is an `Int64` in the schema but is sent as `UInt64` by some servers.
+#[derive(Clone, Copy)]
+pub enum TimeColumn<'a> {
+    Int64(&'a Int64Array),
+    UInt64(&'a UInt64Array),
+}
+
+impl<'a> TimeColumn<'a> {
+    pub fn new(col: &'a dyn Array, name: &str) -> Result<Self> {
+        match col.data_type() {
+            DataType::Int64 => Ok(Self::Int64(col.as_any().downcast_ref().unwrap())),
+            DataType::UInt64 => Ok(Self::UInt64(col.as_any().downcast_ref().unwrap())),
+            dt => Err(anyhow!(
+                "expected Int64 column for '{}', it was {:?}",
+                name,
+                dt
+            )),
+        }
+    }
+
+    pub fn get(&self, idx: usize) -> Option<u64> {
+        match self {
+            Self::Int64(arr) => arr.get(idx).map(|t| t as u64),
+            Self::UInt64(arr) => arr.get(idx),
+        }
+    }
+}
+
+pub fn find_column<'a>(batch: &'a ArrowBatch, name: &str) -> Option<&'a dyn Array> {
+    batch
+        .schema
+        .fields
+        .iter()
+        .position(|f| f.name == name)
+        .and_then(|idx| batch.chunk.columns().get(idx))
+        .map(|col| col.as_ref())
+}
+
+/// Gathers the rows at the given indices into a new array.
+pub fn take_rows(array: &dyn Array, indices: &[usize]) -> Box<dyn Array> {
+    // growable doesn't support Utf8 so it goes through binary, same as concat_chunks.
+    if let Some(arr) = array.as_any().downcast_ref::<Utf8Array<i32>>() {
+        let arr = take_rows(&arr.to_binary(), indices);
+        return binary_to_utf8(
+            arr.as_any().downcast_ref::<BinaryArray<i32>>().unwrap(),
+            DataType::Utf8,
+        )
+        .unwrap()
+        .boxed();
+    }
+
+    let mut growable = make_growable(&[array], true, indices.len());
+
+    for &idx in indices {
+        assert!(idx < array.len(), "row index out of bounds");
+        // Safety: the index is checked above
+        unsafe { growable.extend(0, idx, 1) };
+    }
+
+    growable.as_box()
+}
+
+/// Binary columns that hold 32 byte addresses or contract ids.
+const ADDRESS_COLUMNS: &[&str] = &[
+    "root_contract_id",
+    "to",
+    "to_address",
+    "sender",
+    "recipient",
+    "contract_id",
+    "owner",
+    "contract",
+    "input_contract",
+];
+
 pub fn hex_encode_batch<F: Fn(&[u8]) -> String + Send + Sync + Copy>(
     batch: &ArrowBatch,
     encode: F,
 ) -> ArrowBatch {
+    encode_binary_columns(batch, |_| encode)
+}
+
+/// Encodes address columns as checksummed hex and other binary columns as prefixed hex.
+pub fn checksum_encode_batch(batch: &ArrowBatch) -> ArrowBatch {
+    encode_binary_columns(batch, |name| -> fn(&[u8]) -> String {
+        if ADDRESS_COLUMNS.contains(&name) {
+            checksum_encode_address
+        } else {
+            hex_encode_prefixed
+        }
+    })
+}
+
+fn checksum_encode_address(bytes: &[u8]) -> String {
+    hyperfuel_format::checksum_encode(bytes).unwrap_or_else(|_| hex_encode_prefixed(bytes))
+}
+
+fn encode_binary_columns<F, E>(batch: &ArrowBatch, encoder_for: E) -> ArrowBatch
+where
+    F: Fn(&[u8]) -> String + Copy,
+    E: Fn(&str) -> F + Send + Sync,
+{
     let (fields, cols) = batch
         .chunk
         .columns()
@@ -32,9 +254,10 @@ pub fn hex_encode_batch<F: Fn(&[u8]) -> String + Send + Sync + Copy>(
         .zip(batch.schema.fields.par_iter())
         .map(|(col, field)| {
             let col = match col.data_type() {
-                DataType::Binary => {
-                    Box::new(hex_encode(col.as_any().downcast_ref().unwrap(), encode))
-                }
+                DataType::Binary => Box::new(hex_encode(
+                    col.as_any().downcast_ref().unwrap(),
+                    encoder_for(&field.name),
+                )),
                 _ => col.clone(),
             };
 
@@ -88,6 +311,32 @@ pub fn map_batch_to_binary_view(batch: ArrowBatch) -> ArrowBatch {
                     .iter(),
             )
             .boxed(),
+            // parquet can't write nested binary, like the list column mappings
+            DataType::LargeList(field)
+                if matches!(
+                    field.data_type,
+                    DataType::Binary | DataType::FixedSizeBinary(_)
+                ) =>
+            {
+                let list = col.as_any().downcast_ref::<ListArray<i64>>().unwrap();
+                let values = list.values().as_any();
+                let values = match values.downcast_ref::<BinaryArray<i32>>() {
+                    Some(values) => BinaryViewArray::arr_from_iter(values.iter()),
+                    None => BinaryViewArray::arr_from_iter(
+                        values
+                            .downcast_ref::<FixedSizeBinaryArray>()
+                            .unwrap()
+                            .iter(),
+                    ),
+                };
+                ListArray::<i64>::new(
+                    ListArray::<i64>::default_datatype(DataType::BinaryView),
+                    list.offsets().clone(),
+                    values.boxed(),
+                    list.validity().cloned(),
+                )
+                .boxed()
+            }
             _ => col.clone(),
         })
         .collect::<Vec<_>>();
@@ -114,3 +363,55 @@ pub fn map_batch_to_binary_view(batch: ArrowBatch) -> ArrowBatch {
         schema: Arc::new(schema),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unpack_hash_list() {
+        let hashes = vec![Hash::from([1; 32]), Hash::from([2; 32])];
+
+        let buf = pack_hash_list(&hashes);
+        assert_eq!(buf, bincode::serialize(&hashes).unwrap());
+        assert_eq!(unpack_hash_list(&buf).unwrap(), hashes);
+        assert_eq!(unpack_hash_list(&pack_hash_list(&[])).unwrap(), []);
+
+        // concatenated hashes aren't a hash list
+        let concatenated = hyperfuel_format::pack_hashes(&hashes);
+        assert!(unpack_hash_list(&concatenated).is_err());
+        assert!(unpack_hash_list(&buf[1..]).is_err());
+        assert!(unpack_hash_list(&[buf.as_slice(), &[0]].concat()).is_err());
+        assert!(unpack_hash_list(&[]).is_err());
+    }
+
+    #[test]
+    fn test_checksum_encode_batch() {
+        let addr = [0x9c; 32];
+        let binary = |v: &[u8]| BinaryArray::<i32>::from_slice([v]).boxed();
+        let batch = ArrowBatch {
+            chunk: ArrowChunk::new(vec![binary(&addr), binary(&addr), binary(&[0xab])]).into(),
+            schema: Schema::from(vec![
+                Field::new("contract_id", DataType::Binary, true),
+                Field::new("asset_id", DataType::Binary, true),
+                Field::new("to", DataType::Binary, true),
+            ])
+            .into(),
+        };
+
+        let batch = checksum_encode_batch(&batch);
+        let col = |i: usize| {
+            batch.chunk.columns()[i]
+                .as_any()
+                .downcast_ref::<Utf8Array<i32>>()
+                .unwrap()
+                .value(0)
+                .to_owned()
+        };
+
+        assert_eq!(col(0), hyperfuel_format::checksum_encode(&addr).unwrap());
+        assert_ne!(col(0), col(1));
+        assert_eq!(col(1), hex_encode_prefixed(&addr));
+        assert_eq!(col(2), "0xab");
+    }
+}
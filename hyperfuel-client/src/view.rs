@@ -0,0 +1,606 @@
+use anyhow::{anyhow, Result};
+use hyperfuel_format::{InputType, OutputType, ReceiptType, TransactionStatus, TransactionType};
+use polars_arrow::array::{Array, StaticArray, UInt64Array, UInt8Array};
+
+use crate::{
+    util::{find_column, BinaryColumn, TimeColumn, Utf8Column},
+    ArrowBatch,
+};
+
+fn uint64_column<'a>(col: &'a dyn Array, name: &str) -> Result<&'a UInt64Array> {
+    col.as_any().downcast_ref().ok_or_else(|| {
+        anyhow!(
+            "expected UInt64 column for '{}', it was {:?}",
+            name,
+            col.data_type()
+        )
+    })
+}
+
+fn uint8_column<'a>(col: &'a dyn Array, name: &str) -> Result<&'a UInt8Array> {
+    col.as_any().downcast_ref().ok_or_else(|| {
+        anyhow!(
+            "expected UInt8 column for '{}', it was {:?}",
+            name,
+            col.data_type()
+        )
+    })
+}
+
+/// Columns whose accessors don't return an `Option` are checked to have no nulls when the views
+/// are built.
+fn non_null<'a>(col: &'a dyn Array, name: &str) -> Result<&'a dyn Array> {
+    if col.null_count() > 0 {
+        return Err(anyhow!("column '{}' has null values", name));
+    }
+    Ok(col)
+}
+
+/// Hash columns are checked to only have 32 byte values when the views are built.
+fn as_hash(buf: &[u8]) -> &[u8; 32] {
+    buf.try_into()
+        .expect("hash column has a value that isn't 32 bytes")
+}
+
+// Maps each kind of field to its column type, how the column is built and how a value is read.
+macro_rules! view_column_type {
+    (hash) => { BinaryColumn<'a> };
+    (opt_hash) => { BinaryColumn<'a> };
+    (bytes) => { BinaryColumn<'a> };
+    (opt_str) => { Utf8Column<'a> };
+    (uint) => { &'a UInt64Array };
+    (opt_uint) => { &'a UInt64Array };
+    (time) => { TimeColumn<'a> };
+    (tx_status) => { &'a UInt8Array };
+    (tx_type) => { &'a UInt8Array };
+    (receipt_type) => { &'a UInt8Array };
+    (input_type) => { &'a UInt8Array };
+    (output_type) => { &'a UInt8Array };
+}
+
+macro_rules! view_column_new {
+    (hash, $col:expr, $name:expr) => {
+        BinaryColumn::new_hash(non_null($col, $name)?, $name)
+    };
+    (opt_hash, $col:expr, $name:expr) => {
+        BinaryColumn::new_hash($col, $name)
+    };
+    (bytes, $col:expr, $name:expr) => {
+        BinaryColumn::new($col, $name)
+    };
+    (opt_str, $col:expr, $name:expr) => {
+        Utf8Column::new($col, $name)
+    };
+    (uint, $col:expr, $name:expr) => {
+        uint64_column(non_null($col, $name)?, $name)
+    };
+    (opt_uint, $col:expr, $name:expr) => {
+        uint64_column($col, $name)
+    };
+    (time, $col:expr, $name:expr) => {
+        TimeColumn::new(non_null($col, $name)?, $name)
+    };
+    ($kind:ident, $col:expr, $name:expr) => {
+        uint8_column(non_null($col, $name)?, $name)
+    };
+}
+
+macro_rules! view_accessor {
+    ($name:ident, hash) => {
+        #[doc = concat!("Value of the `", stringify!($name), "` column.")]
+        ///
+        /// Panics if the column wasn't selected. A column with null values is rejected when the
+        /// views are built.
+        pub fn $name(&self) -> &'a [u8; 32] {
+            as_hash(
+                self.required(self.columns.$name, stringify!($name))
+                    .get(self.idx)
+                    .unwrap(),
+            )
+        }
+    };
+    ($name:ident, opt_hash) => {
+        #[doc = concat!("Value of the `", stringify!($name), "` column.")]
+        pub fn $name(&self) -> Option<&'a [u8; 32]> {
+            self.columns
+                .$name
+                .and_then(|c| c.get(self.idx))
+                .map(as_hash)
+        }
+    };
+    ($name:ident, bytes) => {
+        #[doc = concat!("Value of the `", stringify!($name), "` column.")]
+        pub fn $name(&self) -> Option<&'a [u8]> {
+            self.columns.$name.and_then(|c| c.get(self.idx))
+        }
+    };
+    ($name:ident, opt_str) => {
+        #[doc = concat!("Value of the `", stringify!($name), "` column.")]
+        pub fn $name(&self) -> Option<&'a str> {
+            self.columns.$name.and_then(|c| c.get(self.idx))
+        }
+    };
+    ($name:ident, uint) => {
+        #[doc = concat!("Value of the `", stringify!($name), "` column.")]
+        ///
+        /// Panics if the column wasn't selected. A column with null values is rejected when the
+        /// views are built.
+        pub fn $name(&self) -> u64 {
+            self.required(self.columns.$name, stringify!($name))
+                .value(self.idx)
+        }
+    };
+    ($name:ident, opt_uint) => {
+        #[doc = concat!("Value of the `", stringify!($name), "` column.")]
+        pub fn $name(&self) -> Option<u64> {
+            self.columns.$name.and_then(|c| c.get(self.idx))
+        }
+    };
+    ($name:ident, time) => {
+        #[doc = concat!("Value of the `", stringify!($name), "` column.")]
+        ///
+        /// Panics if the column wasn't selected. A column with null values is rejected when the
+        /// views are built.
+        pub fn $name(&self) -> u64 {
+            self.required(self.columns.$name, stringify!($name))
+                .get(self.idx)
+                .unwrap()
+        }
+    };
+    ($name:ident, tx_status) => {
+        #[doc = concat!("Value of the `", stringify!($name), "` column.")]
+        ///
+        /// Panics if the column wasn't selected. A column with null values is rejected when the
+        /// views are built.
+        pub fn $name(&self) -> TransactionStatus {
+            let val = self
+                .required(self.columns.$name, stringify!($name))
+                .value(self.idx);
+            TransactionStatus::from_u8(val)
+        }
+    };
+    ($name:ident, tx_type) => {
+        #[doc = concat!("Value of the `", stringify!($name), "` column.")]
+        ///
+        /// Panics if the column wasn't selected. A column with null values is rejected when the
+        /// views are built.
+        pub fn $name(&self) -> TransactionType {
+            let val = self
+                .required(self.columns.$name, stringify!($name))
+                .value(self.idx);
+            TransactionType::from(val)
+        }
+    };
+    ($name:ident, receipt_type) => {
+        #[doc = concat!("Value of the `", stringify!($name), "` column.")]
+        ///
+        /// Panics if the column wasn't selected. A column with null values is rejected when the
+        /// views are built.
+        pub fn $name(&self) -> ReceiptType {
+            let val = self
+                .required(self.columns.$name, stringify!($name))
+                .value(self.idx);
+            ReceiptType::from_u8(val)
+        }
+    };
+    ($name:ident, input_type) => {
+        #[doc = concat!("Value of the `", stringify!($name), "` column.")]
+        ///
+        /// Panics if the column wasn't selected. A column with null values is rejected when the
+        /// views are built.
+        pub fn $name(&self) -> InputType {
+            let val = self
+                .required(self.columns.$name, stringify!($name))
+                .value(self.idx);
+            InputType::from_u8(val)
+        }
+    };
+    ($name:ident, output_type) => {
+        #[doc = concat!("Value of the `", stringify!($name), "` column.")]
+        ///
+        /// Panics if the column wasn't selected. A column with null values is rejected when the
+        /// views are built.
+        pub fn $name(&self) -> OutputType {
+            let val = self
+                .required(self.columns.$name, stringify!($name))
+                .value(self.idx);
+            OutputType::from_u8(val)
+        }
+    };
+}
+
+macro_rules! define_view {
+    (
+        $(#[$view_meta:meta])*
+        $view:ident,
+        $(#[$views_meta:meta])*
+        $views:ident,
+        $columns:ident {
+            $($field:ident: $kind:ident,)*
+        }
+    ) => {
+        #[derive(Clone, Copy)]
+        struct $columns<'a> {
+            $($field: Option<view_column_type!($kind)>,)*
+        }
+
+        $(#[$views_meta])*
+        #[derive(Clone, Copy)]
+        pub struct $views<'a> {
+            columns: $columns<'a>,
+            len: usize,
+        }
+
+        impl<'a> $views<'a> {
+            /// Looks up the columns of the batch. Columns that are not in the batch read as
+            /// `None`, returns an error if a column has an unexpected type, a hash column has
+            /// a value that isn't 32 bytes or a column whose accessor doesn't return an `Option`
+            /// has a null value.
+            pub fn new(batch: &'a ArrowBatch) -> Result<Self> {
+                Ok(Self {
+                    columns: $columns {
+                        $($field: find_column(batch, stringify!($field))
+                            .map(|col| view_column_new!($kind, col, stringify!($field)))
+                            .transpose()?,)*
+                    },
+                    len: batch.chunk.len(),
+                })
+            }
+
+            /// Number of rows.
+            pub fn len(&self) -> usize {
+                self.len
+            }
+
+            /// Returns true if there are no rows.
+            pub fn is_empty(&self) -> bool {
+                self.len == 0
+            }
+
+            /// Returns a view of the row at `idx`, or `None` if it is out of bounds.
+            ///
+            /// The view borrows the batch, not `self`, so values read from it live as long as the
+            /// batch.
+            pub fn get(&self, idx: usize) -> Option<$view<'a>> {
+                (idx < self.len).then(|| $view {
+                    columns: self.columns,
+                    idx,
+                })
+            }
+
+            /// Iterates over views of all rows.
+            pub fn iter(&self) -> impl Iterator<Item = $view<'a>> {
+                let columns = self.columns;
+                (0..self.len).map(move |idx| $view { columns, idx })
+            }
+        }
+
+        $(#[$view_meta])*
+        #[derive(Clone, Copy)]
+        pub struct $view<'a> {
+            columns: $columns<'a>,
+            idx: usize,
+        }
+
+        // the receipt table has a `len` column.
+        #[allow(clippy::len_without_is_empty)]
+        impl<'a> $view<'a> {
+            /// Index of the row in the batch.
+            pub fn index(&self) -> usize {
+                self.idx
+            }
+
+            #[allow(dead_code)]
+            fn required<T>(&self, col: Option<T>, name: &str) -> T {
+                col.unwrap_or_else(|| panic!("column '{}' is not in the batch", name))
+            }
+
+            $(view_accessor!($field, $kind);)*
+        }
+    };
+}
+
+define_view!(
+    /// Borrowed view of a single block header row.
+    BlockHeaderView,
+    /// Zero-copy row access to a block header batch.
+    BlockHeaderViews,
+    BlockHeaderColumns {
+        id: hash,
+        da_height: uint,
+        consensus_parameters_version: uint,
+        state_transition_bytecode_version: uint,
+        transactions_count: uint,
+        message_receipt_count: uint,
+        transactions_root: hash,
+        message_outbox_root: hash,
+        event_inbox_root: hash,
+        height: uint,
+        prev_root: hash,
+        time: time,
+        application_hash: hash,
+    }
+);
+
+define_view!(
+    /// Borrowed view of a single transaction row.
+    TransactionView,
+    /// Zero-copy row access to a transaction batch.
+    TransactionViews,
+    TransactionColumns {
+        block_height: uint,
+        id: hash,
+        input_asset_ids: bytes,
+        input_contracts: bytes,
+        input_contract_utxo_id: opt_hash,
+        input_contract_balance_root: opt_hash,
+        input_contract_state_root: opt_hash,
+        input_contract_tx_pointer_block_height: opt_uint,
+        input_contract_tx_pointer_tx_index: opt_uint,
+        input_contract: opt_hash,
+        policies_tip: opt_uint,
+        policies_witness_limit: opt_uint,
+        policies_maturity: opt_uint,
+        policies_max_fee: opt_uint,
+        script_gas_limit: opt_uint,
+        maturity: opt_uint,
+        mint_amount: opt_uint,
+        mint_asset_id: opt_hash,
+        mint_gas_price: opt_uint,
+        tx_pointer_block_height: opt_uint,
+        tx_pointer_tx_index: opt_uint,
+        tx_type: tx_type,
+        output_contract_input_index: opt_uint,
+        output_contract_balance_root: opt_hash,
+        output_contract_state_root: opt_hash,
+        witnesses: bytes,
+        receipts_root: opt_hash,
+        status: tx_status,
+        time: time,
+        reason: opt_str,
+        script: bytes,
+        script_data: bytes,
+        bytecode_witness_index: opt_uint,
+        bytecode_root: opt_hash,
+        subsection_index: opt_uint,
+        subsections_number: opt_uint,
+        proof_set: bytes,
+        consensus_parameters_upgrade_purpose_witness_index: opt_uint,
+        consensus_parameters_upgrade_purpose_checksum: bytes,
+        state_transition_upgrade_purpose_root: opt_hash,
+        salt: bytes,
+    }
+);
+
+define_view!(
+    /// Borrowed view of a single receipt row.
+    ReceiptView,
+    /// Zero-copy row access to a receipt batch.
+    ReceiptViews,
+    ReceiptColumns {
+        receipt_index: uint,
+        root_contract_id: opt_hash,
+        tx_id: hash,
+        tx_status: tx_status,
+        tx_type: tx_type,
+        block_height: uint,
+        pc: opt_uint,
+        is: opt_uint,
+        to: opt_hash,
+        to_address: opt_hash,
+        amount: opt_uint,
+        asset_id: opt_hash,
+        gas: opt_uint,
+        param1: opt_uint,
+        param2: opt_uint,
+        val: opt_uint,
+        ptr: opt_uint,
+        digest: opt_hash,
+        reason: opt_uint,
+        ra: opt_uint,
+        rb: opt_uint,
+        rc: opt_uint,
+        rd: opt_uint,
+        len: opt_uint,
+        receipt_type: receipt_type,
+        result: opt_uint,
+        gas_used: opt_uint,
+        data: bytes,
+        sender: opt_hash,
+        recipient: opt_hash,
+        nonce: bytes,
+        contract_id: opt_hash,
+        sub_id: opt_hash,
+    }
+);
+
+define_view!(
+    /// Borrowed view of a single input row.
+    InputView,
+    /// Zero-copy row access to an input batch.
+    InputViews,
+    InputColumns {
+        tx_id: hash,
+        tx_status: tx_status,
+        tx_type: tx_type,
+        block_height: uint,
+        input_type: input_type,
+        utxo_id: opt_hash,
+        owner: opt_hash,
+        amount: opt_uint,
+        asset_id: opt_hash,
+        tx_pointer_block_height: opt_uint,
+        tx_pointer_tx_index: opt_uint,
+        witness_index: opt_uint,
+        predicate_gas_used: opt_uint,
+        predicate: bytes,
+        predicate_data: bytes,
+        balance_root: opt_hash,
+        state_root: opt_hash,
+        contract: opt_hash,
+        sender: opt_hash,
+        recipient: opt_hash,
+        nonce: bytes,
+        data: bytes,
+    }
+);
+
+define_view!(
+    /// Borrowed view of a single output row.
+    OutputView,
+    /// Zero-copy row access to an output batch.
+    OutputViews,
+    OutputColumns {
+        tx_id: hash,
+        tx_status: tx_status,
+        tx_type: tx_type,
+        block_height: uint,
+        output_type: output_type,
+        to: opt_hash,
+        amount: opt_uint,
+        asset_id: opt_hash,
+        input_index: opt_uint,
+        balance_root: opt_hash,
+        state_root: opt_hash,
+        contract: opt_hash,
+    }
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{util::map_batch_to_binary_view, ToArrow};
+    use hyperfuel_format::Receipt;
+    use polars_arrow::datatypes::{ArrowSchema as Schema, Field};
+
+    fn receipts() -> Vec<Receipt> {
+        vec![
+            Receipt {
+                tx_id: [1; 32].into(),
+                block_height: 3.into(),
+                receipt_type: ReceiptType::LogData,
+                rb: Some(9.into()),
+                data: Some(vec![1, 2, 3].into()),
+                ..Default::default()
+            },
+            Receipt {
+                tx_id: [2; 32].into(),
+                block_height: 4.into(),
+                receipt_type: ReceiptType::Call,
+                to: Some([5; 32].into()),
+                ..Default::default()
+            },
+        ]
+    }
+
+    fn check(batch: &ArrowBatch) {
+        let views = ReceiptViews::new(batch).unwrap();
+        assert_eq!(views.len(), 2);
+
+        let rows = views.iter().collect::<Vec<_>>();
+        assert_eq!(rows[0].tx_id(), &[1; 32]);
+        assert_eq!(rows[0].block_height(), 3);
+        assert_eq!(rows[0].receipt_type(), ReceiptType::LogData);
+        assert_eq!(rows[0].rb(), Some(9));
+        assert_eq!(rows[0].data(), Some([1, 2, 3].as_slice()));
+        assert_eq!(rows[0].to(), None);
+        assert_eq!(rows[1].tx_id(), &[2; 32]);
+        assert_eq!(rows[1].to(), Some(&[5; 32]));
+        assert_eq!(rows[1].data(), None);
+        assert!(views.get(2).is_none());
+    }
+
+    #[test]
+    fn test_receipt_view_binary() {
+        check(&Receipt::to_arrow(&receipts(), None).unwrap());
+    }
+
+    #[test]
+    fn test_receipt_view_binary_view() {
+        check(&map_batch_to_binary_view(
+            Receipt::to_arrow(&receipts(), None).unwrap(),
+        ));
+    }
+
+    #[test]
+    fn test_receipt_view_missing_column() {
+        let selection = ["tx_id".to_owned()].into_iter().collect();
+        let batch = Receipt::to_arrow(&receipts(), Some(&selection)).unwrap();
+        let views = ReceiptViews::new(&batch).unwrap();
+        let row = views.get(0).unwrap();
+
+        assert_eq!(row.tx_id(), &[1; 32]);
+        assert_eq!(row.data(), None);
+    }
+
+    #[test]
+    fn test_receipt_view_outlives_views() {
+        let batch = Receipt::to_arrow(&receipts(), None).unwrap();
+        let (tx_id, data) = {
+            let row = ReceiptViews::new(&batch).unwrap().get(0).unwrap();
+            (row.tx_id(), row.data())
+        };
+
+        assert_eq!(tx_id, &[1; 32]);
+        assert_eq!(data, Some([1, 2, 3].as_slice()));
+    }
+
+    #[test]
+    fn test_receipt_view_invalid_hash() {
+        let selection = ["tx_id".to_owned(), "data".to_owned()]
+            .into_iter()
+            .collect();
+        let mut batch = Receipt::to_arrow(&receipts(), Some(&selection)).unwrap();
+        // rename `data` to `to`, a hash column with 3 byte values
+        batch.schema = Schema::from(
+            batch
+                .schema
+                .fields
+                .iter()
+                .map(|f| Field::new(f.name.replace("data", "to"), f.data_type.clone(), true))
+                .collect::<Vec<_>>(),
+        )
+        .into();
+
+        let err = ReceiptViews::new(&batch).err().unwrap();
+        assert!(err.to_string().contains("hash column 'to'"));
+    }
+
+    #[test]
+    #[should_panic(expected = "column 'block_height' is not in the batch")]
+    fn test_receipt_view_missing_required_column() {
+        let selection = ["tx_id".to_owned()].into_iter().collect();
+        let batch = Receipt::to_arrow(&receipts(), Some(&selection)).unwrap();
+        let views = ReceiptViews::new(&batch).unwrap();
+        views.get(0).unwrap().block_height();
+    }
+
+    #[test]
+    fn test_receipt_view_null_in_required_column() {
+        let selection = ["rb".to_owned(), "to".to_owned()].into_iter().collect();
+        let batch = Receipt::to_arrow(&receipts(), Some(&selection)).unwrap();
+        // rename the optional columns to required ones, each has a null value
+        let rename = |from: &str, to: &str| {
+            let mut batch = batch.clone();
+            batch.schema = Schema::from(
+                batch
+                    .schema
+                    .fields
+                    .iter()
+                    .map(|f| Field::new(f.name.replace(from, to), f.data_type.clone(), true))
+                    .collect::<Vec<_>>(),
+            )
+            .into();
+            batch
+        };
+
+        let err = ReceiptViews::new(&rename("rb", "block_height"))
+            .err()
+            .unwrap();
+        assert!(err
+            .to_string()
+            .contains("column 'block_height' has null values"));
+        let err = ReceiptViews::new(&rename("to", "tx_id")).err().unwrap();
+        assert!(err.to_string().contains("column 'tx_id' has null values"));
+    }
+}
@@ -0,0 +1,97 @@
+use hyperfuel_client::{
+    format::{Receipt, ReceiptType},
+    AbiDecode, SizedStr,
+};
+
+mod logs {
+    hyperfuel_client::hyperfuel_abigen!("test-data/logs-abi.json");
+}
+
+use logs::{Address, Log, Status, Transfer};
+
+const TRANSFER_LOG_ID: u64 = 17859024950540227480;
+const STATUS_LOG_ID: u64 = 4363211024530769387;
+
+fn encoded_transfer() -> Vec<u8> {
+    let mut data = vec![7; 32];
+    data.extend(1000u64.to_be_bytes());
+    data.extend(2u64.to_be_bytes());
+    data.extend(b"hi");
+    data.extend(2u64.to_be_bytes());
+    data.extend([1, 2]);
+    data.extend(1u64.to_be_bytes());
+    data.extend(5u64.to_be_bytes());
+    data.push(1);
+    data.extend(3u64.to_be_bytes());
+    data.extend(4u64.to_be_bytes());
+    data
+}
+
+fn log_data(log_id: u64, data: Vec<u8>) -> Receipt {
+    Receipt {
+        receipt_type: ReceiptType::LogData,
+        rb: Some(log_id.into()),
+        data: Some(data.into()),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_abigen_decode_log() {
+    let transfer = Transfer {
+        to: Address {
+            bits: [7; 32].into(),
+        },
+        amount: 1000,
+        memo: "hi".to_owned(),
+        tags: vec![1, 2],
+        fee: Some(5),
+        pair: (true, [3, 4]),
+    };
+
+    let log = logs::decode_log(&log_data(TRANSFER_LOG_ID, encoded_transfer()))
+        .unwrap()
+        .unwrap();
+    assert_eq!(log, Log::Transfer(transfer.clone()));
+    assert_eq!(log.log_id(), TRANSFER_LOG_ID);
+    assert_eq!(Transfer::decode(&encoded_transfer()).unwrap(), transfer);
+
+    let data = 2u64.to_be_bytes().into_iter().chain(*b"oops").collect();
+    assert_eq!(
+        logs::decode_log(&log_data(STATUS_LOG_ID, data)).unwrap(),
+        Some(Log::Status(Status::Failed(SizedStr("oops".to_owned()))))
+    );
+    assert_eq!(
+        Status::decode(&0u64.to_be_bytes()).unwrap(),
+        Status::Pending
+    );
+
+    assert_eq!(
+        logs::decode_log(&log_data(1515152261580153489, 9u64.to_be_bytes().to_vec())).unwrap(),
+        Some(Log::U64(9))
+    );
+    assert_eq!(logs::LOG_IDS.len(), 3);
+    assert!(logs::LOG_IDS.contains(&STATUS_LOG_ID));
+}
+
+#[test]
+fn test_abigen_decode_errors() {
+    assert_eq!(logs::decode_log(&log_data(1, vec![])).unwrap(), None);
+
+    let not_log_data = Receipt {
+        receipt_type: ReceiptType::Log,
+        rb: Some(STATUS_LOG_ID.into()),
+        ..Default::default()
+    };
+    assert_eq!(logs::decode_log(&not_log_data).unwrap(), None);
+
+    let mut data = encoded_transfer();
+    data.pop();
+    assert!(logs::decode_log(&log_data(TRANSFER_LOG_ID, data)).is_err());
+
+    let mut data = encoded_transfer();
+    data.push(0);
+    assert!(logs::decode_log(&log_data(TRANSFER_LOG_ID, data)).is_err());
+
+    assert!(Status::decode(&3u64.to_be_bytes()).is_err());
+}
@@ -0,0 +1,191 @@
+use std::sync::Arc;
+
+use hyperfuel_client::{
+    format::{Data, Hash, Receipt, ReceiptType, UInt},
+    ArrowBatch, ArrowChunk, ArrowResponseData, FromArrow, FromArrowColumn, FromArrowResponse,
+    SelectFields, ToArrow,
+};
+use polars_arrow::array::{Array, BinaryViewArray, UInt64Array, Utf8ViewArray};
+use polars_arrow::datatypes::{ArrowSchema as Schema, Field};
+
+#[derive(Debug, PartialEq, FromArrow)]
+#[hyperfuel(table = "receipt")]
+struct LogRow {
+    tx_id: Hash,
+    block_height: u64,
+    receipt_type: ReceiptType,
+    #[hyperfuel(rename = "rb")]
+    log_id: UInt,
+    data: Option<Data>,
+    #[hyperfuel(default)]
+    amount: UInt,
+    #[hyperfuel(skip)]
+    note: String,
+}
+
+fn receipts() -> Vec<Receipt> {
+    vec![
+        Receipt {
+            tx_id: [1; 32].into(),
+            block_height: 7.into(),
+            receipt_type: ReceiptType::LogData,
+            rb: Some(42.into()),
+            data: Some(vec![0xde, 0xad].into()),
+            ..Default::default()
+        },
+        Receipt {
+            tx_id: [2; 32].into(),
+            block_height: 8.into(),
+            receipt_type: ReceiptType::Log,
+            rb: Some(43.into()),
+            amount: Some(5.into()),
+            ..Default::default()
+        },
+    ]
+}
+
+#[test]
+fn test_derive_from_arrow() {
+    let batch = Receipt::to_arrow(&receipts(), None).unwrap();
+    let rows = LogRow::from_arrow(&batch);
+
+    assert_eq!(
+        rows,
+        vec![
+            LogRow {
+                tx_id: [1; 32].into(),
+                block_height: 7,
+                receipt_type: ReceiptType::LogData,
+                log_id: 42.into(),
+                data: Some(vec![0xde, 0xad].into()),
+                amount: 0.into(),
+                note: String::new(),
+            },
+            LogRow {
+                tx_id: [2; 32].into(),
+                block_height: 8,
+                receipt_type: ReceiptType::Log,
+                log_id: 43.into(),
+                data: None,
+                amount: 5.into(),
+                note: String::new(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_derive_field_selection() {
+    let selection = LogRow::field_selection();
+
+    assert_eq!(
+        selection
+            .receipt
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>(),
+        [
+            "amount",
+            "block_height",
+            "data",
+            "rb",
+            "receipt_type",
+            "tx_id"
+        ]
+    );
+    assert!(selection.block.is_empty());
+    assert!(selection.transaction.is_empty());
+
+    // the selection only has the columns the struct reads, so it decodes a projected batch.
+    let batch = Receipt::to_arrow(&receipts(), Some(&selection.receipt)).unwrap();
+    assert_eq!(LogRow::from_arrow(&batch).len(), 2);
+}
+
+#[test]
+#[should_panic(expected = "Construct from_arrow rb")]
+fn test_derive_missing_required_column() {
+    let selection = ["tx_id", "block_height", "receipt_type"]
+        .into_iter()
+        .map(String::from)
+        .collect();
+    let batch = Receipt::to_arrow(&receipts(), Some(&selection)).unwrap();
+    LogRow::from_arrow(&batch);
+}
+
+#[test]
+fn test_derive_from_arrow_response() {
+    let batch = Receipt::to_arrow(&receipts(), None).unwrap();
+    let data = ArrowResponseData {
+        receipts: vec![batch.clone(), batch],
+        ..Default::default()
+    };
+
+    let mut rows = Vec::<LogRow>::from_arrow_response(&data);
+    assert_eq!(rows.len(), 4);
+
+    let empty = Vec::<LogRow>::from_arrow_response(&ArrowResponseData::default());
+    FromArrowResponse::append(&mut rows, empty);
+    assert_eq!(rows.len(), 4);
+    assert_eq!(rows[2].log_id, 42.into());
+}
+
+/// Replaces the column `name` of the batch.
+fn with_column(batch: &ArrowBatch, name: &str, col: Box<dyn Array>) -> ArrowBatch {
+    let idx = batch
+        .schema
+        .fields
+        .iter()
+        .position(|f| f.name == name)
+        .unwrap();
+    let mut cols = batch.chunk.columns().to_vec();
+    let mut fields = batch.schema.fields.clone();
+    fields[idx] = Field::new(name.to_owned(), col.data_type().clone(), true);
+    cols[idx] = col;
+
+    ArrowBatch {
+        chunk: Arc::new(ArrowChunk::new(cols)),
+        schema: Arc::new(Schema::from(fields)),
+    }
+}
+
+#[test]
+fn test_derive_binary_view_and_hex() {
+    let batch = Receipt::to_arrow(&receipts(), None).unwrap();
+    // binary columns are BinaryView in the schema, and hex strings if the response is hex encoded
+    let batch = with_column(
+        &batch,
+        "tx_id",
+        BinaryViewArray::from_slice([Some([1; 32]), Some([2; 32])]).boxed(),
+    );
+    let batch = with_column(
+        &batch,
+        "data",
+        Utf8ViewArray::from_slice([Some("0xdead"), None]).boxed(),
+    );
+
+    let rows = LogRow::from_arrow(&batch);
+    assert_eq!(rows[0].tx_id, [1; 32].into());
+    assert_eq!(rows[1].tx_id, [2; 32].into());
+    assert_eq!(rows[0].data, Some(vec![0xde, 0xad].into()));
+    assert_eq!(rows[1].data, None);
+}
+
+#[test]
+#[should_panic(expected = "expected binary column for 'tx_id', it was UInt64")]
+fn test_derive_wrong_column_type() {
+    let batch = Receipt::to_arrow(&receipts(), None).unwrap();
+    let batch = with_column(&batch, "tx_id", UInt64Array::from_slice([1, 2]).boxed());
+    LogRow::from_arrow(&batch);
+}
+
+#[test]
+fn test_read_column_wrong_width() {
+    let col = BinaryViewArray::from_slice([Some(&[1; 20][..]), Some(&[2; 32]), None]);
+    assert_eq!(
+        Hash::read_column(&col, "tx_id").unwrap(),
+        [None, Some([2; 32].into()), None]
+    );
+
+    let err = u64::read_column(&col, "tx_id").unwrap_err();
+    assert!(err.to_string().contains("BinaryView"));
+}
@@ -0,0 +1,414 @@
+//! Generation of Rust types from the logged types of a Sway JSON ABI.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::PathBuf,
+};
+
+use hyperfuel_abi::{Generics, MetadataType, Primitive, ProgramAbi, Resolver, TypeBuilder};
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{Error, Ident, LitStr, Result};
+
+pub fn expand(path: LitStr) -> Result<TokenStream2> {
+    let span = path.span();
+    let err = |msg: String| Error::new(span, msg);
+
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+        .map_err(|_| err("CARGO_MANIFEST_DIR is not set".to_owned()))?;
+    let full_path = PathBuf::from(manifest_dir).join(path.value());
+    let json = std::fs::read_to_string(&full_path)
+        .map_err(|e| err(format!("read {}: {}", full_path.display(), e)))?;
+    let abi = ProgramAbi::from_json(&json).map_err(|e| err(e.to_string()))?;
+
+    let resolver = Resolver::new(&abi);
+    let mut gen = Generator::default();
+    let mut logs = Vec::with_capacity(abi.logged_types.len());
+    let mut variant_names = BTreeSet::new();
+
+    for decl in abi.logged_types.iter() {
+        let log_id = decl.log_id.value().map_err(|e| err(e.to_string()))?;
+        let concrete = resolver
+            .concrete_type(&decl.concrete_type_id)
+            .map_err(|e| err(e.to_string()))?;
+        let variant = variant_name(&concrete.type_field);
+        if !variant_names.insert(variant.clone()) {
+            return Err(err(format!(
+                "logged types map to the same Log variant {variant}"
+            )));
+        }
+        let ty = resolver
+            .concrete(&mut gen, &decl.concrete_type_id)
+            .map_err(|e| err(e.to_string()))?;
+
+        logs.push((log_id, format_ident!("{}", variant), ty));
+    }
+
+    let types = gen.generate_types(&resolver).map_err(err)?;
+
+    let full_path = full_path.to_string_lossy().into_owned();
+    let variants = logs.iter().map(|(_, variant, ty)| {
+        quote! { #variant(#ty) }
+    });
+    let log_id_arms = logs.iter().map(|(log_id, variant, _)| {
+        quote! { Self::#variant(_) => #log_id }
+    });
+    let decode_arms = logs.iter().map(|(log_id, variant, ty)| {
+        quote! {
+            #log_id => <#ty as ::hyperfuel_client::AbiDecode>::decode(data).map(|v| Some(Self::#variant(v)))
+        }
+    });
+    let log_ids = logs.iter().map(|(log_id, _, _)| log_id);
+
+    Ok(quote! {
+        // makes the compiler rebuild when the abi changes.
+        const _: &str = include_str!(#full_path);
+
+        #(#types)*
+
+        /// A log of one of the logged types of the ABI.
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub enum Log {
+            #(
+                #[allow(missing_docs)]
+                #variants,
+            )*
+        }
+
+        impl Log {
+            /// Returns the log id of the logged type.
+            pub fn log_id(&self) -> u64 {
+                match self {
+                    #(#log_id_arms,)*
+                }
+            }
+
+            /// Decodes the data of a log with the given log id.
+            ///
+            /// Returns `None` if the ABI has no logged type with this id.
+            pub fn decode(
+                log_id: u64,
+                data: &[u8],
+            ) -> ::hyperfuel_client::__private::anyhow::Result<Option<Self>> {
+                match log_id {
+                    #(#decode_arms,)*
+                    _ => Ok(None),
+                }
+            }
+        }
+
+        /// Log ids of all logged types of the ABI, to be used in `ReceiptSelection::rb`.
+        pub const LOG_IDS: &[u64] = &[#(#log_ids),*];
+
+        /// Decodes the data of a LogData receipt.
+        ///
+        /// Returns `None` if the receipt isn't a LogData receipt or its log id isn't in the ABI.
+        pub fn decode_log(
+            receipt: &::hyperfuel_client::format::Receipt,
+        ) -> ::hyperfuel_client::__private::anyhow::Result<Option<Log>> {
+            if receipt.receipt_type != ::hyperfuel_client::format::ReceiptType::LogData {
+                return Ok(None);
+            }
+
+            match (receipt.rb.as_ref(), receipt.data.as_ref()) {
+                (Some(rb), Some(data)) => Log::decode(**rb, data.as_ref()),
+                _ => Ok(None),
+            }
+        }
+    })
+}
+
+#[derive(Default)]
+struct Generator {
+    /// Structs and enums that are referenced by the logged types, keyed by metadata type id.
+    used: BTreeSet<usize>,
+}
+
+impl TypeBuilder for Generator {
+    type Type = TokenStream2;
+
+    fn primitive(&mut self, primitive: Primitive) -> TokenStream2 {
+        match primitive {
+            Primitive::Unit => quote! { () },
+            Primitive::Bool => quote! { bool },
+            Primitive::U8 => quote! { u8 },
+            Primitive::U16 => quote! { u16 },
+            Primitive::U32 => quote! { u32 },
+            Primitive::U64 => quote! { u64 },
+            Primitive::U256 => quote! { ::hyperfuel_client::abi::U256 },
+            Primitive::B256 => quote! { ::hyperfuel_client::format::Hash },
+            Primitive::Bytes | Primitive::RawSlice => quote! { ::std::vec::Vec<u8> },
+            Primitive::String | Primitive::StringSlice => quote! { ::std::string::String },
+            Primitive::StringArray(len) => quote! { ::hyperfuel_client::SizedStr<#len> },
+        }
+    }
+
+    fn vec(&mut self, elem: TokenStream2) -> TokenStream2 {
+        quote! { ::std::vec::Vec<#elem> }
+    }
+
+    fn tuple(&mut self, elems: Vec<TokenStream2>) -> TokenStream2 {
+        quote! { (#(#elems,)*) }
+    }
+
+    fn array(&mut self, elem: TokenStream2, len: usize) -> TokenStream2 {
+        quote! { [#elem; #len] }
+    }
+
+    fn named(
+        &mut self,
+        _: &Resolver<'_>,
+        ty: &MetadataType,
+        args: Vec<TokenStream2>,
+    ) -> hyperfuel_abi::Result<TokenStream2> {
+        match (ty.type_field.as_str(), args.as_slice()) {
+            ("enum std::option::Option", [value]) => {
+                return Ok(quote! { ::std::option::Option<#value> });
+            }
+            ("enum std::result::Result", [value, error]) => {
+                return Ok(quote! { ::std::result::Result<#value, #error> });
+            }
+            _ => (),
+        }
+
+        let name = type_ident(&ty.type_field).map_err(hyperfuel_abi::Error::UnsupportedType)?;
+        self.used.insert(ty.metadata_type_id);
+        Ok(if args.is_empty() {
+            quote! { #name }
+        } else {
+            quote! { #name<#(#args),*> }
+        })
+    }
+}
+
+impl Generator {
+    /// Generates the structs and enums referenced by the logged types, including the ones
+    /// that are only referenced by other generated types.
+    fn generate_types(
+        &mut self,
+        resolver: &Resolver<'_>,
+    ) -> std::result::Result<Vec<TokenStream2>, String> {
+        let mut generated = BTreeSet::new();
+        let mut names = BTreeMap::new();
+        let mut types = Vec::new();
+
+        while let Some(id) = self.used.difference(&generated).next().copied() {
+            generated.insert(id);
+            let ty = resolver.metadata_type(id).map_err(|e| e.to_string())?;
+
+            let name = type_ident(&ty.type_field)?;
+            if name == "Log" {
+                return Err(format!(
+                    "{} conflicts with the generated Log enum",
+                    ty.type_field
+                ));
+            }
+            if let Some(other) = names.insert(name.to_string(), &ty.type_field) {
+                return Err(format!(
+                    "{} and {} map to the same Rust type {}",
+                    other, ty.type_field, name
+                ));
+            }
+
+            types.push(self.generate_type(resolver, ty, name)?);
+        }
+
+        Ok(types)
+    }
+
+    fn generate_type(
+        &mut self,
+        resolver: &Resolver<'_>,
+        ty: &MetadataType,
+        name: Ident,
+    ) -> std::result::Result<TokenStream2, String> {
+        let params =
+            ty.type_parameters
+                .iter()
+                .map(|id| {
+                    let param = resolver.metadata_type(*id).map_err(|e| e.to_string())?;
+                    let param = param.type_field.strip_prefix("generic ").ok_or_else(|| {
+                        format!("type parameter {} isn't generic", param.type_field)
+                    })?;
+                    Ok((*id, field_ident(param)?))
+                })
+                .collect::<std::result::Result<Vec<_>, String>>()?;
+        let generics = params
+            .iter()
+            .map(|(id, param)| (*id, quote! { #param }))
+            .collect::<Generics<_>>();
+        let params = params
+            .into_iter()
+            .map(|(_, param)| param)
+            .collect::<Vec<_>>();
+
+        let (decl_generics, impl_generics) = if params.is_empty() {
+            (quote! {}, quote! {})
+        } else {
+            (
+                quote! { <#(#params),*> },
+                quote! { <#(#params: ::hyperfuel_client::AbiDecode),*> },
+            )
+        };
+
+        let doc = format!("Generated from `{}` of the ABI.", ty.type_field);
+        let type_name = ty.type_field.split_once(' ').map_or("", |(_, name)| name);
+
+        if ty.type_field.starts_with("struct ") {
+            let fields = ty
+                .components
+                .iter()
+                .map(|c| {
+                    let ty = resolver
+                        .component(self, c, &generics)
+                        .map_err(|e| e.to_string())?;
+                    Ok((field_ident(&c.name)?, ty))
+                })
+                .collect::<std::result::Result<Vec<_>, String>>()?;
+            let decls = fields.iter().map(|(ident, ty)| quote! { pub #ident: #ty });
+            let decodes = fields.iter().map(|(ident, ty)| {
+                quote! { #ident: <#ty as ::hyperfuel_client::AbiDecode>::decode_from(buf)? }
+            });
+
+            Ok(quote! {
+                #[doc = #doc]
+                #[derive(Debug, Clone, PartialEq, Eq)]
+                pub struct #name #decl_generics {
+                    #(
+                        #[allow(missing_docs)]
+                        #decls,
+                    )*
+                }
+
+                impl #impl_generics ::hyperfuel_client::AbiDecode for #name #decl_generics {
+                    fn decode_from(
+                        buf: &mut &[u8],
+                    ) -> ::hyperfuel_client::__private::anyhow::Result<Self> {
+                        Ok(Self {
+                            #(#decodes,)*
+                        })
+                    }
+                }
+            })
+        } else {
+            let mut decls = Vec::with_capacity(ty.components.len());
+            let mut arms = Vec::with_capacity(ty.components.len());
+
+            for (discriminant, c) in ty.components.iter().enumerate() {
+                let discriminant = discriminant as u64;
+                let variant = field_ident(&c.name)?;
+                let variant_ty = resolver
+                    .component(self, c, &generics)
+                    .map_err(|e| e.to_string())?;
+
+                if variant_ty.to_string() == "()" {
+                    decls.push(quote! { #variant });
+                    arms.push(quote! { #discriminant => Self::#variant });
+                } else {
+                    decls.push(quote! { #variant(#variant_ty) });
+                    arms.push(quote! {
+                        #discriminant => Self::#variant(
+                            <#variant_ty as ::hyperfuel_client::AbiDecode>::decode_from(buf)?
+                        )
+                    });
+                }
+            }
+
+            Ok(quote! {
+                #[doc = #doc]
+                #[derive(Debug, Clone, PartialEq, Eq)]
+                pub enum #name #decl_generics {
+                    #(
+                        #[allow(missing_docs)]
+                        #decls,
+                    )*
+                }
+
+                impl #impl_generics ::hyperfuel_client::AbiDecode for #name #decl_generics {
+                    fn decode_from(
+                        buf: &mut &[u8],
+                    ) -> ::hyperfuel_client::__private::anyhow::Result<Self> {
+                        let discriminant =
+                            <u64 as ::hyperfuel_client::AbiDecode>::decode_from(buf)?;
+                        Ok(match discriminant {
+                            #(#arms,)*
+                            d => {
+                                return Err(::hyperfuel_client::__private::anyhow::anyhow!(
+                                    "invalid discriminant {} for {}",
+                                    d,
+                                    #type_name
+                                ))
+                            }
+                        })
+                    }
+                }
+            })
+        }
+    }
+}
+
+/// Name of a generated struct or enum, the last segment of its path.
+fn type_ident(type_field: &str) -> std::result::Result<Ident, String> {
+    let path = type_field
+        .split_once(' ')
+        .map_or(type_field, |(_, path)| path);
+    let name = path.rsplit("::").next().unwrap_or(path);
+    field_ident(name).map_err(|e| format!("{type_field}: {e}"))
+}
+
+/// Identifier for a field or variant name, using a raw identifier for Rust keywords.
+fn field_ident(name: &str) -> std::result::Result<Ident, String> {
+    if let Ok(ident) = syn::parse_str::<Ident>(name) {
+        return Ok(ident);
+    }
+
+    match name {
+        // these keywords can't be raw identifiers.
+        "self" | "Self" | "super" | "crate" | "_" => {
+            Err(format!("`{name}` can't be used as a Rust identifier"))
+        }
+        _ => syn::parse_str::<Ident>(&format!("r#{name}"))
+            .map_err(|_| format!("`{name}` isn't a valid Rust identifier")),
+    }
+}
+
+/// Name of the Log variant of a logged type, e.g. `OptionU64` for
+/// `enum std::option::Option<u64>`.
+fn variant_name(type_field: &str) -> String {
+    let type_field = type_field
+        .strip_prefix("struct ")
+        .or_else(|| type_field.strip_prefix("enum "))
+        .unwrap_or(type_field);
+
+    let mut name = String::new();
+    let mut rest = type_field;
+    while !rest.is_empty() {
+        let start = rest
+            .find(|c: char| c.is_ascii_alphanumeric())
+            .unwrap_or(rest.len());
+        rest = &rest[start..];
+        let end = rest
+            .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .unwrap_or(rest.len());
+        let (word, tail) = rest.split_at(end);
+        rest = tail;
+
+        // path segments are dropped so only the type names remain.
+        if tail.starts_with("::") {
+            continue;
+        }
+        for part in word.split('_') {
+            let mut chars = part.chars();
+            if let Some(first) = chars.next() {
+                name.push(first.to_ascii_uppercase());
+                name.extend(chars);
+            }
+        }
+    }
+
+    if name.starts_with(|c: char| c.is_ascii_digit()) || name.is_empty() {
+        name.insert(0, 'T');
+    }
+
+    name
+}
@@ -0,0 +1,293 @@
+//! Derive macros for hyperfuel-client.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, spanned::Spanned, Data, DeriveInput, Error, Fields, GenericArgument, LitStr,
+    PathArguments, Result, Type,
+};
+
+mod abigen;
+
+const TABLES: &[&str] = &["block", "transaction", "receipt", "input", "output"];
+
+/// Derives `FromArrow`, `TableRow` and `SelectFields` for a struct with named fields.
+///
+/// Each field is read from the column with the same name, in the table given by the
+/// `#[hyperfuel(table = "...")]` container attribute (one of `block`, `transaction`,
+/// `receipt`, `input` or `output`). Field types must implement `FromArrowColumn`.
+///
+/// Fields of type `Option<T>` are read as nullable columns. Any other field is required and
+/// `from_arrow` panics if the column is missing or contains a null, same as the built-in
+/// implementations. `from_arrow` also panics if a column has a type the field can't be read
+/// from, see `FromArrowColumn::from_batch`.
+///
+/// Field attributes:
+/// - `#[hyperfuel(rename = "rb")]` reads the field from a differently named column.
+/// - `#[hyperfuel(default)]` uses `Default::default()` instead of panicking on a missing value.
+/// - `#[hyperfuel(skip)]` doesn't read the field and doesn't select its column.
+#[proc_macro_derive(FromArrow, attributes(hyperfuel))]
+pub fn derive_from_arrow(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// Generates Rust types for the logged types of a Sway JSON ABI.
+///
+/// Takes the path of the ABI relative to the crate's `Cargo.toml`, e.g.
+/// `hyperfuel_abigen!("abi/my-contract-abi.json")`, and generates in the calling module:
+/// - a struct or enum implementing `AbiDecode` for every struct and enum used by a logged type,
+///   named by the last segment of its path,
+/// - a `Log` enum with a variant per logged type and `Log::log_id`/`Log::decode`,
+/// - `LOG_IDS`, the log ids of all logged types, to be used in `ReceiptSelection::rb`,
+/// - `decode_log(&Receipt)`, which decodes a LogData receipt by its log id in `rb`.
+///
+/// Only the encoding version 1 is supported. Put the invocation in its own module to use
+/// several ABIs in the same crate.
+#[proc_macro]
+pub fn hyperfuel_abigen(input: TokenStream) -> TokenStream {
+    let path = parse_macro_input!(input as LitStr);
+    abigen::expand(path)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+struct FieldSpec {
+    ident: syn::Ident,
+    column: String,
+    ty: Type,
+    kind: FieldKind,
+}
+
+enum FieldKind {
+    Required,
+    Default,
+    Optional(Box<Type>),
+    Skip,
+}
+
+fn expand(input: DeriveInput) -> Result<TokenStream2> {
+    let name = &input.ident;
+    let table = parse_container_attrs(&input)?;
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new(
+                    input.span(),
+                    "FromArrow can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new(
+                input.span(),
+                "FromArrow can only be derived for structs",
+            ))
+        }
+    };
+
+    let specs = fields.iter().map(parse_field).collect::<Result<Vec<_>>>()?;
+
+    let column_vars = specs
+        .iter()
+        .filter(|f| !matches!(f.kind, FieldKind::Skip))
+        .map(|f| {
+            let var = format_ident!("__col_{}", f.ident);
+            let column = &f.column;
+            let ty = match &f.kind {
+                FieldKind::Optional(inner) => inner.as_ref(),
+                _ => &f.ty,
+            };
+            quote! {
+                let mut #var =
+                    <#ty as ::hyperfuel_client::FromArrowColumn>::from_batch(batch, #column);
+            }
+        });
+
+    let field_inits = specs.iter().map(|f| {
+        let ident = &f.ident;
+        let var = format_ident!("__col_{}", f.ident);
+        let expect_msg = format!("Construct from_arrow {}", f.column);
+        // every row is read once, so values are moved out of the column
+        let value = quote! { #var.as_mut().and_then(|col| col[idx].take()) };
+        match &f.kind {
+            FieldKind::Required => quote! {
+                #ident: #value.expect(#expect_msg)
+            },
+            FieldKind::Default => quote! {
+                #ident: #value.unwrap_or_default()
+            },
+            FieldKind::Optional(_) => quote! {
+                #ident: #value
+            },
+            FieldKind::Skip => quote! {
+                #ident: ::std::default::Default::default()
+            },
+        }
+    });
+
+    let table_ident = format_ident!("{}", table);
+    let selected = specs
+        .iter()
+        .filter(|f| !matches!(f.kind, FieldKind::Skip))
+        .map(|f| {
+            let column = &f.column;
+            quote! {
+                selection.#table_ident.insert(#column.to_owned());
+            }
+        });
+
+    let batches_field = format_ident!(
+        "{}",
+        match table.as_str() {
+            "block" => "blocks",
+            "transaction" => "transactions",
+            "receipt" => "receipts",
+            "input" => "inputs",
+            _ => "outputs",
+        }
+    );
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::hyperfuel_client::FromArrow for #name #ty_generics #where_clause {
+            fn from_arrow(batch: &::hyperfuel_client::ArrowBatch) -> ::std::vec::Vec<Self> {
+                #(#column_vars)*
+
+                (0..batch.chunk.len())
+                    .map(|idx| Self {
+                        #(#field_inits,)*
+                    })
+                    .collect()
+            }
+        }
+
+        impl #impl_generics ::hyperfuel_client::TableRow for #name #ty_generics #where_clause {
+            fn batches(
+                data: &::hyperfuel_client::ArrowResponseData,
+            ) -> &[::hyperfuel_client::ArrowBatch] {
+                &data.#batches_field
+            }
+        }
+
+        impl #impl_generics ::hyperfuel_client::SelectFields for #name #ty_generics #where_clause {
+            fn field_selection() -> ::hyperfuel_client::net_types::FieldSelection {
+                let mut selection = ::hyperfuel_client::net_types::FieldSelection::default();
+                #(#selected)*
+                selection
+            }
+        }
+    })
+}
+
+fn parse_container_attrs(input: &DeriveInput) -> Result<String> {
+    let mut table = None;
+
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident("hyperfuel"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("table") {
+                let lit: LitStr = meta.value()?.parse()?;
+                if !TABLES.contains(&lit.value().as_str()) {
+                    return Err(meta.error(format!(
+                        "unknown table \"{}\", expected one of: {}",
+                        lit.value(),
+                        TABLES.join(", ")
+                    )));
+                }
+                table = Some(lit.value());
+                Ok(())
+            } else {
+                Err(meta.error("unknown hyperfuel container attribute"))
+            }
+        })?;
+    }
+
+    table.ok_or_else(|| {
+        Error::new(
+            input.ident.span(),
+            "missing #[hyperfuel(table = \"...\")] attribute",
+        )
+    })
+}
+
+fn parse_field(field: &syn::Field) -> Result<FieldSpec> {
+    let ident = field.ident.clone().unwrap();
+    let mut column = ident.to_string();
+    let mut default = false;
+    let mut skip = false;
+
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident("hyperfuel"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                let lit: LitStr = meta.value()?.parse()?;
+                column = lit.value();
+                Ok(())
+            } else if meta.path.is_ident("default") {
+                default = true;
+                Ok(())
+            } else if meta.path.is_ident("skip") {
+                skip = true;
+                Ok(())
+            } else {
+                Err(meta.error("unknown hyperfuel field attribute"))
+            }
+        })?;
+    }
+
+    let kind = if skip {
+        FieldKind::Skip
+    } else if let Some(inner) = option_inner(&field.ty) {
+        if default {
+            return Err(Error::new(
+                field.span(),
+                "#[hyperfuel(default)] can't be used on an Option field",
+            ));
+        }
+        FieldKind::Optional(Box::new(inner.clone()))
+    } else if default {
+        FieldKind::Default
+    } else {
+        FieldKind::Required
+    };
+
+    Ok(FieldSpec {
+        ident,
+        column,
+        ty: field.ty.clone(),
+        kind,
+    })
+}
+
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    if path.qself.is_some() {
+        return None;
+    }
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        GenericArgument::Type(inner) if args.args.len() == 1 => Some(inner),
+        _ => None,
+    }
+}
@@ -25,6 +25,23 @@ pub enum Error {
     UnknownInputType(String),
     #[error("Unknown output type: {0}")]
     UnknownOutputType(String),
+    #[error("Invalid address checksum. Value was: \"{0}\"")]
+    InvalidAddressChecksum(String),
+    #[error("Invalid bech32 address: {0}")]
+    InvalidBech32(String),
+    #[error("Invalid witnesses: {0}")]
+    InvalidWitnesses(String),
+    #[error("Invalid packed hashes. Length {0} is not a multiple of 32.")]
+    InvalidPackedHashes(usize),
+    #[error("Invalid signature: {0}")]
+    InvalidSignature(String),
+    #[error("Invalid timestamp: {0}")]
+    InvalidTimestamp(String),
+    #[error("{kind} is missing the {field} field")]
+    MissingField {
+        kind: &'static str,
+        field: &'static str,
+    },
 }
 
 pub type Result<T> = StdResult<T, Error>;
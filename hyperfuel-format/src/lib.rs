@@ -3,6 +3,10 @@ mod types;
 
 pub use error::{Error, Result};
 pub use types::{
-    Address, BlockHeader, Data, FixedSizeData, Hash, Hex, Input, InputType, Output, OutputType,
-    Quantity, Receipt, ReceiptType, Transaction, TransactionStatus, TransactionType, UInt,
+    asset_id, bytecode_root, checksum_encode, contract_id, disassemble, disassembly_listing,
+    pack_hashes, parse_witnesses, predicate_owner, recover_signer, unpack_hashes, Address,
+    BlockHeader, Data, FixedSizeData, Hash, Hex, Input, InputKind, InputType, Instruction, Nonce,
+    Opcode, Operands, Output, OutputKind, OutputType, PanicInstruction, PanicReason, Quantity,
+    Receipt, ReceiptKind, ReceiptType, RevertSignal, Timestamp, Transaction, TransactionStatus,
+    TransactionType, UInt, UtxoId, CONTRACT_ID_SEED, FUEL_BECH32_HRP, TAI64_UNIX_EPOCH,
 };
@@ -0,0 +1,209 @@
+use bech32::{Bech32m, Hrp};
+use sha2::{Digest, Sha256};
+
+use super::util::decode_hex;
+use super::FixedSizeData;
+use crate::{Error, Result};
+
+/// Human readable part of legacy Fuel bech32 addresses.
+pub const FUEL_BECH32_HRP: &str = "fuel";
+
+impl FixedSizeData<32> {
+    /// Encodes as a checksummed hex string like `0x9cfB2CAd…`.
+    ///
+    /// The case of each hex letter is set by the matching nibble of the sha256 of the
+    /// lowercase hex string without the prefix, it is uppercase if the nibble is 8 or above.
+    pub fn to_checksum_hex(&self) -> String {
+        encode_checksummed(self)
+    }
+
+    /// Decodes a prefixed hex string, validating the checksum if it has mixed case.
+    ///
+    /// All lowercase and all uppercase strings carry no checksum so they are accepted as is.
+    pub fn from_checksum_hex(hex: &str) -> Result<Self> {
+        let val = hex
+            .strip_prefix("0x")
+            .ok_or_else(|| Error::InvalidHexPrefix(hex.to_owned()))?;
+        let bytes = decode_hex(val).map_err(Error::DecodeHex)?;
+        let addr = Self::try_from(bytes)?;
+
+        let has_lower = val.bytes().any(|b| b.is_ascii_lowercase());
+        let has_upper = val.bytes().any(|b| b.is_ascii_uppercase());
+        if has_lower && has_upper && addr.to_checksum_hex() != hex {
+            return Err(Error::InvalidAddressChecksum(hex.to_owned()));
+        }
+
+        Ok(addr)
+    }
+
+    /// Encodes as a legacy `fuel1…` bech32m address.
+    pub fn to_bech32(&self) -> String {
+        let hrp = Hrp::parse_unchecked(FUEL_BECH32_HRP);
+        bech32::encode::<Bech32m>(hrp, self.as_slice()).expect("32 bytes fit in a bech32 string")
+    }
+
+    /// Decodes a legacy `fuel1…` bech32 address.
+    pub fn from_bech32(s: &str) -> Result<Self> {
+        let (hrp, data) = bech32::decode(s).map_err(|e| Error::InvalidBech32(e.to_string()))?;
+
+        if !hrp.as_str().eq_ignore_ascii_case(FUEL_BECH32_HRP) {
+            return Err(Error::InvalidBech32(format!(
+                "expected the \"{FUEL_BECH32_HRP}\" prefix, got \"{hrp}\""
+            )));
+        }
+
+        Self::try_from(data)
+    }
+}
+
+/// Encodes 32 bytes as a checksummed hex string, see [`FixedSizeData::to_checksum_hex`].
+///
+/// Errors if `bytes` isn't 32 bytes long, since the checksum only covers 64 hex chars.
+pub fn checksum_encode(bytes: &[u8]) -> Result<String> {
+    let bytes: &[u8; 32] = bytes.try_into().map_err(|_| Error::UnexpectedLength {
+        expected: 32,
+        got: bytes.len(),
+    })?;
+
+    Ok(encode_checksummed(bytes))
+}
+
+fn encode_checksummed(bytes: &[u8; 32]) -> String {
+    let lower = faster_hex::hex_string(bytes);
+    let hash = Sha256::digest(lower.as_bytes());
+
+    let mut out = String::with_capacity(lower.len() + 2);
+    out.push_str("0x");
+    for (i, c) in lower.chars().enumerate() {
+        let byte = hash[i / 2];
+        let nibble = if i % 2 == 0 { byte >> 4 } else { byte & 0x0f };
+        if nibble >= 8 {
+            out.push(c.to_ascii_uppercase());
+        } else {
+            out.push(c);
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Address;
+
+    fn addr() -> Address {
+        Address::from(hex_literal::hex!(
+            "9cfb2cad509d417ec40b70ebe1dd72a3624d46fdd1ea5420dbd755ce7f4dc897"
+        ))
+    }
+
+    #[test]
+    fn test_checksum_round_trip() {
+        let checksummed = addr().to_checksum_hex();
+
+        assert_eq!(
+            checksummed.to_ascii_lowercase(),
+            format!("0x{}", faster_hex::hex_string(addr().as_slice()))
+        );
+        assert_ne!(checksummed.to_ascii_lowercase(), checksummed);
+        assert_eq!(Address::from_checksum_hex(&checksummed).unwrap(), addr());
+
+        let lower = checksummed.to_ascii_lowercase();
+        assert_eq!(Address::from_checksum_hex(&lower).unwrap(), addr());
+        let upper = format!("0x{}", lower[2..].to_ascii_uppercase());
+        assert_eq!(Address::from_checksum_hex(&upper).unwrap(), addr());
+    }
+
+    #[test]
+    fn test_checksum_invalid() {
+        let checksummed = addr().to_checksum_hex();
+        let idx = checksummed[2..]
+            .find(|c: char| c.is_ascii_alphabetic())
+            .unwrap()
+            + 2;
+        let mut flipped = checksummed.clone().into_bytes();
+        flipped[idx] ^= 0x20;
+        let flipped = String::from_utf8(flipped).unwrap();
+
+        assert!(matches!(
+            Address::from_checksum_hex(&flipped),
+            Err(Error::InvalidAddressChecksum(_))
+        ));
+        assert!(matches!(
+            Address::from_checksum_hex(&checksummed[2..]),
+            Err(Error::InvalidHexPrefix(_))
+        ));
+
+        // every letter has the wrong case, the string is still mixed case.
+        let inverted = checksummed[2..]
+            .chars()
+            .map(|c| match c.is_ascii_uppercase() {
+                true => c.to_ascii_lowercase(),
+                false => c.to_ascii_uppercase(),
+            })
+            .collect::<String>();
+        assert!(matches!(
+            Address::from_checksum_hex(&format!("0x{inverted}")),
+            Err(Error::InvalidAddressChecksum(_))
+        ));
+    }
+
+    #[test]
+    fn test_checksum_encode_length() {
+        assert_eq!(
+            checksum_encode(addr().as_slice()).unwrap(),
+            addr().to_checksum_hex()
+        );
+        assert!(matches!(
+            checksum_encode(&[0xab; 31]),
+            Err(Error::UnexpectedLength {
+                expected: 32,
+                got: 31
+            })
+        ));
+        assert!(matches!(
+            checksum_encode(&[0xab; 33]),
+            Err(Error::UnexpectedLength {
+                expected: 32,
+                got: 33
+            })
+        ));
+    }
+
+    #[test]
+    fn test_bech32_round_trip() {
+        let encoded = addr().to_bech32();
+
+        assert!(encoded.starts_with("fuel1"));
+        assert_eq!(Address::from_bech32(&encoded).unwrap(), addr());
+        assert_eq!(
+            Address::from_bech32(&encoded.to_ascii_uppercase()).unwrap(),
+            addr()
+        );
+    }
+
+    #[test]
+    fn test_bech32_invalid() {
+        let encoded = addr().to_bech32();
+        let mut corrupted = encoded.clone();
+        corrupted.replace_range(10..11, if &encoded[10..11] == "q" { "p" } else { "q" });
+        assert!(matches!(
+            Address::from_bech32(&corrupted),
+            Err(Error::InvalidBech32(_))
+        ));
+
+        let other_hrp =
+            bech32::encode::<Bech32m>(Hrp::parse_unchecked("bc"), addr().as_slice()).unwrap();
+        assert!(matches!(
+            Address::from_bech32(&other_hrp),
+            Err(Error::InvalidBech32(_))
+        ));
+
+        let short = bech32::encode::<Bech32m>(Hrp::parse_unchecked("fuel"), &[1, 2, 3]).unwrap();
+        assert!(matches!(
+            Address::from_bech32(&short),
+            Err(Error::UnexpectedLength { .. })
+        ));
+    }
+}
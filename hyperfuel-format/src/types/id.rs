@@ -0,0 +1,257 @@
+use sha2::{Digest, Sha256};
+use std::fmt;
+
+use super::{Address, ContractId, FixedSizeData, Hash, Input, Output, Receipt, ReceiptType};
+use crate::{Error, Result};
+
+/// Prefix of the preimages of contract ids and predicate owners.
+pub const CONTRACT_ID_SEED: [u8; 4] = *b"FUEL";
+
+/// Bytecode is split into leaves of this size to compute its merkle root.
+const BYTECODE_LEAF_SIZE: usize = 16 * 1024;
+
+fn sha256(parts: &[&[u8]]) -> Hash {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    let digest: [u8; 32] = hasher.finalize().into();
+    digest.into()
+}
+
+/// Asset id of the asset minted by `contract_id` under `sub_id`.
+pub fn asset_id(contract_id: &ContractId, sub_id: &Hash) -> Hash {
+    sha256(&[contract_id.as_slice(), sub_id.as_slice()])
+}
+
+/// Id of a contract deployed by a Create transaction.
+pub fn contract_id(salt: &Hash, bytecode_root: &Hash, state_root: &Hash) -> ContractId {
+    sha256(&[
+        &CONTRACT_ID_SEED,
+        salt.as_slice(),
+        bytecode_root.as_slice(),
+        state_root.as_slice(),
+    ])
+}
+
+/// Binary merkle root of contract or predicate bytecode.
+///
+/// The bytecode is split into 16 KiB leaves, the last leaf is zero padded to a multiple of 8
+/// bytes.
+pub fn bytecode_root(bytecode: &[u8]) -> Hash {
+    let leaves = bytecode
+        .chunks(BYTECODE_LEAF_SIZE)
+        .map(|leaf| {
+            let mut leaf = leaf.to_vec();
+            leaf.resize(leaf.len().next_multiple_of(8), 0);
+            sha256(&[&[0], &leaf])
+        })
+        .collect::<Vec<_>>();
+
+    merkle_root(&leaves)
+}
+
+fn merkle_root(leaves: &[Hash]) -> Hash {
+    match leaves {
+        [] => sha256(&[]),
+        [leaf] => leaf.clone(),
+        leaves => {
+            // left subtree holds the largest power of two that is less than the number of leaves
+            let split = leaves.len().next_power_of_two() / 2;
+            let left = merkle_root(&leaves[..split]);
+            let right = merkle_root(&leaves[split..]);
+            sha256(&[&[1], left.as_slice(), right.as_slice()])
+        }
+    }
+}
+
+/// Address of the coins that are spendable by `predicate`.
+pub fn predicate_owner(predicate: &[u8]) -> Address {
+    sha256(&[&CONTRACT_ID_SEED, bytecode_root(predicate).as_slice()])
+}
+
+/// Identifies a transaction output that can be spent by an input of a later transaction.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct UtxoId {
+    pub tx_id: Hash,
+    pub output_index: u16,
+}
+
+impl UtxoId {
+    pub fn new(tx_id: Hash, output_index: u16) -> Self {
+        Self {
+            tx_id,
+            output_index,
+        }
+    }
+
+    /// The 34 byte encoding, transaction id followed by the big endian output index.
+    pub fn to_bytes(&self) -> [u8; 34] {
+        let mut out = [0; 34];
+        out[..32].copy_from_slice(self.tx_id.as_slice());
+        out[32..].copy_from_slice(&self.output_index.to_be_bytes());
+        out
+    }
+}
+
+impl TryFrom<&[u8]> for UtxoId {
+    type Error = Error;
+
+    fn try_from(buf: &[u8]) -> Result<Self> {
+        if buf.len() != 34 {
+            return Err(Error::UnexpectedLength {
+                expected: 34,
+                got: buf.len(),
+            });
+        }
+
+        Ok(Self {
+            tx_id: FixedSizeData::try_from(&buf[..32])?,
+            output_index: u16::from_be_bytes([buf[32], buf[33]]),
+        })
+    }
+}
+
+impl fmt::Display for UtxoId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "0x{}{:04x}",
+            faster_hex::hex_string(self.tx_id.as_slice()),
+            self.output_index
+        )
+    }
+}
+
+impl Receipt {
+    /// Returns the id of the asset minted or burned by a Mint or Burn receipt.
+    pub fn derived_asset_id(&self) -> Option<Hash> {
+        match self.receipt_type {
+            ReceiptType::Mint | ReceiptType::Burn => {
+                Some(asset_id(self.contract_id.as_ref()?, self.sub_id.as_ref()?))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Input {
+    /// Returns the owner address derived from the predicate bytecode of a predicate input.
+    pub fn predicate_owner(&self) -> Option<Address> {
+        self.predicate
+            .as_ref()
+            .filter(|p| !p.as_ref().is_empty())
+            .map(|p| predicate_owner(p.as_ref()))
+    }
+}
+
+impl Output {
+    /// Returns the utxo id of this output, `output_index` is its position in the transaction.
+    pub fn utxo_id(&self, output_index: u16) -> UtxoId {
+        UtxoId::new(self.tx_id.clone(), output_index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+
+    #[test]
+    fn test_asset_id() {
+        let contract_id = Hash::from([0x11; 32]);
+
+        assert_eq!(
+            asset_id(&contract_id, &Hash::from([0; 32])),
+            Hash::from(hex!(
+                "1aa2a59f578bb6d05a7cd9dcac714ab7ddb077a0482ce43d72b513f67079bee9"
+            ))
+        );
+
+        let receipt = Receipt {
+            receipt_type: ReceiptType::Burn,
+            contract_id: Some(contract_id),
+            sub_id: Some(Hash::from([0x22; 32])),
+            ..Default::default()
+        };
+        assert_eq!(
+            receipt.derived_asset_id(),
+            Some(Hash::from(hex!(
+                "5189c77d29fe5d546a045ec46986852785fea5c13ac7da9c115ff5fb6edf817c"
+            )))
+        );
+    }
+
+    #[test]
+    fn test_bytecode_root() {
+        assert_eq!(
+            bytecode_root(&[]),
+            Hash::from(hex!(
+                "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+            ))
+        );
+
+        // single leaf padded to 16 bytes
+        let code = (0..13).collect::<Vec<u8>>();
+        assert_eq!(
+            bytecode_root(&code),
+            Hash::from(hex!(
+                "ed0f8d682f36ebe9abbbff7054da92f7390f3d50fc6e9d4496bc51c3f46818dd"
+            ))
+        );
+
+        // three leaves
+        let code = (0..40000).map(|i| (i * 7 % 256) as u8).collect::<Vec<_>>();
+        assert_eq!(
+            bytecode_root(&code),
+            Hash::from(hex!(
+                "4c01e9a97b1c9165843e95185474eb923453cc8ddcf1d0c05da6b1de04c2c339"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_contract_id() {
+        let code = (0..40000).map(|i| (i * 7 % 256) as u8).collect::<Vec<_>>();
+
+        assert_eq!(
+            contract_id(
+                &Hash::from([0x33; 32]),
+                &bytecode_root(&code),
+                &Hash::from([0; 32])
+            ),
+            Hash::from(hex!(
+                "6d8115cd84c05c2fe70972bc4de9530b1564b130e28ca9ebcbd368ee3a5ed027"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_predicate_owner() {
+        let input = Input {
+            predicate: Some(hex!("1a403000240400005d000000").to_vec().into()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            input.predicate_owner(),
+            Some(Hash::from(hex!(
+                "233ed09f517131ca01b341d70f597532705ec7dd4e5e105e394a6e9c3326a8e7"
+            )))
+        );
+        assert_eq!(Input::default().predicate_owner(), None);
+    }
+
+    #[test]
+    fn test_utxo_id() {
+        let output = Output {
+            tx_id: Hash::from([0xab; 32]),
+            ..Default::default()
+        };
+        let utxo_id = output.utxo_id(258);
+
+        assert_eq!(utxo_id.to_string(), format!("0x{}0102", "ab".repeat(32)));
+        assert_eq!(UtxoId::try_from(&utxo_id.to_bytes()[..]).unwrap(), utxo_id);
+        assert!(UtxoId::try_from(&[0u8; 32][..]).is_err());
+    }
+}
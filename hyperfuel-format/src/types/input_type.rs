@@ -5,6 +5,7 @@ use std::fmt;
 use std::result::Result as StdResult;
 use std::str::FromStr;
 
+use super::quantity::{decode_u8_hex, encode_hex, u8_hex_str};
 use super::Hex;
 
 #[allow(clippy::enum_variant_names)]
@@ -14,46 +15,63 @@ pub enum InputType {
     InputCoin,
     InputContract,
     InputMessage,
+    /// A discriminant that was added to Fuel after this version of the library.
+    Unknown(u8),
+}
+
+impl fmt::Display for InputType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unknown(val) => write!(f, "Unknown({val})"),
+            val => f.write_str(val.name()),
+        }
+    }
 }
 
 impl FromStr for InputType {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self> {
-        match s {
-            "0x0" => Ok(Self::InputCoin),
-            "0x1" => Ok(Self::InputContract),
-            "0x2" => Ok(Self::InputMessage),
-            _ => Err(Error::UnknownInputType(s.to_owned())),
-        }
+        decode_u8_hex(s)
+            .map(Self::from_u8)
+            .ok_or_else(|| Error::UnknownInputType(s.to_owned()))
     }
 }
 
 impl InputType {
-    pub fn as_str(&self) -> &'static str {
-        match self {
-            Self::InputCoin => "0x0",
-            Self::InputContract => "0x1",
-            Self::InputMessage => "0x2",
-        }
-    }
-
     pub fn as_u8(&self) -> u8 {
         match self {
             Self::InputCoin => 0,
             Self::InputContract => 1,
             Self::InputMessage => 2,
+            Self::Unknown(val) => *val,
         }
     }
 
-    pub fn from_u8(n: u8) -> Result<Self> {
+    pub fn from_u8(n: u8) -> Self {
         match n {
-            0 => Ok(Self::InputCoin),
-            1 => Ok(Self::InputContract),
-            2 => Ok(Self::InputMessage),
-            _ => Err(Error::UnknownInputType(n.to_string())),
+            0 => Self::InputCoin,
+            1 => Self::InputContract,
+            2 => Self::InputMessage,
+            n => Self::Unknown(n),
         }
     }
+
+    /// Name of the variant, e.g. "InputContract". Unknown discriminants are named "Unknown".
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::InputCoin => "InputCoin",
+            Self::InputContract => "InputContract",
+            Self::InputMessage => "InputMessage",
+            Self::Unknown(_) => "Unknown",
+        }
+    }
+
+    /// Hex string of the type.
+    #[deprecated(note = "use `Hex::encode_hex` instead")]
+    pub fn as_str(&self) -> &'static str {
+        u8_hex_str(self.as_u8())
+    }
 }
 
 struct InputTypeVisitor;
@@ -87,13 +105,13 @@ impl Serialize for InputType {
     where
         S: Serializer,
     {
-        serializer.serialize_str(self.as_str())
+        serializer.serialize_str(&self.encode_hex())
     }
 }
 
 impl Hex for InputType {
     fn encode_hex(&self) -> String {
-        self.as_str().to_owned()
+        encode_hex(&[self.as_u8()])
     }
 
     fn decode_hex(hex: &str) -> Result<Self> {
@@ -114,8 +132,20 @@ mod tests {
     }
 
     #[test]
-    #[should_panic]
+    fn test_u8_round_trip() {
+        for val in 0..=u8::MAX {
+            assert_eq!(InputType::from_u8(val).as_u8(), val);
+        }
+    }
+
+    #[test]
     fn test_de_unknown() {
-        assert_de_tokens(&InputType::InputCoin, &[Token::Str("0x4")]);
+        assert_tokens(&InputType::Unknown(4), &[Token::Str("0x4")]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_de_invalid() {
+        assert_de_tokens(&InputType::InputCoin, &[Token::Str("4")]);
     }
 }
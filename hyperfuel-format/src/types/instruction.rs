@@ -0,0 +1,380 @@
+use std::fmt;
+
+/// Operands of an instruction. Registers are 6 bit ids, immediates are the remaining bits of
+/// the 24 bit operand field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operands {
+    None,
+    Reg1(u8),
+    Reg2(u8, u8),
+    Reg3(u8, u8, u8),
+    Reg4(u8, u8, u8, u8),
+    Reg3Imm6(u8, u8, u8, u8),
+    Reg2Imm12(u8, u8, u16),
+    Reg1Imm18(u8, u32),
+    Imm24(u32),
+    /// Operand field of an unknown opcode.
+    Raw(u32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OperandFormat {
+    None,
+    Reg1,
+    Reg2,
+    Reg3,
+    Reg4,
+    Reg3Imm6,
+    Reg2Imm12,
+    Reg1Imm18,
+    Imm24,
+}
+
+macro_rules! opcodes {
+    ($($code:literal $name:ident $mnemonic:literal $format:ident,)*) => {
+        /// FuelVM opcode, the first byte of an instruction.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum Opcode {
+            $($name,)*
+            /// An opcode that was added to the VM after this version of the library.
+            Unknown(u8),
+        }
+
+        impl Opcode {
+            /// All known opcodes.
+            pub const ALL: &'static [Opcode] = &[$(Self::$name,)*];
+
+            pub fn from_u8(val: u8) -> Self {
+                match val {
+                    $($code => Self::$name,)*
+                    val => Self::Unknown(val),
+                }
+            }
+
+            pub fn to_u8(&self) -> u8 {
+                match self {
+                    $(Self::$name => $code,)*
+                    Self::Unknown(val) => *val,
+                }
+            }
+
+            /// Assembly mnemonic like `ADDI`, `UNKNOWN` for unknown opcodes.
+            pub fn mnemonic(&self) -> &'static str {
+                match self {
+                    $(Self::$name => $mnemonic,)*
+                    Self::Unknown(_) => "UNKNOWN",
+                }
+            }
+
+            fn operand_format(&self) -> Option<OperandFormat> {
+                match self {
+                    $(Self::$name => Some(OperandFormat::$format),)*
+                    Self::Unknown(_) => None,
+                }
+            }
+        }
+    };
+}
+
+opcodes! {
+    0x10 Add "ADD" Reg3,
+    0x11 And "AND" Reg3,
+    0x12 Div "DIV" Reg3,
+    0x13 Eq "EQ" Reg3,
+    0x14 Exp "EXP" Reg3,
+    0x15 Gt "GT" Reg3,
+    0x16 Lt "LT" Reg3,
+    0x17 Mlog "MLOG" Reg3,
+    0x18 Mroo "MROO" Reg3,
+    0x19 Mod "MOD" Reg3,
+    0x1a Move "MOVE" Reg2,
+    0x1b Mul "MUL" Reg3,
+    0x1c Not "NOT" Reg2,
+    0x1d Or "OR" Reg3,
+    0x1e Sll "SLL" Reg3,
+    0x1f Srl "SRL" Reg3,
+    0x20 Sub "SUB" Reg3,
+    0x21 Xor "XOR" Reg3,
+    0x22 Mldv "MLDV" Reg4,
+    0x24 Ret "RET" Reg1,
+    0x25 Retd "RETD" Reg2,
+    0x26 Aloc "ALOC" Reg1,
+    0x27 Mcl "MCL" Reg2,
+    0x28 Mcp "MCP" Reg3,
+    0x29 Meq "MEQ" Reg4,
+    0x2a Bhsh "BHSH" Reg2,
+    0x2b Bhei "BHEI" Reg1,
+    0x2c Burn "BURN" Reg2,
+    0x2d Call "CALL" Reg4,
+    0x2e Ccp "CCP" Reg4,
+    0x2f Croo "CROO" Reg2,
+    0x30 Csiz "CSIZ" Reg2,
+    0x31 Cb "CB" Reg1,
+    0x32 Ldc "LDC" Reg3Imm6,
+    0x33 Log "LOG" Reg4,
+    0x34 Logd "LOGD" Reg4,
+    0x35 Mint "MINT" Reg2,
+    0x36 Rvrt "RVRT" Reg1,
+    0x37 Scwq "SCWQ" Reg3,
+    0x38 Srw "SRW" Reg3,
+    0x39 Srwq "SRWQ" Reg4,
+    0x3a Sww "SWW" Reg3,
+    0x3b Swwq "SWWQ" Reg4,
+    0x3c Tr "TR" Reg3,
+    0x3d Tro "TRO" Reg4,
+    0x3e Eck1 "ECK1" Reg3,
+    0x3f Ecr1 "ECR1" Reg3,
+    0x40 Ed19 "ED19" Reg4,
+    0x41 K256 "K256" Reg3,
+    0x42 S256 "S256" Reg3,
+    0x43 Time "TIME" Reg2,
+    0x47 Noop "NOOP" None,
+    0x48 Flag "FLAG" Reg1,
+    0x49 Bal "BAL" Reg3,
+    0x4a Jmp "JMP" Reg1,
+    0x4b Jne "JNE" Reg3,
+    0x4c Smo "SMO" Reg4,
+    0x50 Addi "ADDI" Reg2Imm12,
+    0x51 Andi "ANDI" Reg2Imm12,
+    0x52 Divi "DIVI" Reg2Imm12,
+    0x53 Expi "EXPI" Reg2Imm12,
+    0x54 Modi "MODI" Reg2Imm12,
+    0x55 Muli "MULI" Reg2Imm12,
+    0x56 Ori "ORI" Reg2Imm12,
+    0x57 Slli "SLLI" Reg2Imm12,
+    0x58 Srli "SRLI" Reg2Imm12,
+    0x59 Subi "SUBI" Reg2Imm12,
+    0x5a Xori "XORI" Reg2Imm12,
+    0x5b Jnei "JNEI" Reg2Imm12,
+    0x5c Lb "LB" Reg2Imm12,
+    0x5d Lw "LW" Reg2Imm12,
+    0x5e Sb "SB" Reg2Imm12,
+    0x5f Sw "SW" Reg2Imm12,
+    0x60 Mcpi "MCPI" Reg2Imm12,
+    0x61 Gtf "GTF" Reg2Imm12,
+    0x70 Mcli "MCLI" Reg1Imm18,
+    0x71 Gm "GM" Reg1Imm18,
+    0x72 Movi "MOVI" Reg1Imm18,
+    0x73 Jnzi "JNZI" Reg1Imm18,
+    0x74 Jmpf "JMPF" Reg1Imm18,
+    0x75 Jmpb "JMPB" Reg1Imm18,
+    0x76 Jnzf "JNZF" Reg2Imm12,
+    0x77 Jnzb "JNZB" Reg2Imm12,
+    0x78 Jnef "JNEF" Reg3Imm6,
+    0x79 Jneb "JNEB" Reg3Imm6,
+    0x90 Ji "JI" Imm24,
+    0x91 Cfei "CFEI" Imm24,
+    0x92 Cfsi "CFSI" Imm24,
+    0x93 Cfe "CFE" Reg1,
+    0x94 Cfs "CFS" Reg1,
+    0x95 Pshl "PSHL" Imm24,
+    0x96 Pshh "PSHH" Imm24,
+    0x97 Popl "POPL" Imm24,
+    0x98 Poph "POPH" Imm24,
+    0xa0 Wdcm "WDCM" Reg3Imm6,
+    0xa1 Wqcm "WQCM" Reg3Imm6,
+    0xa2 Wdop "WDOP" Reg3Imm6,
+    0xa3 Wqop "WQOP" Reg3Imm6,
+    0xa4 Wdml "WDML" Reg3Imm6,
+    0xa5 Wqml "WQML" Reg3Imm6,
+    0xa6 Wddv "WDDV" Reg3Imm6,
+    0xa7 Wqdv "WQDV" Reg3Imm6,
+    0xa8 Wdmd "WDMD" Reg4,
+    0xa9 Wqmd "WQMD" Reg4,
+    0xaa Wdam "WDAM" Reg4,
+    0xab Wqam "WQAM" Reg4,
+    0xac Wdmm "WDMM" Reg4,
+    0xad Wqmm "WQMM" Reg4,
+    0xb0 Ecal "ECAL" Reg4,
+    0xb8 Bsiz "BSIZ" Reg2,
+    0xb9 Bldd "BLDD" Reg4,
+    0xba Ecop "ECOP" Reg4,
+    0xbb Epar "EPAR" Reg4,
+}
+
+impl fmt::Display for Opcode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unknown(val) => write!(f, "UNKNOWN(0x{val:02x})"),
+            opcode => f.write_str(opcode.mnemonic()),
+        }
+    }
+}
+
+/// A decoded FuelVM instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Instruction {
+    pub opcode: Opcode,
+    pub operands: Operands,
+}
+
+impl Instruction {
+    /// Decodes a 4 byte big endian instruction word.
+    pub fn decode(word: u32) -> Self {
+        let opcode = Opcode::from_u8((word >> 24) as u8);
+        let imm24 = word & 0xff_ffff;
+        let reg = |idx: u32| ((imm24 >> (18 - idx * 6)) & 0x3f) as u8;
+
+        let operands = match opcode.operand_format() {
+            Some(OperandFormat::None) => Operands::None,
+            Some(OperandFormat::Reg1) => Operands::Reg1(reg(0)),
+            Some(OperandFormat::Reg2) => Operands::Reg2(reg(0), reg(1)),
+            Some(OperandFormat::Reg3) => Operands::Reg3(reg(0), reg(1), reg(2)),
+            Some(OperandFormat::Reg4) => Operands::Reg4(reg(0), reg(1), reg(2), reg(3)),
+            Some(OperandFormat::Reg3Imm6) => Operands::Reg3Imm6(reg(0), reg(1), reg(2), reg(3)),
+            Some(OperandFormat::Reg2Imm12) => {
+                Operands::Reg2Imm12(reg(0), reg(1), (imm24 & 0xfff) as u16)
+            }
+            Some(OperandFormat::Reg1Imm18) => Operands::Reg1Imm18(reg(0), imm24 & 0x3_ffff),
+            Some(OperandFormat::Imm24) => Operands::Imm24(imm24),
+            None => Operands::Raw(imm24),
+        };
+
+        Self { opcode, operands }
+    }
+
+    /// Encodes back into a 4 byte big endian instruction word.
+    pub fn encode(&self) -> u32 {
+        let reg = |idx: u32, val: u8| u32::from(val & 0x3f) << (18 - idx * 6);
+
+        let operands = match self.operands {
+            Operands::None => 0,
+            Operands::Reg1(a) => reg(0, a),
+            Operands::Reg2(a, b) => reg(0, a) | reg(1, b),
+            Operands::Reg3(a, b, c) => reg(0, a) | reg(1, b) | reg(2, c),
+            Operands::Reg4(a, b, c, d) | Operands::Reg3Imm6(a, b, c, d) => {
+                reg(0, a) | reg(1, b) | reg(2, c) | reg(3, d)
+            }
+            Operands::Reg2Imm12(a, b, imm) => reg(0, a) | reg(1, b) | u32::from(imm & 0xfff),
+            Operands::Reg1Imm18(a, imm) => reg(0, a) | (imm & 0x3_ffff),
+            Operands::Imm24(imm) | Operands::Raw(imm) => imm & 0xff_ffff,
+        };
+
+        (u32::from(self.opcode.to_u8()) << 24) | operands
+    }
+}
+
+struct Reg(u8);
+
+impl fmt::Display for Reg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const RESERVED: [&str; 16] = [
+            "zero", "one", "of", "pc", "ssp", "sp", "fp", "hp", "err", "ggas", "cgas", "bal", "is",
+            "ret", "retl", "flag",
+        ];
+
+        match RESERVED.get(self.0 as usize) {
+            Some(name) => write!(f, "${name}"),
+            None => write!(f, "$r{}", self.0),
+        }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.opcode)?;
+
+        match self.operands {
+            Operands::None => Ok(()),
+            Operands::Reg1(a) => write!(f, " {}", Reg(a)),
+            Operands::Reg2(a, b) => write!(f, " {} {}", Reg(a), Reg(b)),
+            Operands::Reg3(a, b, c) => write!(f, " {} {} {}", Reg(a), Reg(b), Reg(c)),
+            Operands::Reg4(a, b, c, d) => {
+                write!(f, " {} {} {} {}", Reg(a), Reg(b), Reg(c), Reg(d))
+            }
+            Operands::Reg3Imm6(a, b, c, imm) => {
+                write!(f, " {} {} {} {imm}", Reg(a), Reg(b), Reg(c))
+            }
+            Operands::Reg2Imm12(a, b, imm) => write!(f, " {} {} {imm}", Reg(a), Reg(b)),
+            Operands::Reg1Imm18(a, imm) => write!(f, " {} {imm}", Reg(a)),
+            Operands::Imm24(imm) => write!(f, " {imm}"),
+            Operands::Raw(imm) => write!(f, " 0x{imm:06x}"),
+        }
+    }
+}
+
+/// Decodes bytecode into instructions.
+///
+/// Every 4 byte word is decoded, so data sections that are embedded in the bytecode come out as
+/// instructions as well. Trailing bytes that don't make up a full word are ignored.
+pub fn disassemble(bytecode: &[u8]) -> Vec<Instruction> {
+    bytecode
+        .chunks_exact(4)
+        .map(|word| Instruction::decode(u32::from_be_bytes(word.try_into().unwrap())))
+        .collect()
+}
+
+/// Textual listing of bytecode with the byte offset and the raw word of each instruction.
+pub fn disassembly_listing(bytecode: &[u8]) -> String {
+    disassemble(bytecode)
+        .iter()
+        .enumerate()
+        .map(|(i, instruction)| {
+            format!(
+                "{:06x}: {:08x}  {instruction}\n",
+                i * 4,
+                instruction.encode()
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode() {
+        // prelude of a sway script: move $r16 $pc, followed by ret $one and lw $r16 $fp 73
+        let bytecode = hex_literal::hex!("1a403000 24040000 5d4060490000");
+        let instructions = disassemble(&bytecode);
+
+        assert_eq!(
+            instructions,
+            [
+                Instruction {
+                    opcode: Opcode::Move,
+                    operands: Operands::Reg2(16, 3),
+                },
+                Instruction {
+                    opcode: Opcode::Ret,
+                    operands: Operands::Reg1(1),
+                },
+                Instruction {
+                    opcode: Opcode::Lw,
+                    operands: Operands::Reg2Imm12(16, 6, 73),
+                },
+            ]
+        );
+        assert_eq!(
+            disassembly_listing(&bytecode),
+            "000000: 1a403000  MOVE $r16 $pc\n\
+             000004: 24040000  RET $one\n\
+             000008: 5d406049  LW $r16 $fp 73\n"
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for opcode in 0..=u8::MAX {
+            let word = (u32::from(opcode) << 24) | 0x41_0c3f;
+            let instruction = Instruction::decode(word);
+
+            assert_eq!(instruction.opcode.to_u8(), opcode);
+            assert_eq!(Instruction::decode(instruction.encode()), instruction);
+        }
+
+        for opcode in Opcode::ALL {
+            assert_eq!(Opcode::from_u8(opcode.to_u8()), *opcode);
+        }
+    }
+
+    #[test]
+    fn test_unknown() {
+        let instruction = Instruction::decode(0xff12_3456);
+
+        assert_eq!(instruction.opcode, Opcode::Unknown(0xff));
+        assert_eq!(instruction.encode(), 0xff12_3456);
+        assert_eq!(instruction.to_string(), "UNKNOWN(0xff) 0x123456");
+    }
+}
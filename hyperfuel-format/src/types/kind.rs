@@ -0,0 +1,619 @@
+use crate::{Error, Result};
+
+use super::{
+    Address, ContractId, Data, Hash, Input, InputType, Nonce, Output, OutputType, Quantity,
+    Receipt, ReceiptType, UInt,
+};
+
+fn required<T: Clone>(value: &Option<T>, kind: &'static str, field: &'static str) -> Result<T> {
+    value.clone().ok_or(Error::MissingField { kind, field })
+}
+
+/// Quantities are stored without leading zeros, so the nonce is padded back to 32 bytes.
+fn quantity_nonce(nonce: &Quantity) -> Result<Nonce> {
+    let got = nonce.len();
+    if got > 32 {
+        return Err(Error::UnexpectedLength { expected: 32, got });
+    }
+
+    let mut buf = [0; 32];
+    buf[32 - got..].copy_from_slice(nonce);
+    Ok(buf.into())
+}
+
+/// A receipt with the fields of its [`ReceiptType`].
+///
+/// `id` is the contract that produced the receipt, read from `contract_id`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReceiptKind {
+    /// A contract called another contract, `to`, forwarding `amount` of `asset_id` and `gas`.
+    Call {
+        id: ContractId,
+        to: ContractId,
+        amount: UInt,
+        asset_id: Hash,
+        gas: UInt,
+        param1: UInt,
+        param2: UInt,
+        pc: UInt,
+        is: UInt,
+    },
+    /// A contract returned the word `val`.
+    Return {
+        id: ContractId,
+        val: UInt,
+        pc: UInt,
+        is: UInt,
+    },
+    /// A contract returned `len` bytes of data from memory at `ptr`.
+    ReturnData {
+        id: ContractId,
+        ptr: UInt,
+        len: UInt,
+        digest: Hash,
+        data: Option<Data>,
+        pc: UInt,
+        is: UInt,
+    },
+    /// A contract panicked, `reason` holds the panic reason and the instruction.
+    Panic {
+        id: ContractId,
+        reason: UInt,
+        pc: UInt,
+        is: UInt,
+    },
+    /// A contract reverted with the value of `ra`, see [`super::RevertSignal`].
+    Revert {
+        id: ContractId,
+        ra: UInt,
+        pc: UInt,
+        is: UInt,
+    },
+    /// A contract logged the values of four registers.
+    Log {
+        id: ContractId,
+        ra: UInt,
+        rb: UInt,
+        rc: UInt,
+        rd: UInt,
+        pc: UInt,
+        is: UInt,
+    },
+    /// A contract logged `len` bytes of data from memory at `ptr`, Sway programs put the log id
+    /// into `rb`.
+    LogData {
+        id: ContractId,
+        ra: UInt,
+        rb: UInt,
+        ptr: UInt,
+        len: UInt,
+        digest: Hash,
+        data: Option<Data>,
+        pc: UInt,
+        is: UInt,
+    },
+    /// A contract transferred `amount` of `asset_id` to another contract, `to`.
+    Transfer {
+        id: ContractId,
+        to: ContractId,
+        amount: UInt,
+        asset_id: Hash,
+        pc: UInt,
+        is: UInt,
+    },
+    /// A contract transferred `amount` of `asset_id` to an address, `to`.
+    TransferOut {
+        id: ContractId,
+        to: Address,
+        amount: UInt,
+        asset_id: Hash,
+        pc: UInt,
+        is: UInt,
+    },
+    /// The script finished with `result`, after using `gas_used`.
+    ScriptResult { result: UInt, gas_used: UInt },
+    /// A message was sent from `sender` to `recipient` on the base layer.
+    MessageOut {
+        sender: Address,
+        recipient: Address,
+        amount: UInt,
+        nonce: Nonce,
+        len: UInt,
+        digest: Hash,
+        data: Option<Data>,
+    },
+    /// A contract minted `val` of the asset with `sub_id`.
+    Mint {
+        sub_id: Hash,
+        contract_id: ContractId,
+        val: UInt,
+        pc: UInt,
+        is: UInt,
+    },
+    /// A contract burned `val` of the asset with `sub_id`.
+    Burn {
+        sub_id: Hash,
+        contract_id: ContractId,
+        val: UInt,
+        pc: UInt,
+        is: UInt,
+    },
+}
+
+impl TryFrom<&Receipt> for ReceiptKind {
+    type Error = Error;
+
+    fn try_from(r: &Receipt) -> Result<Self> {
+        macro_rules! req {
+            ($kind:literal, $field:ident) => {
+                required(&r.$field, $kind, stringify!($field))?
+            };
+        }
+        macro_rules! id {
+            ($kind:literal) => {
+                required(&r.contract_id, $kind, "contract_id")?
+            };
+        }
+
+        let kind = match r.receipt_type {
+            ReceiptType::Call => Self::Call {
+                id: id!("Call"),
+                to: req!("Call", to),
+                amount: req!("Call", amount),
+                asset_id: req!("Call", asset_id),
+                gas: req!("Call", gas),
+                param1: req!("Call", param1),
+                param2: req!("Call", param2),
+                pc: req!("Call", pc),
+                is: req!("Call", is),
+            },
+            ReceiptType::Return => Self::Return {
+                id: id!("Return"),
+                val: req!("Return", val),
+                pc: req!("Return", pc),
+                is: req!("Return", is),
+            },
+            ReceiptType::ReturnData => Self::ReturnData {
+                id: id!("ReturnData"),
+                ptr: req!("ReturnData", ptr),
+                len: req!("ReturnData", len),
+                digest: req!("ReturnData", digest),
+                data: r.data.clone(),
+                pc: req!("ReturnData", pc),
+                is: req!("ReturnData", is),
+            },
+            ReceiptType::Panic => Self::Panic {
+                id: id!("Panic"),
+                reason: req!("Panic", reason),
+                pc: req!("Panic", pc),
+                is: req!("Panic", is),
+            },
+            ReceiptType::Revert => Self::Revert {
+                id: id!("Revert"),
+                ra: req!("Revert", ra),
+                pc: req!("Revert", pc),
+                is: req!("Revert", is),
+            },
+            ReceiptType::Log => Self::Log {
+                id: id!("Log"),
+                ra: req!("Log", ra),
+                rb: req!("Log", rb),
+                rc: req!("Log", rc),
+                rd: req!("Log", rd),
+                pc: req!("Log", pc),
+                is: req!("Log", is),
+            },
+            ReceiptType::LogData => Self::LogData {
+                id: id!("LogData"),
+                ra: req!("LogData", ra),
+                rb: req!("LogData", rb),
+                ptr: req!("LogData", ptr),
+                len: req!("LogData", len),
+                digest: req!("LogData", digest),
+                data: r.data.clone(),
+                pc: req!("LogData", pc),
+                is: req!("LogData", is),
+            },
+            ReceiptType::Transfer => Self::Transfer {
+                id: id!("Transfer"),
+                to: req!("Transfer", to),
+                amount: req!("Transfer", amount),
+                asset_id: req!("Transfer", asset_id),
+                pc: req!("Transfer", pc),
+                is: req!("Transfer", is),
+            },
+            ReceiptType::TransferOut => Self::TransferOut {
+                id: id!("TransferOut"),
+                to: req!("TransferOut", to_address),
+                amount: req!("TransferOut", amount),
+                asset_id: req!("TransferOut", asset_id),
+                pc: req!("TransferOut", pc),
+                is: req!("TransferOut", is),
+            },
+            ReceiptType::ScriptResult => Self::ScriptResult {
+                result: req!("ScriptResult", result),
+                gas_used: req!("ScriptResult", gas_used),
+            },
+            ReceiptType::MessageOut => Self::MessageOut {
+                sender: req!("MessageOut", sender),
+                recipient: req!("MessageOut", recipient),
+                amount: req!("MessageOut", amount),
+                nonce: quantity_nonce(&req!("MessageOut", nonce))?,
+                len: req!("MessageOut", len),
+                digest: req!("MessageOut", digest),
+                data: r.data.clone(),
+            },
+            ReceiptType::Mint => Self::Mint {
+                sub_id: req!("Mint", sub_id),
+                contract_id: req!("Mint", contract_id),
+                val: req!("Mint", val),
+                pc: req!("Mint", pc),
+                is: req!("Mint", is),
+            },
+            ReceiptType::Burn => Self::Burn {
+                sub_id: req!("Burn", sub_id),
+                contract_id: req!("Burn", contract_id),
+                val: req!("Burn", val),
+                pc: req!("Burn", pc),
+                is: req!("Burn", is),
+            },
+            ReceiptType::Unknown(val) => return Err(Error::UnknownReceiptType(val.to_string())),
+        };
+
+        Ok(kind)
+    }
+}
+
+/// An input with the fields of its [`InputType`].
+///
+/// Coins and messages are spent either with a signature, then `witness_index` is set, or by a
+/// predicate, then `predicate`, `predicate_data` and `predicate_gas_used` are set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputKind {
+    /// A coin owned by `owner`.
+    Coin {
+        utxo_id: Hash,
+        owner: Address,
+        amount: UInt,
+        asset_id: Hash,
+        tx_pointer_block_height: UInt,
+        tx_pointer_tx_index: UInt,
+        witness_index: Option<UInt>,
+        predicate_gas_used: Option<UInt>,
+        predicate: Option<Data>,
+        predicate_data: Option<Data>,
+    },
+    /// A contract whose state the transaction reads or changes.
+    Contract {
+        utxo_id: Hash,
+        balance_root: Hash,
+        state_root: Hash,
+        tx_pointer_block_height: UInt,
+        tx_pointer_tx_index: UInt,
+        contract: ContractId,
+    },
+    /// A message from the base layer, data messages carry `data`.
+    Message {
+        sender: Address,
+        recipient: Address,
+        amount: UInt,
+        nonce: Nonce,
+        witness_index: Option<UInt>,
+        predicate_gas_used: Option<UInt>,
+        data: Option<Data>,
+        predicate: Option<Data>,
+        predicate_data: Option<Data>,
+    },
+}
+
+impl TryFrom<&Input> for InputKind {
+    type Error = Error;
+
+    fn try_from(i: &Input) -> Result<Self> {
+        macro_rules! req {
+            ($kind:literal, $field:ident) => {
+                required(&i.$field, $kind, stringify!($field))?
+            };
+        }
+
+        let kind = match i.input_type {
+            InputType::InputCoin => Self::Coin {
+                utxo_id: req!("Coin", utxo_id),
+                owner: req!("Coin", owner),
+                amount: req!("Coin", amount),
+                asset_id: req!("Coin", asset_id),
+                tx_pointer_block_height: req!("Coin", tx_pointer_block_height),
+                tx_pointer_tx_index: req!("Coin", tx_pointer_tx_index),
+                witness_index: i.witness_index,
+                predicate_gas_used: i.predicate_gas_used,
+                predicate: i.predicate.clone(),
+                predicate_data: i.predicate_data.clone(),
+            },
+            InputType::InputContract => Self::Contract {
+                utxo_id: req!("Contract", utxo_id),
+                balance_root: req!("Contract", balance_root),
+                state_root: req!("Contract", state_root),
+                tx_pointer_block_height: req!("Contract", tx_pointer_block_height),
+                tx_pointer_tx_index: req!("Contract", tx_pointer_tx_index),
+                contract: req!("Contract", contract),
+            },
+            InputType::InputMessage => Self::Message {
+                sender: req!("Message", sender),
+                recipient: req!("Message", recipient),
+                amount: req!("Message", amount),
+                nonce: req!("Message", nonce).as_ref().try_into()?,
+                witness_index: i.witness_index,
+                predicate_gas_used: i.predicate_gas_used,
+                data: i.data.clone(),
+                predicate: i.predicate.clone(),
+                predicate_data: i.predicate_data.clone(),
+            },
+            InputType::Unknown(val) => return Err(Error::UnknownInputType(val.to_string())),
+        };
+
+        Ok(kind)
+    }
+}
+
+/// An output with the fields of its [`OutputType`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OutputKind {
+    /// A coin sent to `to`.
+    Coin {
+        to: Address,
+        amount: UInt,
+        asset_id: Hash,
+    },
+    /// The state of the contract of the input at `input_index` after the transaction.
+    Contract {
+        input_index: UInt,
+        balance_root: Hash,
+        state_root: Hash,
+    },
+    /// The change of `asset_id` left after the transaction, sent to `to`.
+    Change {
+        to: Address,
+        amount: UInt,
+        asset_id: Hash,
+    },
+    /// A coin whose recipient and amount are set during execution, by a `TransferOut`.
+    Variable {
+        to: Address,
+        amount: UInt,
+        asset_id: Hash,
+    },
+    /// The contract deployed by a create transaction.
+    ContractCreated {
+        contract: ContractId,
+        state_root: Hash,
+    },
+}
+
+impl TryFrom<&Output> for OutputKind {
+    type Error = Error;
+
+    fn try_from(o: &Output) -> Result<Self> {
+        macro_rules! req {
+            ($kind:literal, $field:ident) => {
+                required(&o.$field, $kind, stringify!($field))?
+            };
+        }
+
+        let kind = match o.output_type {
+            OutputType::CoinOutput => Self::Coin {
+                to: req!("Coin", to),
+                amount: req!("Coin", amount),
+                asset_id: req!("Coin", asset_id),
+            },
+            OutputType::ContractOutput => Self::Contract {
+                input_index: req!("Contract", input_index),
+                balance_root: req!("Contract", balance_root),
+                state_root: req!("Contract", state_root),
+            },
+            OutputType::ChangeOutput => Self::Change {
+                to: req!("Change", to),
+                amount: req!("Change", amount),
+                asset_id: req!("Change", asset_id),
+            },
+            OutputType::VariableOutput => Self::Variable {
+                to: req!("Variable", to),
+                amount: req!("Variable", amount),
+                asset_id: req!("Variable", asset_id),
+            },
+            OutputType::ContractCreated => Self::ContractCreated {
+                contract: req!("ContractCreated", contract),
+                state_root: req!("ContractCreated", state_root),
+            },
+            OutputType::Unknown(val) => return Err(Error::UnknownOutputType(val.to_string())),
+        };
+
+        Ok(kind)
+    }
+}
+
+impl Receipt {
+    /// Converts the receipt into a [`ReceiptKind`], erroring if a field that its type requires
+    /// is missing or if its type is unknown.
+    pub fn kind(&self) -> Result<ReceiptKind> {
+        self.try_into()
+    }
+}
+
+impl Input {
+    /// Converts the input into an [`InputKind`], erroring if a field that its type requires is
+    /// missing or if its type is unknown.
+    pub fn kind(&self) -> Result<InputKind> {
+        self.try_into()
+    }
+}
+
+impl Output {
+    /// Converts the output into an [`OutputKind`], erroring if a field that its type requires
+    /// is missing or if its type is unknown.
+    pub fn kind(&self) -> Result<OutputKind> {
+        self.try_into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_receipt_kind() {
+        let receipt = Receipt {
+            receipt_type: ReceiptType::Log,
+            contract_id: Some([1; 32].into()),
+            ra: Some(1.into()),
+            rb: Some(2.into()),
+            rc: Some(3.into()),
+            rd: Some(4.into()),
+            pc: Some(5.into()),
+            is: Some(6.into()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            receipt.kind().unwrap(),
+            ReceiptKind::Log {
+                id: [1; 32].into(),
+                ra: 1.into(),
+                rb: 2.into(),
+                rc: 3.into(),
+                rd: 4.into(),
+                pc: 5.into(),
+                is: 6.into(),
+            }
+        );
+
+        let receipt = Receipt {
+            rd: None,
+            ..receipt
+        };
+        assert!(matches!(
+            receipt.kind(),
+            Err(Error::MissingField {
+                kind: "Log",
+                field: "rd"
+            })
+        ));
+
+        let receipt = Receipt {
+            receipt_type: ReceiptType::ScriptResult,
+            result: Some(0.into()),
+            gas_used: Some(100.into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            receipt.kind().unwrap(),
+            ReceiptKind::ScriptResult {
+                result: 0.into(),
+                gas_used: 100.into(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_nonce() {
+        let receipt = Receipt {
+            receipt_type: ReceiptType::MessageOut,
+            sender: Some([1; 32].into()),
+            recipient: Some([2; 32].into()),
+            amount: Some(5.into()),
+            nonce: Some(7u64.into()),
+            len: Some(0.into()),
+            digest: Some([3; 32].into()),
+            ..Default::default()
+        };
+        let mut nonce = [0; 32];
+        nonce[31] = 7;
+        match receipt.kind().unwrap() {
+            ReceiptKind::MessageOut { nonce: got, .. } => assert_eq!(got, Nonce::from(nonce)),
+            kind => panic!("unexpected kind {kind:?}"),
+        }
+
+        let input = Input {
+            input_type: InputType::InputMessage,
+            sender: Some([1; 32].into()),
+            recipient: Some([2; 32].into()),
+            amount: Some(5.into()),
+            nonce: Some(vec![7; 31].into()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            input.kind(),
+            Err(Error::UnexpectedLength {
+                expected: 32,
+                got: 31
+            })
+        ));
+    }
+
+    #[test]
+    fn test_input_kind() {
+        let input = Input {
+            input_type: InputType::InputContract,
+            utxo_id: Some([1; 32].into()),
+            balance_root: Some([2; 32].into()),
+            state_root: Some([3; 32].into()),
+            tx_pointer_block_height: Some(7.into()),
+            tx_pointer_tx_index: Some(1.into()),
+            contract: Some([4; 32].into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            input.kind().unwrap(),
+            InputKind::Contract {
+                utxo_id: [1; 32].into(),
+                balance_root: [2; 32].into(),
+                state_root: [3; 32].into(),
+                tx_pointer_block_height: 7.into(),
+                tx_pointer_tx_index: 1.into(),
+                contract: [4; 32].into(),
+            }
+        );
+
+        let input = Input {
+            input_type: InputType::InputCoin,
+            ..input
+        };
+        assert!(matches!(
+            input.kind(),
+            Err(Error::MissingField {
+                kind: "Coin",
+                field: "owner"
+            })
+        ));
+    }
+
+    #[test]
+    fn test_output_kind() {
+        let output = Output {
+            output_type: OutputType::ChangeOutput,
+            to: Some([1; 32].into()),
+            amount: Some(5.into()),
+            asset_id: Some([2; 32].into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            output.kind().unwrap(),
+            OutputKind::Change {
+                to: [1; 32].into(),
+                amount: 5.into(),
+                asset_id: [2; 32].into(),
+            }
+        );
+
+        let output = Output {
+            output_type: OutputType::ContractCreated,
+            ..output
+        };
+        assert!(matches!(
+            output.kind(),
+            Err(Error::MissingField {
+                kind: "ContractCreated",
+                field: "contract"
+            })
+        ));
+    }
+}
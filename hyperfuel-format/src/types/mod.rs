@@ -1,27 +1,45 @@
 use serde::{Deserialize, Serialize};
 
+mod address;
 mod data;
 mod fixed_size_data;
 mod hex;
+mod id;
 mod input_type;
+mod instruction;
+mod kind;
 mod output_type;
+mod packed;
+mod panic_reason;
 mod quantity;
 mod receipt_type;
+mod revert_signal;
+mod timestamp;
 mod transaction_status;
 mod transaction_type;
 mod uint;
 mod util;
+mod witness;
 
+pub use address::{checksum_encode, FUEL_BECH32_HRP};
 pub use data::Data;
 pub use fixed_size_data::FixedSizeData;
 pub use hex::Hex;
+pub use id::{asset_id, bytecode_root, contract_id, predicate_owner, UtxoId, CONTRACT_ID_SEED};
 pub use input_type::InputType;
+pub use instruction::{disassemble, disassembly_listing, Instruction, Opcode, Operands};
+pub use kind::{InputKind, OutputKind, ReceiptKind};
 pub use output_type::OutputType;
+pub use packed::{pack_hashes, unpack_hashes};
+pub use panic_reason::{PanicInstruction, PanicReason};
 pub use quantity::Quantity;
 pub use receipt_type::ReceiptType;
+pub use revert_signal::RevertSignal;
+pub use timestamp::{Timestamp, TAI64_UNIX_EPOCH};
 pub use transaction_status::TransactionStatus;
 pub use transaction_type::TransactionType;
 pub use uint::UInt;
+pub use witness::{parse_witnesses, recover_signer};
 
 /// The header contains metadata about a certain block.
 #[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -48,7 +66,7 @@ pub struct BlockHeader {
     pub height: UInt,
     /// The merkle root of all previous consensus header hashes (not including this block).
     pub prev_root: Hash,
-    /// The timestamp for the block.
+    /// The timestamp for the block, a TAI64 label. See [`BlockHeader::timestamp`].
     pub time: UInt,
     /// The hash of the serialized application header for this block.
     pub application_hash: Hash,
@@ -60,7 +78,9 @@ pub struct BlockHeader {
 pub struct Transaction {
     pub block_height: UInt,
     pub id: Hash,
+    /// Bincode encoded list in Arrow.
     pub input_asset_ids: Option<Vec<Hash>>,
+    /// Bincode encoded list in Arrow.
     pub input_contracts: Option<Vec<ContractId>>,
     pub input_contract_utxo_id: Option<Hash>,
     pub input_contract_balance_root: Option<Hash>,
@@ -83,9 +103,11 @@ pub struct Transaction {
     pub output_contract_input_index: Option<UInt>,
     pub output_contract_balance_root: Option<Hash>,
     pub output_contract_state_root: Option<Hash>,
+    /// All witnesses of the transaction, see [`Transaction::witness_list`].
     pub witnesses: Option<Data>,
     pub receipts_root: Option<Hash>,
     pub status: TransactionStatus,
+    /// TAI64 label of the block time, see [`Transaction::timestamp`].
     pub time: UInt,
     pub reason: Option<String>,
     pub script: Option<Data>,
@@ -94,6 +116,7 @@ pub struct Transaction {
     pub bytecode_root: Option<Hash>,
     pub subsection_index: Option<UInt>,
     pub subsections_number: Option<UInt>,
+    /// Packed merkle proof hashes, see [`Transaction::proof_set_list`].
     pub proof_set: Option<Data>,
     pub consensus_parameters_upgrade_purpose_witness_index: Option<UInt>,
     pub consensus_parameters_upgrade_purpose_checksum: Option<Data>,
@@ -140,6 +163,24 @@ pub struct Receipt {
     pub sub_id: Option<Hash>,
 }
 
+impl Receipt {
+    /// Returns the decoded `reason` of a Panic receipt.
+    pub fn panic_instruction(&self) -> Option<PanicInstruction> {
+        match self.receipt_type {
+            ReceiptType::Panic => self.reason.map(|reason| (*reason).into()),
+            _ => None,
+        }
+    }
+
+    /// Returns the decoded `ra` of a Revert receipt.
+    pub fn revert_signal(&self) -> Option<RevertSignal> {
+        match self.receipt_type {
+            ReceiptType::Revert => self.ra.map(|ra| (*ra).into()),
+            _ => None,
+        }
+    }
+}
+
 /// An object representing all possible types of inputs. InputCoin, InputContract, InputMessage
 #[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -194,3 +235,6 @@ pub type Address = FixedSizeData<32>;
 
 /// contract id is also a 32 byte hash
 pub type ContractId = FixedSizeData<32>;
+
+/// nonce of a message, 32 bytes of data
+pub type Nonce = FixedSizeData<32>;
@@ -5,6 +5,7 @@ use std::fmt;
 use std::result::Result as StdResult;
 use std::str::FromStr;
 
+use super::quantity::{decode_u8_hex, encode_hex, u8_hex_str};
 use super::Hex;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -15,34 +16,30 @@ pub enum OutputType {
     ChangeOutput,
     VariableOutput,
     ContractCreated,
+    /// A discriminant that was added to Fuel after this version of the library.
+    Unknown(u8),
+}
+
+impl fmt::Display for OutputType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unknown(val) => write!(f, "Unknown({val})"),
+            val => f.write_str(val.name()),
+        }
+    }
 }
 
 impl FromStr for OutputType {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self> {
-        match s {
-            "0x0" => Ok(Self::CoinOutput),
-            "0x1" => Ok(Self::ContractOutput),
-            "0x2" => Ok(Self::ChangeOutput),
-            "0x3" => Ok(Self::VariableOutput),
-            "0x4" => Ok(Self::ContractCreated),
-            _ => Err(Error::UnknownOutputType(s.to_owned())),
-        }
+        decode_u8_hex(s)
+            .map(Self::from_u8)
+            .ok_or_else(|| Error::UnknownOutputType(s.to_owned()))
     }
 }
 
 impl OutputType {
-    pub fn as_str(&self) -> &'static str {
-        match self {
-            Self::CoinOutput => "0x0",
-            Self::ContractOutput => "0x1",
-            Self::ChangeOutput => "0x2",
-            Self::VariableOutput => "0x3",
-            Self::ContractCreated => "0x4",
-        }
-    }
-
     pub fn as_u8(&self) -> u8 {
         match self {
             Self::CoinOutput => 0,
@@ -50,19 +47,38 @@ impl OutputType {
             Self::ChangeOutput => 2,
             Self::VariableOutput => 3,
             Self::ContractCreated => 4,
+            Self::Unknown(val) => *val,
         }
     }
 
-    pub fn from_u8(n: u8) -> Result<Self> {
+    pub fn from_u8(n: u8) -> Self {
         match n {
-            0 => Ok(Self::CoinOutput),
-            1 => Ok(Self::ContractOutput),
-            2 => Ok(Self::ChangeOutput),
-            3 => Ok(Self::VariableOutput),
-            4 => Ok(Self::ContractCreated),
-            _ => Err(Error::UnknownOutputType(n.to_string())),
+            0 => Self::CoinOutput,
+            1 => Self::ContractOutput,
+            2 => Self::ChangeOutput,
+            3 => Self::VariableOutput,
+            4 => Self::ContractCreated,
+            n => Self::Unknown(n),
         }
     }
+
+    /// Name of the variant, e.g. "ContractOutput". Unknown discriminants are named "Unknown".
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::CoinOutput => "CoinOutput",
+            Self::ContractOutput => "ContractOutput",
+            Self::ChangeOutput => "ChangeOutput",
+            Self::VariableOutput => "VariableOutput",
+            Self::ContractCreated => "ContractCreated",
+            Self::Unknown(_) => "Unknown",
+        }
+    }
+
+    /// Hex string of the type.
+    #[deprecated(note = "use `Hex::encode_hex` instead")]
+    pub fn as_str(&self) -> &'static str {
+        u8_hex_str(self.as_u8())
+    }
 }
 
 struct OutputTypeVisitor;
@@ -96,13 +112,13 @@ impl Serialize for OutputType {
     where
         S: Serializer,
     {
-        serializer.serialize_str(self.as_str())
+        serializer.serialize_str(&self.encode_hex())
     }
 }
 
 impl Hex for OutputType {
     fn encode_hex(&self) -> String {
-        self.as_str().to_owned()
+        encode_hex(&[self.as_u8()])
     }
 
     fn decode_hex(hex: &str) -> Result<Self> {
@@ -125,8 +141,20 @@ mod tests {
     }
 
     #[test]
-    #[should_panic]
+    fn test_u8_round_trip() {
+        for val in 0..=u8::MAX {
+            assert_eq!(OutputType::from_u8(val).as_u8(), val);
+        }
+    }
+
+    #[test]
     fn test_de_unknown() {
-        assert_de_tokens(&OutputType::CoinOutput, &[Token::Str("0x5")]);
+        assert_tokens(&OutputType::Unknown(5), &[Token::Str("0x5")]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_de_invalid() {
+        assert_de_tokens(&OutputType::CoinOutput, &[Token::Str("5")]);
     }
 }
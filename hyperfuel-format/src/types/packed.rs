@@ -0,0 +1,50 @@
+use super::{Hash, Transaction};
+use crate::{Error, Result};
+
+/// Splits a packed list of hashes, like `proof_set`, into the hashes.
+///
+/// Packed lists are the 32 byte hashes concatenated, so the length has to be a multiple of 32.
+pub fn unpack_hashes(buf: &[u8]) -> Result<Vec<Hash>> {
+    if !buf.len().is_multiple_of(32) {
+        return Err(Error::InvalidPackedHashes(buf.len()));
+    }
+
+    buf.chunks_exact(32).map(Hash::try_from).collect()
+}
+
+/// Concatenates `hashes` into a packed list, the inverse of [`unpack_hashes`].
+pub fn pack_hashes(hashes: &[Hash]) -> Vec<u8> {
+    hashes.iter().flat_map(|h| h.as_slice()).copied().collect()
+}
+
+impl Transaction {
+    /// Parses the `proof_set` field into the merkle proof hashes, see [`unpack_hashes`].
+    pub fn proof_set_list(&self) -> Result<Vec<Hash>> {
+        match &self.proof_set {
+            Some(proof_set) => unpack_hashes(proof_set.as_ref()),
+            None => Ok(Vec::new()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unpack_hashes() {
+        let hashes = vec![Hash::from([1; 32]), Hash::from([2; 32])];
+        let buf = pack_hashes(&hashes);
+
+        assert_eq!(buf.len(), 64);
+        assert_eq!(unpack_hashes(&buf).unwrap(), hashes);
+        assert_eq!(unpack_hashes(&[]).unwrap(), []);
+        assert!(unpack_hashes(&buf[..63]).is_err());
+
+        let tx = Transaction {
+            proof_set: Some(buf.into()),
+            ..Default::default()
+        };
+        assert_eq!(tx.proof_set_list().unwrap(), hashes);
+    }
+}
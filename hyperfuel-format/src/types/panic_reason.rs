@@ -0,0 +1,251 @@
+use std::fmt;
+
+use super::Instruction;
+
+/// Reason of a FuelVM panic, the `reason` of a Panic receipt holds it in its top byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PanicReason {
+    Revert,
+    OutOfGas,
+    TransactionValidity,
+    MemoryOverflow,
+    ArithmeticOverflow,
+    ContractNotFound,
+    MemoryOwnership,
+    NotEnoughBalance,
+    ExpectedInternalContext,
+    AssetIdNotFound,
+    InputNotFound,
+    OutputNotFound,
+    WitnessNotFound,
+    TransactionMaturity,
+    InvalidMetadataIdentifier,
+    MalformedCallStructure,
+    ReservedRegisterNotWritable,
+    InvalidFlags,
+    InvalidImmediateValue,
+    ExpectedCoinInput,
+    EcalError,
+    MemoryWriteOverlap,
+    ContractNotInInputs,
+    InternalBalanceOverflow,
+    ContractMaxSize,
+    ExpectedUnallocatedStack,
+    MaxStaticContractsReached,
+    TransferAmountCannotBeZero,
+    ExpectedOutputVariable,
+    ExpectedParentInternalContext,
+    PredicateReturnedNonOne,
+    ContractIdAlreadyDeployed,
+    ContractMismatch,
+    MessageDataTooLong,
+    ArithmeticError,
+    ContractInstructionNotAllowed,
+    TransferZeroCoins,
+    InvalidInstruction,
+    MemoryNotExecutable,
+    PolicyIsNotSet,
+    PolicyNotFound,
+    TooManyReceipts,
+    BalanceOverflow,
+    InvalidBlockHeight,
+    TooManySlots,
+    ExpectedNestedCaller,
+    MemoryGrowthOverlap,
+    UninitalizedMemoryAccess,
+    OverridingConsensusParameters,
+    UnknownStateTransactionBytecodeRoot,
+    OverridingStateTransactionBytecode,
+    BytecodeAlreadyUploaded,
+    ThePartIsNotSequentiallyConnected,
+    BlobIdAlreadyUploaded,
+    BlobNotFound,
+    GasCostNotDefined,
+    UnsupportedCurveId,
+    UnsupportedOperationType,
+    InvalidEllipticCurvePoint,
+    InputContractDoesNotExist,
+    /// A reason that was added to the VM after this version of the library.
+    Unknown(u8),
+}
+
+const PANIC_REASONS: [PanicReason; 60] = [
+    PanicReason::Revert,
+    PanicReason::OutOfGas,
+    PanicReason::TransactionValidity,
+    PanicReason::MemoryOverflow,
+    PanicReason::ArithmeticOverflow,
+    PanicReason::ContractNotFound,
+    PanicReason::MemoryOwnership,
+    PanicReason::NotEnoughBalance,
+    PanicReason::ExpectedInternalContext,
+    PanicReason::AssetIdNotFound,
+    PanicReason::InputNotFound,
+    PanicReason::OutputNotFound,
+    PanicReason::WitnessNotFound,
+    PanicReason::TransactionMaturity,
+    PanicReason::InvalidMetadataIdentifier,
+    PanicReason::MalformedCallStructure,
+    PanicReason::ReservedRegisterNotWritable,
+    PanicReason::InvalidFlags,
+    PanicReason::InvalidImmediateValue,
+    PanicReason::ExpectedCoinInput,
+    PanicReason::EcalError,
+    PanicReason::MemoryWriteOverlap,
+    PanicReason::ContractNotInInputs,
+    PanicReason::InternalBalanceOverflow,
+    PanicReason::ContractMaxSize,
+    PanicReason::ExpectedUnallocatedStack,
+    PanicReason::MaxStaticContractsReached,
+    PanicReason::TransferAmountCannotBeZero,
+    PanicReason::ExpectedOutputVariable,
+    PanicReason::ExpectedParentInternalContext,
+    PanicReason::PredicateReturnedNonOne,
+    PanicReason::ContractIdAlreadyDeployed,
+    PanicReason::ContractMismatch,
+    PanicReason::MessageDataTooLong,
+    PanicReason::ArithmeticError,
+    PanicReason::ContractInstructionNotAllowed,
+    PanicReason::TransferZeroCoins,
+    PanicReason::InvalidInstruction,
+    PanicReason::MemoryNotExecutable,
+    PanicReason::PolicyIsNotSet,
+    PanicReason::PolicyNotFound,
+    PanicReason::TooManyReceipts,
+    PanicReason::BalanceOverflow,
+    PanicReason::InvalidBlockHeight,
+    PanicReason::TooManySlots,
+    PanicReason::ExpectedNestedCaller,
+    PanicReason::MemoryGrowthOverlap,
+    PanicReason::UninitalizedMemoryAccess,
+    PanicReason::OverridingConsensusParameters,
+    PanicReason::UnknownStateTransactionBytecodeRoot,
+    PanicReason::OverridingStateTransactionBytecode,
+    PanicReason::BytecodeAlreadyUploaded,
+    PanicReason::ThePartIsNotSequentiallyConnected,
+    PanicReason::BlobIdAlreadyUploaded,
+    PanicReason::BlobNotFound,
+    PanicReason::GasCostNotDefined,
+    PanicReason::UnsupportedCurveId,
+    PanicReason::UnsupportedOperationType,
+    PanicReason::InvalidEllipticCurvePoint,
+    PanicReason::InputContractDoesNotExist,
+];
+
+impl PanicReason {
+    /// Reasons are numbered from 1, 0 is reserved.
+    pub fn from_u8(val: u8) -> Self {
+        match val.checked_sub(1) {
+            Some(idx) if (idx as usize) < PANIC_REASONS.len() => PANIC_REASONS[idx as usize],
+            _ => Self::Unknown(val),
+        }
+    }
+
+    pub fn to_u8(&self) -> u8 {
+        match self {
+            Self::Unknown(val) => *val,
+            reason => {
+                PANIC_REASONS
+                    .iter()
+                    .position(|r| r == reason)
+                    .expect("reason is in the table") as u8
+                    + 1
+            }
+        }
+    }
+}
+
+impl fmt::Display for PanicReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unknown(val) => write!(f, "Unknown({val})"),
+            reason => fmt::Debug::fmt(reason, f),
+        }
+    }
+}
+
+/// The packed `reason` of a Panic receipt: the panic reason in the top byte followed by the
+/// instruction that caused the panic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PanicInstruction {
+    pub reason: PanicReason,
+    /// The raw instruction that caused the panic.
+    pub instruction: u32,
+}
+
+impl PanicInstruction {
+    /// Decodes the instruction that caused the panic.
+    pub fn decoded_instruction(&self) -> Instruction {
+        Instruction::decode(self.instruction)
+    }
+}
+
+const REASON_OFFSET: u64 = 56;
+const INSTRUCTION_OFFSET: u64 = REASON_OFFSET - 32;
+
+impl From<u64> for PanicInstruction {
+    fn from(val: u64) -> Self {
+        Self {
+            reason: PanicReason::from_u8((val >> REASON_OFFSET) as u8),
+            instruction: (val >> INSTRUCTION_OFFSET) as u32,
+        }
+    }
+}
+
+impl From<PanicInstruction> for u64 {
+    fn from(val: PanicInstruction) -> Self {
+        (u64::from(val.reason.to_u8()) << REASON_OFFSET)
+            | (u64::from(val.instruction) << INSTRUCTION_OFFSET)
+    }
+}
+
+impl fmt::Display for PanicInstruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (instruction 0x{:08x})",
+            self.reason, self.instruction
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PanicInstruction, PanicReason, PANIC_REASONS};
+
+    #[test]
+    fn test_reason_u8() {
+        assert_eq!(PanicReason::from_u8(1), PanicReason::Revert);
+        assert_eq!(PanicReason::from_u8(8), PanicReason::NotEnoughBalance);
+        assert_eq!(
+            PanicReason::from_u8(0x3c),
+            PanicReason::InputContractDoesNotExist
+        );
+        assert_eq!(PanicReason::from_u8(0), PanicReason::Unknown(0));
+        assert_eq!(PanicReason::from_u8(0xff), PanicReason::Unknown(0xff));
+
+        for reason in PANIC_REASONS {
+            assert_eq!(PanicReason::from_u8(reason.to_u8()), reason);
+        }
+        assert_eq!(PanicReason::Unknown(0xff).to_u8(), 0xff);
+    }
+
+    #[test]
+    fn test_panic_instruction() {
+        // `tr` with zero coins.
+        let val = 0x253c_0000_0000_0000u64;
+        let panic = PanicInstruction::from(val);
+
+        assert_eq!(panic.reason, PanicReason::TransferZeroCoins);
+        assert_eq!(panic.instruction, 0x3c00_0000);
+        assert_eq!(u64::from(panic), val);
+        assert_eq!(
+            panic.to_string(),
+            "TransferZeroCoins (instruction 0x3c000000)"
+        );
+        assert_eq!(
+            panic.decoded_instruction().to_string(),
+            "TR $zero $zero $zero"
+        );
+    }
+}
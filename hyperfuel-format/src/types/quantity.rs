@@ -5,6 +5,7 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::borrow::Cow;
 use std::fmt;
 use std::result::Result as StdResult;
+use std::sync::OnceLock;
 
 #[derive(Clone, PartialEq, Eq, Hash, derive_more::From, derive_more::Into, derive_more::Deref)]
 pub struct Quantity(Box<[u8]>);
@@ -35,6 +36,27 @@ impl From<u64> for Quantity {
     }
 }
 
+/// Errors if the quantity doesn't fit in a `u64`.
+impl TryFrom<&Quantity> for u64 {
+    type Error = Error;
+
+    fn try_from(value: &Quantity) -> Result<Self> {
+        let start = value
+            .0
+            .iter()
+            .position(|b| *b != 0)
+            .unwrap_or(value.0.len());
+        let bytes = &value.0[start..];
+        if bytes.len() > 8 {
+            return Err(Error::UnexpectedQuantity(value.encode_hex()));
+        }
+
+        let mut buf = [0; 8];
+        buf[8 - bytes.len()..].copy_from_slice(bytes);
+        Ok(u64::from_be_bytes(buf))
+    }
+}
+
 impl From<&[u8]> for Quantity {
     fn from(buf: &[u8]) -> Self {
         assert!(!buf.is_empty());
@@ -126,6 +148,19 @@ pub fn decode_hex(value: &str) -> Result<Vec<u8>> {
     super::util::decode_hex(val.as_ref()).map_err(Error::DecodeHex)
 }
 
+/// Parses a hex quantity that fits in a `u8`, like the discriminants of the type enums.
+pub(crate) fn decode_u8_hex(value: &str) -> Option<u8> {
+    u8::from_str_radix(value.strip_prefix("0x")?, 16).ok()
+}
+
+/// Returns the hex string of a single byte as [`encode_hex`] writes it, e.g. "0xa".
+pub(crate) fn u8_hex_str(val: u8) -> &'static str {
+    static STRS: OnceLock<Vec<String>> = OnceLock::new();
+
+    STRS.get_or_init(|| (0..=u8::MAX).map(|v| encode_hex(&[v])).collect())[usize::from(val)]
+        .as_str()
+}
+
 pub fn encode_hex(buf: &[u8]) -> String {
     let hex_val = faster_hex::hex_string(buf);
 
@@ -180,6 +215,18 @@ mod tests {
         assert_de_tokens(&Quantity::default(), &[Token::Str("0x")]);
     }
 
+    #[test]
+    fn test_try_into_u64() {
+        assert_eq!(u64::try_from(&Quantity::default()).unwrap(), 0);
+        assert_eq!(u64::try_from(&Quantity::from(1234u64)).unwrap(), 1234);
+        assert_eq!(u64::try_from(&Quantity::from(u64::MAX)).unwrap(), u64::MAX);
+        assert_eq!(
+            u64::try_from(&Quantity::from(hex!("0000004200"))).unwrap(),
+            0x4200
+        );
+        assert!(u64::try_from(&Quantity::from(hex!("010000000000000000"))).is_err());
+    }
+
     #[test]
     fn test_from_vec_zero() {
         assert_eq!(Quantity::default(), Quantity::from(vec![0]))
@@ -5,6 +5,7 @@ use std::fmt;
 use std::result::Result as StdResult;
 use std::str::FromStr;
 
+use super::quantity::{decode_u8_hex, u8_hex_str};
 use super::Hex;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -23,25 +24,27 @@ pub enum ReceiptType {
     MessageOut,
     Mint,
     Burn,
+    /// A discriminant that was added to Fuel after this version of the library.
+    Unknown(u8),
 }
 
 impl ReceiptType {
-    pub fn from_u8(val: u8) -> Result<Self> {
+    pub fn from_u8(val: u8) -> Self {
         match val {
-            0 => Ok(Self::Call),
-            1 => Ok(Self::Return),
-            2 => Ok(Self::ReturnData),
-            3 => Ok(Self::Panic),
-            4 => Ok(Self::Revert),
-            5 => Ok(Self::Log),
-            6 => Ok(Self::LogData),
-            7 => Ok(Self::Transfer),
-            8 => Ok(Self::TransferOut),
-            9 => Ok(Self::ScriptResult),
-            10 => Ok(Self::MessageOut),
-            11 => Ok(Self::Mint),
-            12 => Ok(Self::Burn),
-            _ => Err(Error::UnknownReceiptType(val.to_string())),
+            0 => Self::Call,
+            1 => Self::Return,
+            2 => Self::ReturnData,
+            3 => Self::Panic,
+            4 => Self::Revert,
+            5 => Self::Log,
+            6 => Self::LogData,
+            7 => Self::Transfer,
+            8 => Self::TransferOut,
+            9 => Self::ScriptResult,
+            10 => Self::MessageOut,
+            11 => Self::Mint,
+            12 => Self::Burn,
+            val => Self::Unknown(val),
         }
     }
 
@@ -60,6 +63,53 @@ impl ReceiptType {
             Self::MessageOut => 10,
             Self::Mint => 11,
             Self::Burn => 12,
+            Self::Unknown(val) => *val,
+        }
+    }
+
+    /// Name of the variant, e.g. "Return". Unknown discriminants are named "Unknown".
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Call => "Call",
+            Self::Return => "Return",
+            Self::ReturnData => "ReturnData",
+            Self::Panic => "Panic",
+            Self::Revert => "Revert",
+            Self::Log => "Log",
+            Self::LogData => "LogData",
+            Self::Transfer => "Transfer",
+            Self::TransferOut => "TransferOut",
+            Self::ScriptResult => "ScriptResult",
+            Self::MessageOut => "MessageOut",
+            Self::Mint => "Mint",
+            Self::Burn => "Burn",
+            Self::Unknown(_) => "Unknown",
+        }
+    }
+
+    /// Hex string of the receipt type.
+    #[deprecated(note = "use `Hex::encode_hex` instead")]
+    pub fn as_str(&self) -> &'static str {
+        self.hex_str()
+    }
+
+    /// MessageOut, Mint and Burn are written as "0x10", "0x11" and "0x12" for compatibility with
+    /// earlier versions, so `Unknown(16)` to `Unknown(18)` don't round trip through strings.
+    fn hex_str(&self) -> &'static str {
+        match self {
+            Self::MessageOut => "0x10",
+            Self::Mint => "0x11",
+            Self::Burn => "0x12",
+            val => u8_hex_str(val.to_u8()),
+        }
+    }
+}
+
+impl fmt::Display for ReceiptType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unknown(val) => write!(f, "Unknown({val})"),
+            val => f.write_str(val.name()),
         }
     }
 }
@@ -67,42 +117,16 @@ impl ReceiptType {
 impl FromStr for ReceiptType {
     type Err = Error;
 
+    /// Accepts the hex encoding of the discriminant as well as the strings written by
+    /// [`Hex::encode_hex`], which differ for MessageOut, Mint and Burn.
     fn from_str(s: &str) -> Result<Self> {
         match s {
-            "0x0" => Ok(Self::Call),
-            "0x1" => Ok(Self::Return),
-            "0x2" => Ok(Self::ReturnData),
-            "0x3" => Ok(Self::Panic),
-            "0x4" => Ok(Self::Revert),
-            "0x5" => Ok(Self::Log),
-            "0x6" => Ok(Self::LogData),
-            "0x7" => Ok(Self::Transfer),
-            "0x8" => Ok(Self::TransferOut),
-            "0x9" => Ok(Self::ScriptResult),
             "0x10" => Ok(Self::MessageOut),
             "0x11" => Ok(Self::Mint),
             "0x12" => Ok(Self::Burn),
-            _ => Err(Error::UnknownReceiptType(s.to_owned())),
-        }
-    }
-}
-
-impl ReceiptType {
-    pub fn as_str(&self) -> &'static str {
-        match self {
-            Self::Call => "0x0",
-            Self::Return => "0x1",
-            Self::ReturnData => "0x2",
-            Self::Panic => "0x3",
-            Self::Revert => "0x4",
-            Self::Log => "0x5",
-            Self::LogData => "0x6",
-            Self::Transfer => "0x7",
-            Self::TransferOut => "0x8",
-            Self::ScriptResult => "0x9",
-            Self::MessageOut => "0x10",
-            Self::Mint => "0x11",
-            Self::Burn => "0x12",
+            s => decode_u8_hex(s)
+                .map(Self::from_u8)
+                .ok_or_else(|| Error::UnknownReceiptType(s.to_owned())),
         }
     }
 }
@@ -138,13 +162,13 @@ impl Serialize for ReceiptType {
     where
         S: Serializer,
     {
-        serializer.serialize_str(self.as_str())
+        serializer.serialize_str(&self.encode_hex())
     }
 }
 
 impl Hex for ReceiptType {
     fn encode_hex(&self) -> String {
-        self.as_str().to_owned()
+        self.hex_str().to_owned()
     }
 
     fn decode_hex(hex: &str) -> Result<Self> {
@@ -175,8 +199,36 @@ mod tests {
     }
 
     #[test]
-    #[should_panic]
+    fn test_de_hex_discriminant() {
+        assert_de_tokens(&ReceiptType::MessageOut, &[Token::Str("0xa")]);
+        assert_de_tokens(&ReceiptType::Mint, &[Token::Str("0xb")]);
+        assert_de_tokens(&ReceiptType::Burn, &[Token::Str("0xc")]);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_as_str() {
+        assert_eq!(ReceiptType::Call.as_str(), "0x0");
+        assert_eq!(ReceiptType::MessageOut.as_str(), "0x10");
+        assert_eq!(ReceiptType::Unknown(0x13).as_str(), "0x13");
+    }
+
+    #[test]
+    fn test_u8_round_trip() {
+        for val in 0..=u8::MAX {
+            assert_eq!(ReceiptType::from_u8(val).to_u8(), val);
+        }
+        assert_eq!(ReceiptType::from_u8(13), ReceiptType::Unknown(13));
+    }
+
+    #[test]
     fn test_de_unknown() {
-        assert_de_tokens(&ReceiptType::Call, &[Token::Str("0x13")]);
+        assert_tokens(&ReceiptType::Unknown(0x13), &[Token::Str("0x13")]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_de_invalid() {
+        assert_de_tokens(&ReceiptType::Call, &[Token::Str("call")]);
     }
 }
@@ -0,0 +1,112 @@
+use std::fmt;
+
+/// The `ra` of a Revert receipt, with the signals of the Sway standard library decoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RevertSignal {
+    /// `require` failed, the error value is logged before reverting.
+    FailedRequire,
+    /// `transfer` to an address failed.
+    FailedTransferToAddress,
+    /// Sending a message failed.
+    FailedSendMessage,
+    /// `assert_eq` failed, both values are logged before reverting.
+    FailedAssertEq,
+    /// `assert` failed.
+    FailedAssert,
+    /// `assert_ne` failed, both values are logged before reverting.
+    FailedAssertNe,
+    /// `revert_with_log`, the value is logged before reverting.
+    RevertWithLog,
+    /// A code given to `revert` by the program.
+    Other(u64),
+}
+
+const FAILED_REQUIRE_SIGNAL: u64 = 0xffff_ffff_ffff_0000;
+const FAILED_TRANSFER_TO_ADDRESS_SIGNAL: u64 = 0xffff_ffff_ffff_0001;
+const FAILED_SEND_MESSAGE_SIGNAL: u64 = 0xffff_ffff_ffff_0002;
+const FAILED_ASSERT_EQ_SIGNAL: u64 = 0xffff_ffff_ffff_0003;
+const FAILED_ASSERT_SIGNAL: u64 = 0xffff_ffff_ffff_0004;
+const FAILED_ASSERT_NE_SIGNAL: u64 = 0xffff_ffff_ffff_0005;
+const REVERT_WITH_LOG_SIGNAL: u64 = 0xffff_ffff_ffff_0006;
+
+impl RevertSignal {
+    /// Number of values that are logged right before the revert.
+    pub fn num_logged_values(&self) -> usize {
+        match self {
+            Self::FailedRequire | Self::RevertWithLog => 1,
+            Self::FailedAssertEq | Self::FailedAssertNe => 2,
+            _ => 0,
+        }
+    }
+}
+
+impl From<u64> for RevertSignal {
+    fn from(val: u64) -> Self {
+        match val {
+            FAILED_REQUIRE_SIGNAL => Self::FailedRequire,
+            FAILED_TRANSFER_TO_ADDRESS_SIGNAL => Self::FailedTransferToAddress,
+            FAILED_SEND_MESSAGE_SIGNAL => Self::FailedSendMessage,
+            FAILED_ASSERT_EQ_SIGNAL => Self::FailedAssertEq,
+            FAILED_ASSERT_SIGNAL => Self::FailedAssert,
+            FAILED_ASSERT_NE_SIGNAL => Self::FailedAssertNe,
+            REVERT_WITH_LOG_SIGNAL => Self::RevertWithLog,
+            val => Self::Other(val),
+        }
+    }
+}
+
+impl From<RevertSignal> for u64 {
+    fn from(val: RevertSignal) -> Self {
+        match val {
+            RevertSignal::FailedRequire => FAILED_REQUIRE_SIGNAL,
+            RevertSignal::FailedTransferToAddress => FAILED_TRANSFER_TO_ADDRESS_SIGNAL,
+            RevertSignal::FailedSendMessage => FAILED_SEND_MESSAGE_SIGNAL,
+            RevertSignal::FailedAssertEq => FAILED_ASSERT_EQ_SIGNAL,
+            RevertSignal::FailedAssert => FAILED_ASSERT_SIGNAL,
+            RevertSignal::FailedAssertNe => FAILED_ASSERT_NE_SIGNAL,
+            RevertSignal::RevertWithLog => REVERT_WITH_LOG_SIGNAL,
+            RevertSignal::Other(val) => val,
+        }
+    }
+}
+
+impl fmt::Display for RevertSignal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FailedRequire => f.write_str("require failed"),
+            Self::FailedTransferToAddress => f.write_str("transfer to address failed"),
+            Self::FailedSendMessage => f.write_str("send message failed"),
+            Self::FailedAssertEq => f.write_str("assert_eq failed"),
+            Self::FailedAssert => f.write_str("assert failed"),
+            Self::FailedAssertNe => f.write_str("assert_ne failed"),
+            Self::RevertWithLog => f.write_str("revert_with_log"),
+            Self::Other(val) => write!(f, "revert {val}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RevertSignal;
+
+    #[test]
+    fn test_revert_signal() {
+        assert_eq!(
+            RevertSignal::from(0xffff_ffff_ffff_0000),
+            RevertSignal::FailedRequire
+        );
+        assert_eq!(
+            RevertSignal::from(0xffff_ffff_ffff_0003),
+            RevertSignal::FailedAssertEq
+        );
+        assert_eq!(RevertSignal::from(42), RevertSignal::Other(42));
+        assert_eq!(
+            RevertSignal::from(0xffff_ffff_ffff_0007),
+            RevertSignal::Other(0xffff_ffff_ffff_0007)
+        );
+
+        for val in (0xffff_ffff_ffff_0000..=0xffff_ffff_ffff_0006).chain([0, 42]) {
+            assert_eq!(u64::from(RevertSignal::from(val)), val);
+        }
+    }
+}
@@ -0,0 +1,198 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::de::{self, Visitor};
+use serde::{ser, Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::result::Result as StdResult;
+use std::str::FromStr;
+
+use super::{BlockHeader, Transaction};
+use crate::{Error, Result};
+
+/// TAI64 label of the Unix epoch.
+///
+/// Like fuel-core, this uses the fixed 10 second TAI-UTC difference of 1970 and ignores later
+/// leap seconds, so conversions are exact inverses of what the node does.
+pub const TAI64_UNIX_EPOCH: u64 = (1 << 62) + 10;
+
+/// A block or transaction time.
+///
+/// Fuel stores times as TAI64 labels, which are seconds offset by 2^62. Serializes as an
+/// ISO-8601 UTC string like "2023-11-14T22:13:20Z" and deserializes from that or from the label.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp(u64);
+
+impl Timestamp {
+    pub fn from_tai64(label: u64) -> Self {
+        Self(label)
+    }
+
+    /// Returns `None` if the time can't be represented as a TAI64 label.
+    pub fn from_unix(secs: i64) -> Option<Self> {
+        TAI64_UNIX_EPOCH.checked_add_signed(secs).map(Self)
+    }
+
+    pub fn tai64(&self) -> u64 {
+        self.0
+    }
+
+    /// Seconds since the Unix epoch.
+    pub fn unix(&self) -> i64 {
+        // valid labels are below 2^63, so the difference always fits
+        self.0.wrapping_sub(TAI64_UNIX_EPOCH) as i64
+    }
+
+    /// Nanoseconds since the Unix epoch, `None` if it doesn't fit in an `i64`.
+    pub fn unix_nanos(&self) -> Option<i64> {
+        self.unix().checked_mul(1_000_000_000)
+    }
+
+    /// Returns `None` if the time is out of the range of `DateTime`.
+    pub fn to_datetime(&self) -> Option<DateTime<Utc>> {
+        DateTime::from_timestamp(self.unix(), 0)
+    }
+}
+
+impl TryFrom<DateTime<Utc>> for Timestamp {
+    type Error = Error;
+
+    fn try_from(datetime: DateTime<Utc>) -> Result<Self> {
+        Self::from_unix(datetime.timestamp())
+            .ok_or_else(|| Error::InvalidTimestamp(datetime.to_string()))
+    }
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.to_datetime() {
+            Some(datetime) => f.write_str(&datetime.to_rfc3339_opts(SecondsFormat::Secs, true)),
+            None => write!(f, "TAI64({:#x})", self.0),
+        }
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let datetime = DateTime::parse_from_rfc3339(s)
+            .map_err(|e| Error::InvalidTimestamp(format!("{s}: {e}")))?;
+
+        Self::try_from(datetime.to_utc())
+    }
+}
+
+struct TimestampVisitor;
+
+impl<'de> Visitor<'de> for TimestampVisitor {
+    type Value = Timestamp;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("ISO-8601 string or TAI64 label")
+    }
+
+    fn visit_str<E>(self, value: &str) -> StdResult<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Timestamp::from_str(value).map_err(|e| E::custom(e.to_string()))
+    }
+
+    fn visit_u64<E>(self, value: u64) -> StdResult<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        Ok(Timestamp::from_tai64(value))
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D>(deserializer: D) -> StdResult<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(TimestampVisitor)
+    }
+}
+
+impl Serialize for Timestamp {
+    fn serialize<S>(&self, serializer: S) -> StdResult<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if self.to_datetime().is_none() {
+            return Err(ser::Error::custom(format!(
+                "TAI64 label {:#x} is out of the ISO-8601 range",
+                self.0
+            )));
+        }
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl BlockHeader {
+    /// The `time` field as a [`Timestamp`].
+    pub fn timestamp(&self) -> Timestamp {
+        Timestamp::from_tai64(*self.time)
+    }
+}
+
+impl Transaction {
+    /// The `time` field as a [`Timestamp`].
+    pub fn timestamp(&self) -> Timestamp {
+        Timestamp::from_tai64(*self.time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LABEL: u64 = TAI64_UNIX_EPOCH + 1_700_000_000;
+
+    #[test]
+    fn test_unix() {
+        let ts = Timestamp::from_tai64(LABEL);
+        assert_eq!(ts.unix(), 1_700_000_000);
+        assert_eq!(ts.unix_nanos(), Some(1_700_000_000_000_000_000));
+        assert_eq!(Timestamp::from_unix(1_700_000_000), Some(ts));
+        assert_eq!(Timestamp::from_unix(-10).unwrap().unix(), -10);
+        assert_eq!(Timestamp::from_tai64(1 << 62).unix(), -10);
+        assert_eq!(Timestamp::from_tai64(0).unix_nanos(), None);
+    }
+
+    #[test]
+    fn test_iso8601() {
+        let ts = Timestamp::from_tai64(LABEL);
+        assert_eq!(ts.to_string(), "2023-11-14T22:13:20Z");
+        assert_eq!("2023-11-14T22:13:20Z".parse::<Timestamp>().unwrap(), ts);
+        assert_eq!(
+            "2023-11-15T00:13:20+02:00".parse::<Timestamp>().unwrap(),
+            ts
+        );
+        assert!("2023-11-14".parse::<Timestamp>().is_err());
+    }
+
+    #[test]
+    fn test_serde() {
+        let ts = Timestamp::from_tai64(LABEL);
+        assert_eq!(
+            serde_json::to_string(&ts).unwrap(),
+            r#""2023-11-14T22:13:20Z""#
+        );
+        assert_eq!(
+            serde_json::from_str::<Timestamp>(r#""2023-11-14T22:13:20Z""#).unwrap(),
+            ts
+        );
+        assert_eq!(
+            serde_json::from_str::<Timestamp>(&LABEL.to_string()).unwrap(),
+            ts
+        );
+        assert!(serde_json::to_string(&Timestamp::from_tai64(u64::MAX >> 1)).is_err());
+
+        let header = BlockHeader {
+            time: LABEL.into(),
+            ..Default::default()
+        };
+        assert_eq!(header.timestamp(), ts);
+    }
+}
@@ -5,21 +5,29 @@ use std::fmt;
 use std::result::Result as StdResult;
 use std::str::FromStr;
 
+use super::quantity::{decode_u8_hex, encode_hex, u8_hex_str};
 use super::Hex;
 
-#[derive(Default, Clone, PartialEq, Eq)]
+/// Status of a transaction.
+///
+/// Blocks only contain executed transactions, so data from the archive is either `Success` or
+/// `Failure`. Other codes, e.g. for the statuses of transactions that aren't in a block yet, have
+/// no mapping that the server documents and are kept as `Unknown`.
+#[derive(Default, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum TransactionStatus {
     #[default]
     Success,
     Failure,
+    /// A code that this version of the library doesn't know.
+    Unknown(u8),
 }
 
 impl TransactionStatus {
-    pub fn from_u8(val: u8) -> Result<Self> {
+    pub fn from_u8(val: u8) -> Self {
         match val {
-            1 => Ok(Self::Success),
-            0 => Ok(Self::Failure),
-            _ => Err(Error::UnknownTransactionStatus(val.to_string())),
+            1 => Self::Success,
+            0 => Self::Failure,
+            val => Self::Unknown(val),
         }
     }
 
@@ -27,31 +35,50 @@ impl TransactionStatus {
         match self {
             Self::Success => 1,
             Self::Failure => 0,
+            Self::Unknown(val) => *val,
         }
     }
-}
-
-impl FromStr for TransactionStatus {
-    type Err = Error;
 
-    fn from_str(s: &str) -> Result<Self> {
-        match s {
-            "0x1" => Ok(Self::Success),
-            "0x0" => Ok(Self::Failure),
-            _ => Err(Error::UnknownTransactionStatus(s.to_owned())),
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Success => "Success",
+            Self::Failure => "Failure",
+            Self::Unknown(_) => "Unknown",
         }
     }
-}
 
-impl TransactionStatus {
+    /// Hex string of the status.
+    #[deprecated(note = "use `Hex::encode_hex` instead")]
     pub fn as_str(&self) -> &'static str {
+        u8_hex_str(self.to_u8())
+    }
+}
+
+impl From<TransactionStatus> for u8 {
+    fn from(val: TransactionStatus) -> Self {
+        val.to_u8()
+    }
+}
+
+impl fmt::Display for TransactionStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::Success => "0x1",
-            Self::Failure => "0x0",
+            Self::Unknown(val) => write!(f, "Unknown({val})"),
+            status => f.write_str(status.name()),
         }
     }
 }
 
+impl FromStr for TransactionStatus {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        decode_u8_hex(s)
+            .map(Self::from_u8)
+            .ok_or_else(|| Error::UnknownTransactionStatus(s.to_owned()))
+    }
+}
+
 struct TransactionStatusVisitor;
 
 impl<'de> Visitor<'de> for TransactionStatusVisitor {
@@ -83,13 +110,13 @@ impl Serialize for TransactionStatus {
     where
         S: Serializer,
     {
-        serializer.serialize_str(self.as_str())
+        serializer.serialize_str(&self.encode_hex())
     }
 }
 
 impl Hex for TransactionStatus {
     fn encode_hex(&self) -> String {
-        self.as_str().to_owned()
+        encode_hex(&[self.to_u8()])
     }
 
     fn decode_hex(hex: &str) -> Result<Self> {
@@ -115,8 +142,22 @@ mod tests {
     }
 
     #[test]
-    #[should_panic]
+    fn test_u8_round_trip() {
+        for val in 0..=u8::MAX {
+            assert_eq!(TransactionStatus::from_u8(val).to_u8(), val);
+        }
+        assert_eq!(TransactionStatus::from_u8(2), TransactionStatus::Unknown(2));
+        assert_eq!(TransactionStatus::Unknown(6).to_string(), "Unknown(6)");
+    }
+
+    #[test]
     fn test_de_unknown() {
-        assert_de_tokens(&TransactionStatus::Success, &[Token::Str("0x3")]);
+        assert_tokens(&TransactionStatus::Unknown(6), &[Token::Str("0x6")]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_de_invalid() {
+        assert_de_tokens(&TransactionStatus::Success, &[Token::Str("0x100")]);
     }
 }
@@ -7,21 +7,77 @@ use super::quantity::encode_hex;
 use super::Hex;
 use crate::{Error, Result};
 
-#[derive(
-    Default,
-    Clone,
-    Copy,
-    PartialEq,
-    Eq,
-    PartialOrd,
-    Ord,
-    derive_more::From,
-    derive_more::Into,
-    derive_more::Deref,
-    derive_more::Add,
-    derive_more::Sub,
-)]
-pub struct TransactionType(pub u8);
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TransactionType {
+    #[default]
+    Script,
+    Create,
+    Mint,
+    Upgrade,
+    Upload,
+    Blob,
+    /// A transaction type that was added to Fuel after this version of the library.
+    Unknown(u8),
+}
+
+impl TransactionType {
+    pub fn from_u8(val: u8) -> Self {
+        match val {
+            0 => Self::Script,
+            1 => Self::Create,
+            2 => Self::Mint,
+            3 => Self::Upgrade,
+            4 => Self::Upload,
+            5 => Self::Blob,
+            val => Self::Unknown(val),
+        }
+    }
+
+    pub fn to_u8(&self) -> u8 {
+        match self {
+            Self::Script => 0,
+            Self::Create => 1,
+            Self::Mint => 2,
+            Self::Upgrade => 3,
+            Self::Upload => 4,
+            Self::Blob => 5,
+            Self::Unknown(val) => *val,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Script => "Script",
+            Self::Create => "Create",
+            Self::Mint => "Mint",
+            Self::Upgrade => "Upgrade",
+            Self::Upload => "Upload",
+            Self::Blob => "Blob",
+            Self::Unknown(_) => "Unknown",
+        }
+    }
+}
+
+impl From<u8> for TransactionType {
+    fn from(val: u8) -> Self {
+        Self::from_u8(val)
+    }
+}
+
+impl From<TransactionType> for u8 {
+    fn from(val: TransactionType) -> Self {
+        val.to_u8()
+    }
+}
+
+impl fmt::Display for TransactionType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unknown(val) => write!(f, "Unknown({val})"),
+            tx_type => f.write_str(tx_type.name()),
+        }
+    }
+}
 
 struct TransactionTypeVisitor;
 
@@ -60,7 +116,7 @@ impl Serialize for TransactionType {
 
 impl Hex for TransactionType {
     fn encode_hex(&self) -> String {
-        encode_hex(&self.to_be_bytes())
+        encode_hex(&[self.to_u8()])
     }
 
     fn decode_hex(hex: &str) -> Result<Self> {
@@ -74,12 +130,6 @@ impl Hex for TransactionType {
     }
 }
 
-impl fmt::Debug for TransactionType {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "TransactionType({})", self.encode_hex())
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::TransactionType;
@@ -89,19 +139,34 @@ mod tests {
     fn test_serde_zero() {
         assert_eq!(TransactionType::default(), TransactionType::from(0));
 
-        assert_tokens(&TransactionType::from(0), &[Token::Str("0x0")]);
+        assert_tokens(&TransactionType::Script, &[Token::Str("0x0")]);
     }
 
     #[test]
     fn test_serde_max() {
-        assert_tokens(&TransactionType::from(u8::MAX), &[Token::Str("0xff")]);
+        assert_tokens(&TransactionType::Unknown(u8::MAX), &[Token::Str("0xff")]);
     }
 
     #[test]
     fn test_serde() {
+        assert_tokens(&TransactionType::Create, &[Token::Str("0x1")]);
+        assert_tokens(&TransactionType::Mint, &[Token::Str("0x2")]);
+        assert_tokens(&TransactionType::Upgrade, &[Token::Str("0x3")]);
+        assert_tokens(&TransactionType::Upload, &[Token::Str("0x4")]);
+        assert_tokens(&TransactionType::Blob, &[Token::Str("0x5")]);
         assert_tokens(&TransactionType::from(19), &[Token::Str("0x13")]);
     }
 
+    #[test]
+    fn test_u8_round_trip() {
+        for val in 0..=u8::MAX {
+            assert_eq!(TransactionType::from_u8(val).to_u8(), val);
+        }
+        assert_eq!(TransactionType::from(19), TransactionType::Unknown(19));
+        assert_eq!(TransactionType::Blob.to_string(), "Blob");
+        assert_eq!(TransactionType::Unknown(19).to_string(), "Unknown(19)");
+    }
+
     #[test]
     #[should_panic(expected = "Invalid Number from Hex")]
     fn test_serde_overflow() {
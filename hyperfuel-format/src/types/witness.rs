@@ -0,0 +1,196 @@
+use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+use sha2::{Digest, Sha256};
+
+use super::{Address, Data, Hash, Input, InputType, Transaction};
+use crate::{Error, Result};
+
+/// Splits the `witnesses` of a transaction into the individual witnesses.
+///
+/// Witnesses are canonically encoded one after the other: an 8 byte big endian length followed
+/// by the witness bytes, zero padded to a multiple of 8 bytes.
+pub fn parse_witnesses(mut buf: &[u8]) -> Result<Vec<Data>> {
+    let mut witnesses = Vec::new();
+
+    while !buf.is_empty() {
+        if buf.len() < 8 {
+            return Err(Error::InvalidWitnesses(format!(
+                "{} trailing bytes can't hold a length",
+                buf.len()
+            )));
+        }
+        let len = u64::from_be_bytes(buf[..8].try_into().unwrap());
+        buf = &buf[8..];
+
+        let padded_len = usize::try_from(len)
+            .ok()
+            .and_then(|len| len.checked_next_multiple_of(8))
+            .filter(|&padded| padded <= buf.len())
+            .ok_or_else(|| {
+                Error::InvalidWitnesses(format!(
+                    "witness of {len} bytes overruns the remaining {} bytes",
+                    buf.len()
+                ))
+            })?;
+
+        witnesses.push(Data::from(&buf[..len as usize]));
+        buf = &buf[padded_len..];
+    }
+
+    Ok(witnesses)
+}
+
+/// Recovers the address that produced a 64 byte Fuel secp256k1 signature of `message`.
+///
+/// Fuel signatures are `r || s` with the recovery id stored in the top bit of `s`. The address
+/// is the sha256 of the uncompressed public key without its `0x04` prefix.
+pub fn recover_signer(signature: &[u8], message: &Hash) -> Result<Address> {
+    let mut sig: [u8; 64] = signature.try_into().map_err(|_| {
+        Error::InvalidSignature(format!("expected 64 bytes, got {}", signature.len()))
+    })?;
+    let recovery_id = sig[32] >> 7;
+    sig[32] &= 0x7f;
+
+    let sig = Signature::from_slice(&sig).map_err(|e| Error::InvalidSignature(e.to_string()))?;
+    let recovery_id = RecoveryId::from_byte(recovery_id).unwrap();
+    let key = VerifyingKey::recover_from_prehash(message.as_slice(), &sig, recovery_id)
+        .map_err(|e| Error::InvalidSignature(e.to_string()))?;
+
+    let point = key.to_encoded_point(false);
+    let digest: [u8; 32] = Sha256::digest(&point.as_bytes()[1..]).into();
+
+    Ok(digest.into())
+}
+
+impl Transaction {
+    /// Parses the `witnesses` field into the individual witnesses, see [`parse_witnesses`].
+    pub fn witness_list(&self) -> Result<Vec<Data>> {
+        match &self.witnesses {
+            Some(witnesses) => parse_witnesses(witnesses.as_ref()),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Recovers the signer of each witness over the transaction id.
+    ///
+    /// Witnesses that aren't valid signatures, like predicate data or contract bytecode, are
+    /// `None`.
+    pub fn signers(&self) -> Result<Vec<Option<Address>>> {
+        Ok(self
+            .witness_list()?
+            .iter()
+            .map(|witness| recover_signer(witness.as_ref(), &self.id).ok())
+            .collect())
+    }
+}
+
+impl Input {
+    /// Checks whether the witness that a coin input points to was signed by the input owner.
+    ///
+    /// `signers` are the recovered signers of the transaction, see [`Transaction::signers`].
+    /// Returns `None` for inputs that aren't coin inputs spent by a signature.
+    pub fn is_signed_by_owner(&self, signers: &[Option<Address>]) -> Option<bool> {
+        if self.input_type != InputType::InputCoin || self.predicate.is_some() {
+            return None;
+        }
+        let witness_index = usize::try_from(*self.witness_index?).ok()?;
+        let owner = self.owner.as_ref()?;
+
+        Some(signers.get(witness_index)?.as_ref() == Some(owner))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+    use k256::ecdsa::SigningKey;
+
+    fn sign(secret: &[u8; 32], message: &Hash) -> [u8; 64] {
+        let key = SigningKey::from_slice(secret).unwrap();
+        let (sig, recovery_id) = key.sign_prehash_recoverable(message.as_slice()).unwrap();
+
+        let mut out: [u8; 64] = sig.to_bytes().into();
+        out[32] |= recovery_id.to_byte() << 7;
+        out
+    }
+
+    fn encode_witnesses(witnesses: &[&[u8]]) -> Vec<u8> {
+        let mut out = Vec::new();
+        for witness in witnesses {
+            out.extend_from_slice(&(witness.len() as u64).to_be_bytes());
+            out.extend_from_slice(witness);
+            out.resize(out.len().next_multiple_of(8), 0);
+        }
+        out
+    }
+
+    // sha256 of the uncompressed public keys of the secret keys 1 and 0x11..11
+    const SIGNER_1: [u8; 32] =
+        hex!("09c0b2d1a486c439a87bcba6b46a7a1a23f3897cc83a94521a96da5c23bc58db");
+    const SIGNER_11: [u8; 32] =
+        hex!("b4e84a53cd74a3ed9bf15e1edd92c2a69e567462c58aa8265f89d24c2204e1d7");
+
+    #[test]
+    fn test_parse_witnesses() {
+        let buf = encode_witnesses(&[&[1, 2, 3], &[], &[4; 8]]);
+
+        let witnesses = parse_witnesses(&buf).unwrap();
+        assert_eq!(
+            witnesses,
+            [
+                Data::from(&[1, 2, 3][..]),
+                Data::from(&[][..]),
+                Data::from(&[4; 8][..])
+            ]
+        );
+
+        assert!(parse_witnesses(&buf[..buf.len() - 1]).is_err());
+        assert!(parse_witnesses(&[0, 0, 0, 0, 0, 0, 0, 9, 1]).is_err());
+        assert!(parse_witnesses(&u64::MAX.to_be_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_recover_signer() {
+        let tx_id = Hash::from([0xab; 32]);
+        let mut secret = [0; 32];
+        secret[31] = 1;
+
+        let sig = sign(&secret, &tx_id);
+        assert_eq!(
+            recover_signer(&sig, &tx_id).unwrap(),
+            Address::from(SIGNER_1)
+        );
+        assert_ne!(
+            recover_signer(&sig, &Hash::from([0xac; 32])).unwrap(),
+            Address::from(SIGNER_1)
+        );
+        assert!(recover_signer(&sig[..63], &tx_id).is_err());
+        assert!(recover_signer(&[0; 64], &tx_id).is_err());
+    }
+
+    #[test]
+    fn test_signers() {
+        let tx_id = Hash::from([0x42; 32]);
+        let sig = sign(&[0x11; 32], &tx_id);
+        let tx = Transaction {
+            id: tx_id,
+            witnesses: Some(encode_witnesses(&[&[7; 20], &sig]).into()),
+            ..Default::default()
+        };
+
+        let signers = tx.signers().unwrap();
+        assert_eq!(signers, [None, Some(Address::from(SIGNER_11))]);
+
+        let coin = |witness_index: u64, owner: [u8; 32]| Input {
+            input_type: InputType::InputCoin,
+            witness_index: Some(witness_index.into()),
+            owner: Some(owner.into()),
+            ..Default::default()
+        };
+        assert_eq!(coin(1, SIGNER_11).is_signed_by_owner(&signers), Some(true));
+        assert_eq!(coin(1, SIGNER_1).is_signed_by_owner(&signers), Some(false));
+        assert_eq!(coin(0, SIGNER_11).is_signed_by_owner(&signers), Some(false));
+        assert_eq!(coin(2, SIGNER_11).is_signed_by_owner(&signers), None);
+        assert_eq!(Input::default().is_signed_by_owner(&signers), None);
+    }
+}
@@ -0,0 +1,372 @@
+use std::borrow::Borrow;
+use std::collections::BTreeSet;
+
+use hyperfuel_format::{
+    Hash, InputType, OutputType, ReceiptType, TransactionStatus, TransactionType,
+};
+
+use crate::{
+    BlockField, FieldSelection, InputField, InputSelection, JoinMode, OutputField, OutputSelection,
+    Query, QueryError, ReceiptField, ReceiptSelection, TransactionField,
+};
+
+/// Setters that add to the `Vec<Hash>` fields of a selection.
+macro_rules! hash_setters {
+    ($($field:ident),* $(,)?) => {
+        $(
+            #[doc = concat!("Also matches the given `", stringify!($field), "` values.")]
+            pub fn $field<H: Into<Hash>>(mut self, values: impl IntoIterator<Item = H>) -> Self {
+                self.$field.extend(values.into_iter().map(Into::into));
+                self
+            }
+        )*
+    };
+}
+
+/// Setters for the transaction status and type that every selection has.
+macro_rules! tx_setters {
+    () => {
+        /// Only matches rows of transactions with one of the given statuses.
+        pub fn tx_status(mut self, statuses: impl IntoIterator<Item = TransactionStatus>) -> Self {
+            self.tx_status
+                .extend(statuses.into_iter().map(|s| s.to_u8()));
+            self
+        }
+
+        /// Only matches rows of transactions of one of the given types.
+        pub fn tx_type(mut self, types: impl IntoIterator<Item = TransactionType>) -> Self {
+            self.tx_type.extend(types.into_iter().map(|t| t.to_u8()));
+            self
+        }
+    };
+}
+
+impl ReceiptSelection {
+    hash_setters!(
+        root_contract_id,
+        to,
+        to_address,
+        asset_id,
+        sender,
+        recipient,
+        contract_id
+    );
+    tx_setters!();
+
+    /// Also matches receipts of the given types.
+    pub fn receipt_type(mut self, types: impl IntoIterator<Item = ReceiptType>) -> Self {
+        self.receipt_type
+            .extend(types.into_iter().map(|t| t.to_u8()));
+        self
+    }
+
+    /// Also matches receipts with the given `ra` register values.
+    pub fn ra(mut self, values: impl IntoIterator<Item = u64>) -> Self {
+        self.ra.extend(values);
+        self
+    }
+
+    /// Also matches receipts with the given `rb` register values, e.g. log ids.
+    pub fn rb(mut self, values: impl IntoIterator<Item = u64>) -> Self {
+        self.rb.extend(values);
+        self
+    }
+
+    /// Also matches receipts with the given `rc` register values.
+    pub fn rc(mut self, values: impl IntoIterator<Item = u64>) -> Self {
+        self.rc.extend(values);
+        self
+    }
+
+    /// Also matches receipts with the given `rd` register values.
+    pub fn rd(mut self, values: impl IntoIterator<Item = u64>) -> Self {
+        self.rd.extend(values);
+        self
+    }
+}
+
+impl InputSelection {
+    hash_setters!(owner, asset_id, contract, sender, recipient);
+    tx_setters!();
+
+    /// Also matches inputs of the given types.
+    pub fn input_type(mut self, types: impl IntoIterator<Item = InputType>) -> Self {
+        self.input_type.extend(types.into_iter().map(|t| t.as_u8()));
+        self
+    }
+}
+
+impl OutputSelection {
+    hash_setters!(to, asset_id, contract);
+    tx_setters!();
+
+    /// Also matches outputs of the given types.
+    pub fn output_type(mut self, types: impl IntoIterator<Item = OutputType>) -> Self {
+        self.output_type
+            .extend(types.into_iter().map(|t| t.as_u8()));
+        self
+    }
+}
+
+impl FieldSelection {
+    /// Selects every column of every table.
+    pub fn all_fields() -> Self {
+        fn names<F: ToString>(fields: &[F]) -> BTreeSet<String> {
+            fields.iter().map(|f| f.to_string()).collect()
+        }
+
+        Self {
+            block: names(BlockField::all_fields()),
+            transaction: names(TransactionField::all_fields()),
+            receipt: names(ReceiptField::all_fields()),
+            input: names(InputField::all_fields()),
+            output: names(OutputField::all_fields()),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.block.is_empty()
+            && self.transaction.is_empty()
+            && self.receipt.is_empty()
+            && self.input.is_empty()
+            && self.output.is_empty()
+    }
+}
+
+/// Builds a [`Query`] from typed selections and fields, see [`Query::builder`].
+///
+/// ```
+/// use hyperfuel_format::ReceiptType;
+/// use hyperfuel_net_types::{Query, ReceiptField, ReceiptSelection};
+///
+/// let query = Query::builder()
+///     .from_block(100)
+///     .to_block(200)
+///     .receipts(
+///         ReceiptSelection::default()
+///             .root_contract_id([[1; 32]])
+///             .receipt_type([ReceiptType::Log, ReceiptType::LogData]),
+///     )
+///     .receipt_fields([ReceiptField::TxId, ReceiptField::Data])
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(query.receipts[0].receipt_type, [5, 6]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct QueryBuilder {
+    query: Query,
+}
+
+impl Query {
+    /// Returns a builder for a query.
+    pub fn builder() -> QueryBuilder {
+        QueryBuilder::default()
+    }
+}
+
+impl QueryBuilder {
+    /// The first block of the query.
+    pub fn from_block(mut self, block: u64) -> Self {
+        self.query.from_block = block;
+        self
+    }
+
+    /// The block to stop at, exclusive.
+    pub fn to_block(mut self, block: u64) -> Self {
+        self.query.to_block = Some(block);
+        self
+    }
+
+    /// Adds a receipt selection, receipts that match any selection are returned.
+    pub fn receipts(mut self, selection: ReceiptSelection) -> Self {
+        self.query.receipts.push(selection);
+        self
+    }
+
+    /// Adds an input selection, inputs that match any selection are returned.
+    pub fn inputs(mut self, selection: InputSelection) -> Self {
+        self.query.inputs.push(selection);
+        self
+    }
+
+    /// Adds an output selection, outputs that match any selection are returned.
+    pub fn outputs(mut self, selection: OutputSelection) -> Self {
+        self.query.outputs.push(selection);
+        self
+    }
+
+    /// Returns every block in the range, not only the ones related to returned rows.
+    pub fn include_all_blocks(mut self) -> Self {
+        self.query.include_all_blocks = true;
+        self
+    }
+
+    /// Selects the given block columns.
+    pub fn block_fields(
+        mut self,
+        fields: impl IntoIterator<Item = impl Borrow<BlockField>>,
+    ) -> Self {
+        let names = fields.into_iter().map(|f| f.borrow().name().to_owned());
+        self.query.field_selection.block.extend(names);
+        self
+    }
+
+    /// Selects the given transaction columns.
+    pub fn transaction_fields(
+        mut self,
+        fields: impl IntoIterator<Item = impl Borrow<TransactionField>>,
+    ) -> Self {
+        let names = fields.into_iter().map(|f| f.borrow().name().to_owned());
+        self.query.field_selection.transaction.extend(names);
+        self
+    }
+
+    /// Selects the given receipt columns.
+    pub fn receipt_fields(
+        mut self,
+        fields: impl IntoIterator<Item = impl Borrow<ReceiptField>>,
+    ) -> Self {
+        let names = fields.into_iter().map(|f| f.borrow().name().to_owned());
+        self.query.field_selection.receipt.extend(names);
+        self
+    }
+
+    /// Selects the given input columns.
+    pub fn input_fields(
+        mut self,
+        fields: impl IntoIterator<Item = impl Borrow<InputField>>,
+    ) -> Self {
+        let names = fields.into_iter().map(|f| f.borrow().name().to_owned());
+        self.query.field_selection.input.extend(names);
+        self
+    }
+
+    /// Selects the given output columns.
+    pub fn output_fields(
+        mut self,
+        fields: impl IntoIterator<Item = impl Borrow<OutputField>>,
+    ) -> Self {
+        let names = fields.into_iter().map(|f| f.borrow().name().to_owned());
+        self.query.field_selection.output.extend(names);
+        self
+    }
+
+    /// Selects every column of every table, see [`FieldSelection::all_fields`].
+    pub fn all_fields(mut self) -> Self {
+        self.query.field_selection = FieldSelection::all_fields();
+        self
+    }
+
+    /// Caps the number of returned blocks.
+    pub fn max_num_blocks(mut self, max: usize) -> Self {
+        self.query.max_num_blocks = Some(max);
+        self
+    }
+
+    /// Caps the number of returned transactions.
+    pub fn max_num_transactions(mut self, max: usize) -> Self {
+        self.query.max_num_transactions = Some(max);
+        self
+    }
+
+    /// Caps the number of returned receipts.
+    pub fn max_num_receipts(mut self, max: usize) -> Self {
+        self.query.max_num_receipts = Some(max);
+        self
+    }
+
+    /// Caps the number of returned inputs.
+    pub fn max_num_inputs(mut self, max: usize) -> Self {
+        self.query.max_num_inputs = Some(max);
+        self
+    }
+
+    /// Caps the number of returned outputs.
+    pub fn max_num_outputs(mut self, max: usize) -> Self {
+        self.query.max_num_outputs = Some(max);
+        self
+    }
+
+    /// How related rows are joined.
+    pub fn join_mode(mut self, join_mode: JoinMode) -> Self {
+        self.query.join_mode = join_mode;
+        self
+    }
+
+    /// Returns the query, or an error if it can't return any data, see [`Query::validate`].
+    pub fn build(self) -> Result<Query, QueryError> {
+        let query = self.query;
+
+        query.validate()?;
+        if query.field_selection.is_empty() {
+            return Err(QueryError::NoFieldsSelected);
+        }
+
+        Ok(query)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build() {
+        let query = Query::builder()
+            .from_block(10)
+            .inputs(
+                InputSelection::default()
+                    .owner([[1; 32]])
+                    .owner([[2; 32]])
+                    .input_type([InputType::InputCoin])
+                    .tx_status([TransactionStatus::Success]),
+            )
+            .outputs(OutputSelection::default().output_type([OutputType::ContractCreated]))
+            .block_fields(BlockField::all_fields())
+            .input_fields([InputField::TxId, InputField::Owner])
+            .build()
+            .unwrap();
+
+        assert_eq!(query.from_block, 10);
+        assert_eq!(query.inputs[0].owner, [[1; 32].into(), [2; 32].into()]);
+        assert_eq!(query.inputs[0].input_type, [0]);
+        assert_eq!(query.inputs[0].tx_status, [1]);
+        assert_eq!(query.outputs[0].output_type, [4]);
+        assert!(query.field_selection.block.contains("time"));
+        assert_eq!(
+            query.field_selection.input,
+            ["owner", "tx_id"].map(String::from).into()
+        );
+    }
+
+    #[test]
+    fn test_build_errors() {
+        assert_eq!(
+            Query::builder()
+                .from_block(5)
+                .to_block(5)
+                .all_fields()
+                .build()
+                .unwrap_err(),
+            QueryError::InvalidBlockRange {
+                from_block: 5,
+                to_block: 5
+            }
+        );
+        assert_eq!(
+            Query::builder().build().unwrap_err(),
+            QueryError::NoFieldsSelected
+        );
+    }
+
+    #[test]
+    fn test_all_fields() {
+        let selection = FieldSelection::all_fields();
+        assert!(selection.receipt.contains("receipt_type"));
+        assert!(selection
+            .transaction
+            .contains("consensus_parameters_upgrade_purpose_witness_index"));
+        assert_eq!(ReceiptField::ReceiptType.to_string(), "receipt_type");
+    }
+}
@@ -0,0 +1,32 @@
+use thiserror::Error as ThisError;
+
+#[derive(Debug, Clone, PartialEq, Eq, ThisError)]
+pub enum QueryError {
+    #[error(
+        "Invalid block range. to_block {to_block} has to be greater than from_block {from_block}."
+    )]
+    InvalidBlockRange { from_block: u64, to_block: u64 },
+    #[error("No fields selected. The query wouldn't return any data.")]
+    NoFieldsSelected,
+    #[error("Invalid {table} field selection: {message}")]
+    UnknownFields {
+        table: &'static str,
+        message: String,
+    },
+    #[error("Unknown {field} code {code} in {table} selection {index}.")]
+    UnknownCode {
+        table: &'static str,
+        index: usize,
+        field: &'static str,
+        code: u8,
+    },
+    #[error("{table} selection {index} can never match. {field} is never set on {types}.")]
+    NeverMatches {
+        table: &'static str,
+        index: usize,
+        field: &'static str,
+        types: String,
+    },
+    #[error("Failed to decode capnp query: {0}")]
+    DecodeCapnp(String),
+}
@@ -0,0 +1,648 @@
+//! Field enums for the columns of each table of `hyperfuel_schema`, e.g. `BlockField`.
+//!
+//! `test_fields_match_schema` checks that they are in sync with the schema.
+
+use serde::{Deserialize, Serialize};
+
+/// Columns of the block table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum BlockField {
+    #[serde(rename = "id")]
+    Id,
+    #[serde(rename = "da_height")]
+    DaHeight,
+    #[serde(rename = "consensus_parameters_version")]
+    ConsensusParametersVersion,
+    #[serde(rename = "state_transition_bytecode_version")]
+    StateTransitionBytecodeVersion,
+    #[serde(rename = "transactions_count")]
+    TransactionsCount,
+    #[serde(rename = "message_receipt_count")]
+    MessageReceiptCount,
+    #[serde(rename = "transactions_root")]
+    TransactionsRoot,
+    #[serde(rename = "message_outbox_root")]
+    MessageOutboxRoot,
+    #[serde(rename = "event_inbox_root")]
+    EventInboxRoot,
+    #[serde(rename = "height")]
+    Height,
+    #[serde(rename = "prev_root")]
+    PrevRoot,
+    #[serde(rename = "time")]
+    Time,
+    #[serde(rename = "application_hash")]
+    ApplicationHash,
+}
+
+impl BlockField {
+    /// Every column of the table, in schema order.
+    pub fn all_fields() -> &'static [Self] {
+        &[
+            Self::Id,
+            Self::DaHeight,
+            Self::ConsensusParametersVersion,
+            Self::StateTransitionBytecodeVersion,
+            Self::TransactionsCount,
+            Self::MessageReceiptCount,
+            Self::TransactionsRoot,
+            Self::MessageOutboxRoot,
+            Self::EventInboxRoot,
+            Self::Height,
+            Self::PrevRoot,
+            Self::Time,
+            Self::ApplicationHash,
+        ]
+    }
+
+    /// Name of the column.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Id => "id",
+            Self::DaHeight => "da_height",
+            Self::ConsensusParametersVersion => "consensus_parameters_version",
+            Self::StateTransitionBytecodeVersion => "state_transition_bytecode_version",
+            Self::TransactionsCount => "transactions_count",
+            Self::MessageReceiptCount => "message_receipt_count",
+            Self::TransactionsRoot => "transactions_root",
+            Self::MessageOutboxRoot => "message_outbox_root",
+            Self::EventInboxRoot => "event_inbox_root",
+            Self::Height => "height",
+            Self::PrevRoot => "prev_root",
+            Self::Time => "time",
+            Self::ApplicationHash => "application_hash",
+        }
+    }
+}
+
+impl std::fmt::Display for BlockField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Columns of the transaction table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum TransactionField {
+    #[serde(rename = "block_height")]
+    BlockHeight,
+    #[serde(rename = "id")]
+    Id,
+    #[serde(rename = "input_asset_ids")]
+    InputAssetIds,
+    #[serde(rename = "input_contracts")]
+    InputContracts,
+    #[serde(rename = "input_contract_utxo_id")]
+    InputContractUtxoId,
+    #[serde(rename = "input_contract_balance_root")]
+    InputContractBalanceRoot,
+    #[serde(rename = "input_contract_state_root")]
+    InputContractStateRoot,
+    #[serde(rename = "input_contract_tx_pointer_block_height")]
+    InputContractTxPointerBlockHeight,
+    #[serde(rename = "input_contract_tx_pointer_tx_index")]
+    InputContractTxPointerTxIndex,
+    #[serde(rename = "input_contract")]
+    InputContract,
+    #[serde(rename = "policies_tip")]
+    PoliciesTip,
+    #[serde(rename = "policies_witness_limit")]
+    PoliciesWitnessLimit,
+    #[serde(rename = "policies_maturity")]
+    PoliciesMaturity,
+    #[serde(rename = "policies_max_fee")]
+    PoliciesMaxFee,
+    #[serde(rename = "script_gas_limit")]
+    ScriptGasLimit,
+    #[serde(rename = "maturity")]
+    Maturity,
+    #[serde(rename = "mint_amount")]
+    MintAmount,
+    #[serde(rename = "mint_asset_id")]
+    MintAssetId,
+    #[serde(rename = "mint_gas_price")]
+    MintGasPrice,
+    #[serde(rename = "tx_pointer_block_height")]
+    TxPointerBlockHeight,
+    #[serde(rename = "tx_pointer_tx_index")]
+    TxPointerTxIndex,
+    #[serde(rename = "tx_type")]
+    TxType,
+    #[serde(rename = "output_contract_input_index")]
+    OutputContractInputIndex,
+    #[serde(rename = "output_contract_balance_root")]
+    OutputContractBalanceRoot,
+    #[serde(rename = "output_contract_state_root")]
+    OutputContractStateRoot,
+    #[serde(rename = "witnesses")]
+    Witnesses,
+    #[serde(rename = "receipts_root")]
+    ReceiptsRoot,
+    #[serde(rename = "status")]
+    Status,
+    #[serde(rename = "time")]
+    Time,
+    #[serde(rename = "reason")]
+    Reason,
+    #[serde(rename = "script")]
+    Script,
+    #[serde(rename = "script_data")]
+    ScriptData,
+    #[serde(rename = "bytecode_witness_index")]
+    BytecodeWitnessIndex,
+    #[serde(rename = "bytecode_root")]
+    BytecodeRoot,
+    #[serde(rename = "subsection_index")]
+    SubsectionIndex,
+    #[serde(rename = "subsections_number")]
+    SubsectionsNumber,
+    #[serde(rename = "proof_set")]
+    ProofSet,
+    #[serde(rename = "consensus_parameters_upgrade_purpose_witness_index")]
+    ConsensusParametersUpgradePurposeWitnessIndex,
+    #[serde(rename = "consensus_parameters_upgrade_purpose_checksum")]
+    ConsensusParametersUpgradePurposeChecksum,
+    #[serde(rename = "state_transition_upgrade_purpose_root")]
+    StateTransitionUpgradePurposeRoot,
+    #[serde(rename = "salt")]
+    Salt,
+}
+
+impl TransactionField {
+    /// Every column of the table, in schema order.
+    pub fn all_fields() -> &'static [Self] {
+        &[
+            Self::BlockHeight,
+            Self::Id,
+            Self::InputAssetIds,
+            Self::InputContracts,
+            Self::InputContractUtxoId,
+            Self::InputContractBalanceRoot,
+            Self::InputContractStateRoot,
+            Self::InputContractTxPointerBlockHeight,
+            Self::InputContractTxPointerTxIndex,
+            Self::InputContract,
+            Self::PoliciesTip,
+            Self::PoliciesWitnessLimit,
+            Self::PoliciesMaturity,
+            Self::PoliciesMaxFee,
+            Self::ScriptGasLimit,
+            Self::Maturity,
+            Self::MintAmount,
+            Self::MintAssetId,
+            Self::MintGasPrice,
+            Self::TxPointerBlockHeight,
+            Self::TxPointerTxIndex,
+            Self::TxType,
+            Self::OutputContractInputIndex,
+            Self::OutputContractBalanceRoot,
+            Self::OutputContractStateRoot,
+            Self::Witnesses,
+            Self::ReceiptsRoot,
+            Self::Status,
+            Self::Time,
+            Self::Reason,
+            Self::Script,
+            Self::ScriptData,
+            Self::BytecodeWitnessIndex,
+            Self::BytecodeRoot,
+            Self::SubsectionIndex,
+            Self::SubsectionsNumber,
+            Self::ProofSet,
+            Self::ConsensusParametersUpgradePurposeWitnessIndex,
+            Self::ConsensusParametersUpgradePurposeChecksum,
+            Self::StateTransitionUpgradePurposeRoot,
+            Self::Salt,
+        ]
+    }
+
+    /// Name of the column.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::BlockHeight => "block_height",
+            Self::Id => "id",
+            Self::InputAssetIds => "input_asset_ids",
+            Self::InputContracts => "input_contracts",
+            Self::InputContractUtxoId => "input_contract_utxo_id",
+            Self::InputContractBalanceRoot => "input_contract_balance_root",
+            Self::InputContractStateRoot => "input_contract_state_root",
+            Self::InputContractTxPointerBlockHeight => "input_contract_tx_pointer_block_height",
+            Self::InputContractTxPointerTxIndex => "input_contract_tx_pointer_tx_index",
+            Self::InputContract => "input_contract",
+            Self::PoliciesTip => "policies_tip",
+            Self::PoliciesWitnessLimit => "policies_witness_limit",
+            Self::PoliciesMaturity => "policies_maturity",
+            Self::PoliciesMaxFee => "policies_max_fee",
+            Self::ScriptGasLimit => "script_gas_limit",
+            Self::Maturity => "maturity",
+            Self::MintAmount => "mint_amount",
+            Self::MintAssetId => "mint_asset_id",
+            Self::MintGasPrice => "mint_gas_price",
+            Self::TxPointerBlockHeight => "tx_pointer_block_height",
+            Self::TxPointerTxIndex => "tx_pointer_tx_index",
+            Self::TxType => "tx_type",
+            Self::OutputContractInputIndex => "output_contract_input_index",
+            Self::OutputContractBalanceRoot => "output_contract_balance_root",
+            Self::OutputContractStateRoot => "output_contract_state_root",
+            Self::Witnesses => "witnesses",
+            Self::ReceiptsRoot => "receipts_root",
+            Self::Status => "status",
+            Self::Time => "time",
+            Self::Reason => "reason",
+            Self::Script => "script",
+            Self::ScriptData => "script_data",
+            Self::BytecodeWitnessIndex => "bytecode_witness_index",
+            Self::BytecodeRoot => "bytecode_root",
+            Self::SubsectionIndex => "subsection_index",
+            Self::SubsectionsNumber => "subsections_number",
+            Self::ProofSet => "proof_set",
+            Self::ConsensusParametersUpgradePurposeWitnessIndex => {
+                "consensus_parameters_upgrade_purpose_witness_index"
+            }
+            Self::ConsensusParametersUpgradePurposeChecksum => {
+                "consensus_parameters_upgrade_purpose_checksum"
+            }
+            Self::StateTransitionUpgradePurposeRoot => "state_transition_upgrade_purpose_root",
+            Self::Salt => "salt",
+        }
+    }
+}
+
+impl std::fmt::Display for TransactionField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Columns of the receipt table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum ReceiptField {
+    #[serde(rename = "receipt_index")]
+    ReceiptIndex,
+    #[serde(rename = "root_contract_id")]
+    RootContractId,
+    #[serde(rename = "tx_id")]
+    TxId,
+    #[serde(rename = "tx_status")]
+    TxStatus,
+    #[serde(rename = "tx_type")]
+    TxType,
+    #[serde(rename = "block_height")]
+    BlockHeight,
+    #[serde(rename = "pc")]
+    Pc,
+    #[serde(rename = "is")]
+    Is,
+    #[serde(rename = "to")]
+    To,
+    #[serde(rename = "to_address")]
+    ToAddress,
+    #[serde(rename = "amount")]
+    Amount,
+    #[serde(rename = "asset_id")]
+    AssetId,
+    #[serde(rename = "gas")]
+    Gas,
+    #[serde(rename = "param1")]
+    Param1,
+    #[serde(rename = "param2")]
+    Param2,
+    #[serde(rename = "val")]
+    Val,
+    #[serde(rename = "ptr")]
+    Ptr,
+    #[serde(rename = "digest")]
+    Digest,
+    #[serde(rename = "reason")]
+    Reason,
+    #[serde(rename = "ra")]
+    Ra,
+    #[serde(rename = "rb")]
+    Rb,
+    #[serde(rename = "rc")]
+    Rc,
+    #[serde(rename = "rd")]
+    Rd,
+    #[serde(rename = "len")]
+    Len,
+    #[serde(rename = "receipt_type")]
+    ReceiptType,
+    #[serde(rename = "result")]
+    Result,
+    #[serde(rename = "gas_used")]
+    GasUsed,
+    #[serde(rename = "data")]
+    Data,
+    #[serde(rename = "sender")]
+    Sender,
+    #[serde(rename = "recipient")]
+    Recipient,
+    #[serde(rename = "nonce")]
+    Nonce,
+    #[serde(rename = "contract_id")]
+    ContractId,
+    #[serde(rename = "sub_id")]
+    SubId,
+}
+
+impl ReceiptField {
+    /// Every column of the table, in schema order.
+    pub fn all_fields() -> &'static [Self] {
+        &[
+            Self::ReceiptIndex,
+            Self::RootContractId,
+            Self::TxId,
+            Self::TxStatus,
+            Self::TxType,
+            Self::BlockHeight,
+            Self::Pc,
+            Self::Is,
+            Self::To,
+            Self::ToAddress,
+            Self::Amount,
+            Self::AssetId,
+            Self::Gas,
+            Self::Param1,
+            Self::Param2,
+            Self::Val,
+            Self::Ptr,
+            Self::Digest,
+            Self::Reason,
+            Self::Ra,
+            Self::Rb,
+            Self::Rc,
+            Self::Rd,
+            Self::Len,
+            Self::ReceiptType,
+            Self::Result,
+            Self::GasUsed,
+            Self::Data,
+            Self::Sender,
+            Self::Recipient,
+            Self::Nonce,
+            Self::ContractId,
+            Self::SubId,
+        ]
+    }
+
+    /// Name of the column.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::ReceiptIndex => "receipt_index",
+            Self::RootContractId => "root_contract_id",
+            Self::TxId => "tx_id",
+            Self::TxStatus => "tx_status",
+            Self::TxType => "tx_type",
+            Self::BlockHeight => "block_height",
+            Self::Pc => "pc",
+            Self::Is => "is",
+            Self::To => "to",
+            Self::ToAddress => "to_address",
+            Self::Amount => "amount",
+            Self::AssetId => "asset_id",
+            Self::Gas => "gas",
+            Self::Param1 => "param1",
+            Self::Param2 => "param2",
+            Self::Val => "val",
+            Self::Ptr => "ptr",
+            Self::Digest => "digest",
+            Self::Reason => "reason",
+            Self::Ra => "ra",
+            Self::Rb => "rb",
+            Self::Rc => "rc",
+            Self::Rd => "rd",
+            Self::Len => "len",
+            Self::ReceiptType => "receipt_type",
+            Self::Result => "result",
+            Self::GasUsed => "gas_used",
+            Self::Data => "data",
+            Self::Sender => "sender",
+            Self::Recipient => "recipient",
+            Self::Nonce => "nonce",
+            Self::ContractId => "contract_id",
+            Self::SubId => "sub_id",
+        }
+    }
+}
+
+impl std::fmt::Display for ReceiptField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Columns of the input table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum InputField {
+    #[serde(rename = "tx_id")]
+    TxId,
+    #[serde(rename = "tx_status")]
+    TxStatus,
+    #[serde(rename = "tx_type")]
+    TxType,
+    #[serde(rename = "block_height")]
+    BlockHeight,
+    #[serde(rename = "input_type")]
+    InputType,
+    #[serde(rename = "utxo_id")]
+    UtxoId,
+    #[serde(rename = "owner")]
+    Owner,
+    #[serde(rename = "amount")]
+    Amount,
+    #[serde(rename = "asset_id")]
+    AssetId,
+    #[serde(rename = "tx_pointer_block_height")]
+    TxPointerBlockHeight,
+    #[serde(rename = "tx_pointer_tx_index")]
+    TxPointerTxIndex,
+    #[serde(rename = "witness_index")]
+    WitnessIndex,
+    #[serde(rename = "predicate_gas_used")]
+    PredicateGasUsed,
+    #[serde(rename = "predicate")]
+    Predicate,
+    #[serde(rename = "predicate_data")]
+    PredicateData,
+    #[serde(rename = "balance_root")]
+    BalanceRoot,
+    #[serde(rename = "state_root")]
+    StateRoot,
+    #[serde(rename = "contract")]
+    Contract,
+    #[serde(rename = "sender")]
+    Sender,
+    #[serde(rename = "recipient")]
+    Recipient,
+    #[serde(rename = "nonce")]
+    Nonce,
+    #[serde(rename = "data")]
+    Data,
+}
+
+impl InputField {
+    /// Every column of the table, in schema order.
+    pub fn all_fields() -> &'static [Self] {
+        &[
+            Self::TxId,
+            Self::TxStatus,
+            Self::TxType,
+            Self::BlockHeight,
+            Self::InputType,
+            Self::UtxoId,
+            Self::Owner,
+            Self::Amount,
+            Self::AssetId,
+            Self::TxPointerBlockHeight,
+            Self::TxPointerTxIndex,
+            Self::WitnessIndex,
+            Self::PredicateGasUsed,
+            Self::Predicate,
+            Self::PredicateData,
+            Self::BalanceRoot,
+            Self::StateRoot,
+            Self::Contract,
+            Self::Sender,
+            Self::Recipient,
+            Self::Nonce,
+            Self::Data,
+        ]
+    }
+
+    /// Name of the column.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::TxId => "tx_id",
+            Self::TxStatus => "tx_status",
+            Self::TxType => "tx_type",
+            Self::BlockHeight => "block_height",
+            Self::InputType => "input_type",
+            Self::UtxoId => "utxo_id",
+            Self::Owner => "owner",
+            Self::Amount => "amount",
+            Self::AssetId => "asset_id",
+            Self::TxPointerBlockHeight => "tx_pointer_block_height",
+            Self::TxPointerTxIndex => "tx_pointer_tx_index",
+            Self::WitnessIndex => "witness_index",
+            Self::PredicateGasUsed => "predicate_gas_used",
+            Self::Predicate => "predicate",
+            Self::PredicateData => "predicate_data",
+            Self::BalanceRoot => "balance_root",
+            Self::StateRoot => "state_root",
+            Self::Contract => "contract",
+            Self::Sender => "sender",
+            Self::Recipient => "recipient",
+            Self::Nonce => "nonce",
+            Self::Data => "data",
+        }
+    }
+}
+
+impl std::fmt::Display for InputField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Columns of the output table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum OutputField {
+    #[serde(rename = "tx_id")]
+    TxId,
+    #[serde(rename = "tx_status")]
+    TxStatus,
+    #[serde(rename = "tx_type")]
+    TxType,
+    #[serde(rename = "block_height")]
+    BlockHeight,
+    #[serde(rename = "output_type")]
+    OutputType,
+    #[serde(rename = "to")]
+    To,
+    #[serde(rename = "amount")]
+    Amount,
+    #[serde(rename = "asset_id")]
+    AssetId,
+    #[serde(rename = "input_index")]
+    InputIndex,
+    #[serde(rename = "balance_root")]
+    BalanceRoot,
+    #[serde(rename = "state_root")]
+    StateRoot,
+    #[serde(rename = "contract")]
+    Contract,
+}
+
+impl OutputField {
+    /// Every column of the table, in schema order.
+    pub fn all_fields() -> &'static [Self] {
+        &[
+            Self::TxId,
+            Self::TxStatus,
+            Self::TxType,
+            Self::BlockHeight,
+            Self::OutputType,
+            Self::To,
+            Self::Amount,
+            Self::AssetId,
+            Self::InputIndex,
+            Self::BalanceRoot,
+            Self::StateRoot,
+            Self::Contract,
+        ]
+    }
+
+    /// Name of the column.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::TxId => "tx_id",
+            Self::TxStatus => "tx_status",
+            Self::TxType => "tx_type",
+            Self::BlockHeight => "block_height",
+            Self::OutputType => "output_type",
+            Self::To => "to",
+            Self::Amount => "amount",
+            Self::AssetId => "asset_id",
+            Self::InputIndex => "input_index",
+            Self::BalanceRoot => "balance_root",
+            Self::StateRoot => "state_root",
+            Self::Contract => "contract",
+        }
+    }
+}
+
+impl std::fmt::Display for OutputField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! assert_fields {
+        ($ty:ty, $schema:ident) => {
+            assert_eq!(
+                <$ty>::all_fields()
+                    .iter()
+                    .map(|f| f.name())
+                    .collect::<Vec<_>>(),
+                hyperfuel_schema::$schema()
+                    .fields
+                    .iter()
+                    .map(|f| f.name.as_str())
+                    .collect::<Vec<_>>()
+            )
+        };
+    }
+
+    #[test]
+    fn test_fields_match_schema() {
+        assert_fields!(BlockField, block_header);
+        assert_fields!(TransactionField, transaction);
+        assert_fields!(ReceiptField, receipt);
+        assert_fields!(InputField, input);
+        assert_fields!(OutputField, output);
+    }
+}
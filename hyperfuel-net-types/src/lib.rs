@@ -3,6 +3,16 @@ use std::collections::BTreeSet;
 use hyperfuel_format::{FixedSizeData, Hash};
 use serde::{Deserialize, Serialize};
 
+mod binary_query;
+mod builder;
+mod error;
+mod fields;
+mod validate;
+
+pub use builder::QueryBuilder;
+pub use error::QueryError;
+pub use fields::{BlockField, InputField, OutputField, ReceiptField, TransactionField};
+
 pub type Sighash = FixedSizeData<4>;
 
 pub mod hyperfuel_net_types_capnp {
@@ -36,8 +46,10 @@ pub struct ReceiptSelection {
     pub rc: Vec<u64>,
     #[serde(default)]
     pub rd: Vec<u64>,
+    /// `TransactionStatus` codes, see `TransactionStatus::to_u8`.
     #[serde(default)]
     pub tx_status: Vec<u8>,
+    /// `TransactionType` codes, see `TransactionType::to_u8`.
     #[serde(default)]
     pub tx_type: Vec<u8>,
 }
@@ -57,8 +69,10 @@ pub struct InputSelection {
     pub recipient: Vec<Hash>,
     #[serde(default)]
     pub input_type: Vec<u8>,
+    /// `TransactionStatus` codes, see `TransactionStatus::to_u8`.
     #[serde(default)]
     pub tx_status: Vec<u8>,
+    /// `TransactionType` codes, see `TransactionType::to_u8`.
     #[serde(default)]
     pub tx_type: Vec<u8>,
 }
@@ -74,8 +88,10 @@ pub struct OutputSelection {
     pub contract: Vec<Hash>,
     #[serde(default)]
     pub output_type: Vec<u8>,
+    /// `TransactionStatus` codes, see `TransactionStatus::to_u8`.
     #[serde(default)]
     pub tx_status: Vec<u8>,
+    /// `TransactionType` codes, see `TransactionType::to_u8`.
     #[serde(default)]
     pub tx_type: Vec<u8>,
 }
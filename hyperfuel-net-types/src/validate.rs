@@ -0,0 +1,463 @@
+use std::fmt::Display;
+
+use hyperfuel_format::{InputType, OutputType, ReceiptType, TransactionStatus, TransactionType};
+
+use crate::{FieldSelection, InputSelection, OutputSelection, Query, QueryError, ReceiptSelection};
+
+impl Query {
+    /// Checks the query for mistakes that the server would reject or that would silently
+    /// return no data.
+    ///
+    /// This checks that the block range isn't empty, that every selected field exists in the
+    /// schema, that every type and status code is known, and that no selection filters on a
+    /// field that none of its types have, like `rb` on `Call` receipts.
+    pub fn validate(&self) -> Result<(), QueryError> {
+        self.validate_impl(false)
+    }
+
+    /// Like [`Query::validate`], but allows type and status codes that this version of the
+    /// library doesn't know, for codes that the server added since. Unknown types are assumed to
+    /// have every field.
+    pub fn validate_lenient(&self) -> Result<(), QueryError> {
+        self.validate_impl(true)
+    }
+
+    fn validate_impl(&self, allow_unknown: bool) -> Result<(), QueryError> {
+        if let Some(to_block) = self.to_block {
+            if to_block <= self.from_block {
+                return Err(QueryError::InvalidBlockRange {
+                    from_block: self.from_block,
+                    to_block,
+                });
+            }
+        }
+
+        self.field_selection.validate()?;
+        for (index, selection) in self.receipts.iter().enumerate() {
+            selection.validate(index, allow_unknown)?;
+        }
+        for (index, selection) in self.inputs.iter().enumerate() {
+            selection.validate(index, allow_unknown)?;
+        }
+        for (index, selection) in self.outputs.iter().enumerate() {
+            selection.validate(index, allow_unknown)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FieldSelection {
+    fn validate(&self) -> Result<(), QueryError> {
+        let tables = [
+            ("block", hyperfuel_schema::block_header(), &self.block),
+            (
+                "transaction",
+                hyperfuel_schema::transaction(),
+                &self.transaction,
+            ),
+            ("receipt", hyperfuel_schema::receipt(), &self.receipt),
+            ("input", hyperfuel_schema::input(), &self.input),
+            ("output", hyperfuel_schema::output(), &self.output),
+        ];
+
+        for (table, schema, fields) in tables {
+            hyperfuel_schema::try_project_schema(&schema, fields).map_err(|e| {
+                QueryError::UnknownFields {
+                    table,
+                    message: e.to_string(),
+                }
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ReceiptSelection {
+    fn validate(&self, index: usize, allow_unknown: bool) -> Result<(), QueryError> {
+        use ReceiptType::*;
+
+        let table = "receipt";
+        check_tx_codes(table, index, &self.tx_status, &self.tx_type, allow_unknown)?;
+        let types = parse_codes(
+            table,
+            index,
+            "receipt_type",
+            &self.receipt_type,
+            ReceiptType::from_u8,
+            |t| allow_unknown || !matches!(t, Unknown(_)),
+        )?;
+
+        check_matchable(
+            table,
+            index,
+            &types,
+            |t| matches!(t, Unknown(_)),
+            &[
+                ("to", !self.to.is_empty(), &[Call, Transfer]),
+                ("to_address", !self.to_address.is_empty(), &[TransferOut]),
+                (
+                    "asset_id",
+                    !self.asset_id.is_empty(),
+                    &[Call, Transfer, TransferOut],
+                ),
+                ("sender", !self.sender.is_empty(), &[MessageOut]),
+                ("recipient", !self.recipient.is_empty(), &[MessageOut]),
+                (
+                    "contract_id",
+                    !self.contract_id.is_empty(),
+                    &[
+                        Call,
+                        Return,
+                        ReturnData,
+                        Panic,
+                        Revert,
+                        Log,
+                        LogData,
+                        Transfer,
+                        TransferOut,
+                        Mint,
+                        Burn,
+                    ],
+                ),
+                ("ra", !self.ra.is_empty(), &[Revert, Log, LogData]),
+                ("rb", !self.rb.is_empty(), &[Log, LogData]),
+                ("rc", !self.rc.is_empty(), &[Log]),
+                ("rd", !self.rd.is_empty(), &[Log]),
+            ],
+        )
+    }
+}
+
+impl InputSelection {
+    fn validate(&self, index: usize, allow_unknown: bool) -> Result<(), QueryError> {
+        use InputType::*;
+
+        let table = "input";
+        check_tx_codes(table, index, &self.tx_status, &self.tx_type, allow_unknown)?;
+        let types = parse_codes(
+            table,
+            index,
+            "input_type",
+            &self.input_type,
+            InputType::from_u8,
+            |t| allow_unknown || !matches!(t, Unknown(_)),
+        )?;
+
+        check_matchable(
+            table,
+            index,
+            &types,
+            |t| matches!(t, Unknown(_)),
+            &[
+                ("owner", !self.owner.is_empty(), &[InputCoin]),
+                ("asset_id", !self.asset_id.is_empty(), &[InputCoin]),
+                ("contract", !self.contract.is_empty(), &[InputContract]),
+                ("sender", !self.sender.is_empty(), &[InputMessage]),
+                ("recipient", !self.recipient.is_empty(), &[InputMessage]),
+            ],
+        )
+    }
+}
+
+impl OutputSelection {
+    fn validate(&self, index: usize, allow_unknown: bool) -> Result<(), QueryError> {
+        use OutputType::*;
+
+        let table = "output";
+        check_tx_codes(table, index, &self.tx_status, &self.tx_type, allow_unknown)?;
+        let types = parse_codes(
+            table,
+            index,
+            "output_type",
+            &self.output_type,
+            OutputType::from_u8,
+            |t| allow_unknown || !matches!(t, Unknown(_)),
+        )?;
+
+        check_matchable(
+            table,
+            index,
+            &types,
+            |t| matches!(t, Unknown(_)),
+            &[
+                (
+                    "to",
+                    !self.to.is_empty(),
+                    &[CoinOutput, ChangeOutput, VariableOutput],
+                ),
+                (
+                    "asset_id",
+                    !self.asset_id.is_empty(),
+                    &[CoinOutput, ChangeOutput, VariableOutput],
+                ),
+                ("contract", !self.contract.is_empty(), &[ContractCreated]),
+            ],
+        )
+    }
+}
+
+/// Converts `codes` with `from_u8`, erroring on the first code that isn't `allowed`.
+fn parse_codes<T>(
+    table: &'static str,
+    index: usize,
+    field: &'static str,
+    codes: &[u8],
+    from_u8: impl Fn(u8) -> T,
+    allowed: impl Fn(&T) -> bool,
+) -> Result<Vec<T>, QueryError> {
+    codes
+        .iter()
+        .map(|&code| {
+            let value = from_u8(code);
+            if !allowed(&value) {
+                return Err(QueryError::UnknownCode {
+                    table,
+                    index,
+                    field,
+                    code,
+                });
+            }
+            Ok(value)
+        })
+        .collect()
+}
+
+fn check_tx_codes(
+    table: &'static str,
+    index: usize,
+    tx_status: &[u8],
+    tx_type: &[u8],
+    allow_unknown: bool,
+) -> Result<(), QueryError> {
+    parse_codes(
+        table,
+        index,
+        "tx_status",
+        tx_status,
+        TransactionStatus::from_u8,
+        |s| allow_unknown || !matches!(s, TransactionStatus::Unknown(_)),
+    )?;
+    parse_codes(
+        table,
+        index,
+        "tx_type",
+        tx_type,
+        TransactionType::from_u8,
+        |t| allow_unknown || !matches!(t, TransactionType::Unknown(_)),
+    )?;
+
+    Ok(())
+}
+
+/// Errors if a selection is limited to `types` and filters on a field that none of them have.
+///
+/// `filters` are the field names, whether the selection filters on them and the types that have
+/// them. No types means any type matches, unknown types are assumed to have every field since
+/// they are only allowed by [`Query::validate_lenient`].
+fn check_matchable<T: PartialEq + Display>(
+    table: &'static str,
+    index: usize,
+    types: &[T],
+    is_unknown: impl Fn(&T) -> bool,
+    filters: &[(&'static str, bool, &[T])],
+) -> Result<(), QueryError> {
+    if types.is_empty() {
+        return Ok(());
+    }
+
+    for &(field, is_set, has_field) in filters {
+        if is_set && !types.iter().any(|t| is_unknown(t) || has_field.contains(t)) {
+            return Err(QueryError::NeverMatches {
+                table,
+                index,
+                field,
+                types: types
+                    .iter()
+                    .map(|t| t.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query() -> Query {
+        Query {
+            from_block: 10,
+            to_block: Some(20),
+            field_selection: FieldSelection {
+                receipt: ["tx_id", "rb", "data"].map(String::from).into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut query = query();
+        query.receipts = vec![ReceiptSelection::default()
+            .receipt_type([ReceiptType::Log, ReceiptType::LogData])
+            .rb([7])
+            .tx_status([TransactionStatus::Success])];
+        query.inputs = vec![InputSelection::default()
+            .owner([[1; 32]])
+            .input_type([InputType::InputCoin, InputType::InputMessage])];
+        query.outputs = vec![OutputSelection::default().contract([[2; 32]])];
+
+        query.validate().unwrap();
+    }
+
+    #[test]
+    fn test_validate_block_range() {
+        let mut query = query();
+        query.to_block = Some(10);
+        assert_eq!(
+            query.validate().unwrap_err(),
+            QueryError::InvalidBlockRange {
+                from_block: 10,
+                to_block: 10
+            }
+        );
+
+        query.to_block = None;
+        query.validate().unwrap();
+    }
+
+    #[test]
+    fn test_validate_fields() {
+        let mut query = query();
+        query.field_selection.transaction.insert("tx_idd".into());
+
+        let err = query.validate().unwrap_err();
+        assert!(matches!(
+            err,
+            QueryError::UnknownFields {
+                table: "transaction",
+                ..
+            }
+        ));
+        assert!(err.to_string().contains("tx_idd"));
+    }
+
+    #[test]
+    fn test_validate_codes() {
+        let mut query = query();
+        query.receipts = vec![ReceiptSelection {
+            receipt_type: vec![5, 13],
+            ..Default::default()
+        }];
+        assert_eq!(
+            query.validate().unwrap_err(),
+            QueryError::UnknownCode {
+                table: "receipt",
+                index: 0,
+                field: "receipt_type",
+                code: 13
+            }
+        );
+
+        query.receipts.clear();
+        query.outputs = vec![
+            OutputSelection::default(),
+            OutputSelection {
+                tx_status: vec![9],
+                ..Default::default()
+            },
+        ];
+        assert_eq!(
+            query.validate().unwrap_err(),
+            QueryError::UnknownCode {
+                table: "output",
+                index: 1,
+                field: "tx_status",
+                code: 9
+            }
+        );
+
+        query.outputs[1].tx_status.clear();
+        query.outputs[1].tx_type = vec![200];
+        assert!(matches!(
+            query.validate().unwrap_err(),
+            QueryError::UnknownCode {
+                field: "tx_type",
+                code: 200,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_validate_lenient() {
+        let mut query = query();
+        query.receipts = vec![ReceiptSelection {
+            receipt_type: vec![5, 13],
+            ..Default::default()
+        }];
+        query.validate_lenient().unwrap();
+
+        // unknown types may have the field.
+        query.receipts[0].rb = vec![7];
+        query.receipts[0].receipt_type = vec![0, 13];
+        query.validate_lenient().unwrap();
+        query.receipts[0].receipt_type = vec![0];
+        assert!(matches!(
+            query.validate_lenient().unwrap_err(),
+            QueryError::NeverMatches { field: "rb", .. }
+        ));
+
+        query.receipts.clear();
+        query.outputs = vec![OutputSelection {
+            tx_status: vec![9],
+            tx_type: vec![200],
+            ..Default::default()
+        }];
+        query.validate_lenient().unwrap();
+
+        // the checks that don't depend on codes still apply
+        query.to_block = Some(5);
+        assert!(matches!(
+            query.validate_lenient().unwrap_err(),
+            QueryError::InvalidBlockRange { .. }
+        ));
+    }
+
+    #[test]
+    fn test_validate_never_matches() {
+        let mut query = query();
+        query.receipts = vec![ReceiptSelection::default()
+            .receipt_type([ReceiptType::Call, ReceiptType::Return])
+            .rb([7])];
+
+        let err = query.validate().unwrap_err();
+        assert_eq!(
+            err,
+            QueryError::NeverMatches {
+                table: "receipt",
+                index: 0,
+                field: "rb",
+                types: "Call, Return".into()
+            }
+        );
+
+        query.receipts.clear();
+        query.inputs = vec![InputSelection::default()
+            .contract([[1; 32]])
+            .input_type([InputType::InputCoin])];
+        assert!(matches!(
+            query.validate().unwrap_err(),
+            QueryError::NeverMatches {
+                field: "contract",
+                ..
+            }
+        ));
+    }
+}